@@ -13,6 +13,10 @@ you want to test the diesel integration then we need a running postgres
 instance. Assuming that you have docker running locally and are in bash you can
 do `./run-tests.sh` to execute all tests.
 
+The `odbc` feature links against the system ODBC driver manager, if
+unixODBC isn't installed then test it with
+`cargo test --features "odbc odbc-api/vendored-unix-odbc"`.
+
 ## License
 
 Licensed under either of
@@ -67,3 +67,22 @@ fn uuidb64_to_buf_new_string_and_id_per_loop(b: &mut Bencher) {
         black_box(&buf);
     });
 }
+
+#[bench]
+fn uuidb64_encode_many_into(b: &mut Bencher) {
+    let ids = UuidB64::new_batch(1000);
+    let mut buf = String::new();
+
+    b.iter(|| {
+        buf.clear();
+        UuidB64::encode_many_into(&ids, &mut buf);
+        black_box(&buf);
+    });
+}
+
+#[bench]
+fn uuidb64_decode_many(b: &mut Bencher) {
+    let packed = UuidB64::encode_many(&UuidB64::new_batch(1000));
+
+    b.iter(|| black_box(UuidB64::decode_many(&packed).unwrap()));
+}
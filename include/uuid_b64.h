@@ -0,0 +1,42 @@
+/* C bindings for uuid-b64, built with the `ffi` feature */
+
+#ifndef UUID_B64_H
+#define UUID_B64_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/* The buffer size uuidb64_format needs: 22 characters and a NUL */
+#define UUIDB64_FORMAT_LEN 23
+
+#define UUIDB64_OK 0
+#define UUIDB64_NULL_POINTER 1
+#define UUIDB64_INVALID_LENGTH 2
+#define UUIDB64_INVALID_CHARACTER 3
+#define UUIDB64_INVALID_UUID_BYTES 4
+
+/* An ID as its 16 raw bytes, in the same order as uuid_t */
+typedef struct uuidb64_t {
+    uint8_t bytes[16];
+} uuidb64_t;
+
+/* Generate a new v4 ID */
+uuidb64_t uuidb64_new(void);
+
+/* Parse len bytes of Base64 (not necessarily NUL-terminated) into *out,
+ * returning UUIDB64_OK or an error code without touching *out */
+int uuidb64_parse(const char *text, size_t len, uuidb64_t *out);
+
+/* Write the Base64 form of *id to out, which must have room for
+ * UUIDB64_FORMAT_LEN bytes, NUL-terminated */
+int uuidb64_format(const uuidb64_t *id, char *out);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* UUID_B64_H */
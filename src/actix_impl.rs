@@ -0,0 +1,75 @@
+//! actix-web's `FromRequest` for `IdPath`, and `ResponseError` for its
+//! rejection
+
+extern crate actix_web;
+
+use std::future::{ready, Ready};
+
+use self::actix_web::dev::Payload;
+use self::actix_web::http::{header, StatusCode};
+use self::actix_web::{FromRequest, HttpRequest, HttpResponse, ResponseError};
+
+use web::{from_params, IdPath, IdRejection};
+
+impl FromRequest for IdPath {
+    type Error = IdRejection;
+    type Future = Ready<Result<IdPath, IdRejection>>;
+
+    fn from_request(req: &HttpRequest, _: &mut Payload) -> Self::Future {
+        ready(from_params(req.match_info().iter().map(|(_, value)| value)))
+    }
+}
+
+impl ResponseError for IdRejection {
+    fn status_code(&self) -> StatusCode {
+        StatusCode::from_u16(self.status()).expect("rejections use valid statuses")
+    }
+
+    fn error_response(&self) -> HttpResponse {
+        HttpResponse::build(self.status_code())
+            .insert_header((header::CONTENT_TYPE, "application/json"))
+            .body(self.body())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::actix_web::http::StatusCode;
+    use super::actix_web::test::TestRequest;
+    use super::actix_web::{FromRequest, ResponseError};
+
+    use web::{IdPath, IdRejection};
+
+    #[test]
+    fn extracts_ids() {
+        let req = TestRequest::default()
+            .param("id", "sMHuhm9GTxuNi3hJ51287g")
+            .to_http_request();
+        let IdPath(id) = IdPath::extract(&req).into_inner().unwrap();
+        assert_eq!(
+            id.uuid().hyphenated().to_string(),
+            "b0c1ee86-6f46-4f1b-8d8b-7849e75dbcee"
+        );
+    }
+
+    #[test]
+    fn rejects_malformed_ids() {
+        let req = TestRequest::default().param("id", "nope").to_http_request();
+        let rejection = IdPath::extract(&req).into_inner().unwrap_err();
+        assert_eq!(rejection, IdRejection::Invalid("nope".into()));
+        let response = rejection.error_response();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(
+            response.headers().get("content-type").unwrap(),
+            "application/json"
+        );
+    }
+
+    #[test]
+    fn rejects_bad_routes() {
+        let req = TestRequest::default().to_http_request();
+        let rejection = IdPath::extract(&req).into_inner().unwrap_err();
+        assert_eq!(rejection, IdRejection::WrongParamCount(0));
+        assert_eq!(rejection.status_code(), StatusCode::INTERNAL_SERVER_ERROR);
+    }
+}
@@ -0,0 +1,89 @@
+//! aide's `OperationInput` for `IdPath`, so handlers taking it can be
+//! routed with `ApiRouter` and document their path parameter
+
+extern crate aide;
+
+use self::aide::generate::GenContext;
+use self::aide::openapi::{
+    Operation, Parameter, ParameterData, ParameterSchemaOrContent, PathStyle, SchemaObject,
+};
+use self::aide::operation::add_parameters;
+use self::aide::OperationInput;
+
+use web::IdPath;
+use UuidB64;
+
+/// Documented as a required path parameter named `id`, with `UuidB64`'s
+/// schema
+///
+/// aide doesn't tell extractors the names of the route's parameters, so
+/// routes should call the segment `{id}` for the spec to match them.
+impl OperationInput for IdPath {
+    fn operation_input(ctx: &mut GenContext, operation: &mut Operation) {
+        let json_schema = ctx.schema.subschema_for::<UuidB64>();
+        let id = Parameter::Path {
+            parameter_data: ParameterData {
+                name: "id".into(),
+                description: None,
+                required: true,
+                deprecated: None,
+                format: ParameterSchemaOrContent::Schema(SchemaObject {
+                    json_schema,
+                    external_docs: None,
+                    example: None,
+                }),
+                example: None,
+                examples: Default::default(),
+                explode: None,
+                extensions: Default::default(),
+            },
+            style: PathStyle::Simple,
+        };
+        add_parameters(ctx, operation, vec![id]);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::future::{ready, Future};
+
+    use super::aide::axum::routing::get;
+    use super::aide::axum::ApiRouter;
+    use super::aide::openapi::{OpenApi, Parameter, ParameterSchemaOrContent, ReferenceOr};
+
+    use web::IdPath;
+
+    fn echo(IdPath(id): IdPath) -> impl Future<Output = String> {
+        ready(id.to_string())
+    }
+
+    #[test]
+    fn documents_the_path_parameter() {
+        let mut api = OpenApi::default();
+        let _router = ApiRouter::<()>::new()
+            .api_route("/users/{id}", get(echo))
+            .finish_api(&mut api);
+
+        let paths = api.paths.unwrap();
+        let operation = match paths.paths["/users/{id}"] {
+            ReferenceOr::Item(ref item) => item.get.clone().unwrap(),
+            ref other => panic!("expected a path item, got {:?}", other),
+        };
+        assert_eq!(operation.parameters.len(), 1);
+        match operation.parameters[0] {
+            ReferenceOr::Item(Parameter::Path {
+                ref parameter_data, ..
+            }) => {
+                assert_eq!(parameter_data.name, "id");
+                assert!(parameter_data.required);
+                match parameter_data.format {
+                    ParameterSchemaOrContent::Schema(ref s) => {
+                        assert_eq!(s.json_schema.get("format").unwrap(), "uuid-b64");
+                    }
+                    ref other => panic!("expected a schema, got {:?}", other),
+                }
+            }
+            ref other => panic!("expected a path parameter, got {:?}", other),
+        }
+    }
+}
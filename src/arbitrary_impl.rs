@@ -0,0 +1,39 @@
+//! Fuzzing with structured input
+
+extern crate arbitrary;
+
+use self::arbitrary::{Arbitrary, Result, Unstructured};
+
+use UuidB64;
+
+/// Any 16 bytes, so fuzzers also reach IDs that aren't valid v4 or v7 UUIDs
+impl<'a> Arbitrary<'a> for UuidB64 {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<UuidB64> {
+        <[u8; 16]>::arbitrary(u).map(UuidB64::from_bytes)
+    }
+
+    fn size_hint(_depth: usize) -> (usize, Option<usize>) {
+        (16, Some(16))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::arbitrary::{Arbitrary, Unstructured};
+
+    use UuidB64;
+
+    #[test]
+    fn takes_16_bytes() {
+        let id: UuidB64 = "sMHuhm9GTxuNi3hJ51287g".parse().unwrap();
+        let mut data = id.uuid().as_bytes().to_vec();
+        data.extend_from_slice(&[0xff; 20]);
+        let mut u = Unstructured::new(&data);
+        assert_eq!(UuidB64::arbitrary(&mut u).unwrap(), id);
+        assert_eq!(UuidB64::arbitrary(&mut u).unwrap(), UuidB64::MAX);
+        assert_eq!(u.len(), 4);
+
+        let ids = <Vec<UuidB64>>::arbitrary_take_rest(Unstructured::new(&[0; 40])).unwrap();
+        assert!(ids.iter().all(|id| id.is_nil()));
+    }
+}
@@ -0,0 +1,91 @@
+//! Arrow arrays of IDs
+//!
+//! IDs are stored as `FixedSizeBinary(16)`, the same as Parquet's `UUID`
+//! columns, so an array built here can be written to Parquet directly.
+//!
+//! ```rust,ignore
+//! let ids = UuidB64::to_arrow_array(&ids);
+//! let batch = RecordBatch::try_from_iter(vec![("id", Arc::new(ids) as ArrayRef)])?;
+//! writer.write(&batch)?;
+//! ```
+
+extern crate arrow_array;
+
+use self::arrow_array::FixedSizeBinaryArray;
+use uuid::Uuid;
+
+use errors::ErrorKind;
+use UuidB64;
+
+impl UuidB64 {
+    /// A `FixedSizeBinary(16)` array of the IDs, with no nulls
+    pub fn to_arrow_array(ids: &[UuidB64]) -> FixedSizeBinaryArray {
+        FixedSizeBinaryArray::try_from_sparse_iter_with_size(
+            ids.iter().map(|id| Some(id.0.as_bytes())),
+            16,
+        )
+        .expect("UUIDs are 16 bytes")
+    }
+
+    /// The IDs in a `FixedSizeBinary(16)` array
+    ///
+    /// Fails if the array holds values of any other size, or has nulls.
+    pub fn from_arrow_array(array: &FixedSizeBinaryArray) -> Result<Vec<UuidB64>, ErrorKind> {
+        if array.value_length() != 16 {
+            return Err(ErrorKind::InvalidBinary(format!(
+                "expected 16-byte values, got {}",
+                array.value_length()
+            )));
+        }
+        array
+            .iter()
+            .enumerate()
+            .map(|(i, bytes)| match bytes {
+                Some(bytes) => Ok(UuidB64(
+                    Uuid::from_bytes(bytes).expect("UUIDs are 16 bytes"),
+                )),
+                None => Err(ErrorKind::InvalidBinary(format!("null at index {}", i))),
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::arrow_array::{Array, FixedSizeBinaryArray};
+
+    use UuidB64;
+
+    #[test]
+    fn roundtrips() {
+        let ids = vec![UuidB64::new(), UuidB64::new(), UuidB64::new()];
+        let array = UuidB64::to_arrow_array(&ids);
+        assert_eq!(array.value_length(), 16);
+        assert_eq!(array.len(), 3);
+        assert_eq!(array.value(1), ids[1].uuid().as_bytes());
+        assert_eq!(UuidB64::from_arrow_array(&array).unwrap(), ids);
+        assert_eq!(
+            UuidB64::from_arrow_array(&array.slice(1, 2)).unwrap(),
+            &ids[1..]
+        );
+    }
+
+    #[test]
+    fn empty() {
+        let array = UuidB64::to_arrow_array(&[]);
+        assert_eq!(array.value_length(), 16);
+        assert!(UuidB64::from_arrow_array(&array).unwrap().is_empty());
+    }
+
+    #[test]
+    fn rejects_other_arrays() {
+        let short = FixedSizeBinaryArray::try_from_iter(vec![[0u8; 8]].into_iter()).unwrap();
+        assert!(UuidB64::from_arrow_array(&short).is_err());
+        let nulls = FixedSizeBinaryArray::try_from_sparse_iter_with_size(
+            vec![Some([0u8; 16]), None].into_iter(),
+            16,
+        )
+        .unwrap();
+        assert!(UuidB64::from_arrow_array(&nulls).is_err());
+    }
+}
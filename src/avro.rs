@@ -0,0 +1,189 @@
+//! Apache Avro values and schemas
+//!
+//! Avro has two ways to carry a UUID:
+//!
+//! * [`fixed_schema`](fn.fixed_schema.html), a `fixed` of 16 bytes. This is
+//!   what `AvroSchema` gives `UuidB64` fields, matching its serde
+//!   serialization (raw bytes, since Avro isn't human-readable). Write
+//!   values made by [`to_avro_fixed`](../struct.UuidB64.html#method.to_avro_fixed).
+//! * [`uuid_schema`](fn.uuid_schema.html), a `string` with the `uuid`
+//!   logical type, holding the hyphenated form, for consumers that expect
+//!   that. Write the `Value::Uuid` that `UuidB64` converts `into`.
+//!
+//! [`UuidB64::from_avro_value`](../struct.UuidB64.html#method.from_avro_value)
+//! reads either.
+//!
+//! ```
+//! # extern crate apache_avro;
+//! # extern crate uuid_b64;
+//! # use apache_avro::{from_avro_datum, to_avro_datum};
+//! # use uuid_b64::{avro, UuidB64};
+//! # fn main() {
+//! let id = UuidB64::new();
+//! let schema = avro::fixed_schema();
+//! let datum = to_avro_datum(&schema, id.to_avro_fixed()).unwrap();
+//! assert_eq!(datum.len(), 16);
+//! let value = from_avro_datum(&schema, &mut &datum[..], None).unwrap();
+//! assert_eq!(UuidB64::from_avro_value(&value).unwrap(), id);
+//! # }
+//! ```
+
+extern crate apache_avro;
+
+use std::collections::{BTreeMap, HashMap};
+
+use self::apache_avro::schema::derive::AvroSchemaComponent;
+use self::apache_avro::schema::{FixedSchema, Name, Namespace};
+use self::apache_avro::types::Value;
+use self::apache_avro::Schema;
+
+use errors::ErrorKind;
+use UuidB64;
+
+/// A `fixed` schema named `UuidB64`, 16 bytes long
+pub fn fixed_schema() -> Schema {
+    Schema::Fixed(FixedSchema {
+        name: Name::new("UuidB64").expect("UuidB64 is a valid name"),
+        aliases: None,
+        doc: None,
+        size: 16,
+        default: None,
+        attributes: BTreeMap::new(),
+    })
+}
+
+/// A `string` schema with the `uuid` logical type
+pub fn uuid_schema() -> Schema {
+    Schema::Uuid
+}
+
+impl From<UuidB64> for Value {
+    fn from(id: UuidB64) -> Value {
+        Value::Uuid(apache_avro::Uuid::from_bytes(*id.0.as_bytes()))
+    }
+}
+
+impl UuidB64 {
+    /// A `Value::Fixed` of the 16 bytes, for writing with
+    /// [`fixed_schema`](avro/fn.fixed_schema.html)
+    pub fn to_avro_fixed(&self) -> Value {
+        Value::Fixed(16, self.0.as_bytes().to_vec())
+    }
+
+    /// Read an ID from an Avro value
+    ///
+    /// Accepts `Value::Uuid`, 16-byte `Fixed` or `Bytes` values, and
+    /// strings in any of the forms that
+    /// [`parse_any`](struct.UuidB64.html#method.parse_any) does.
+    ///
+    /// (This can't be `TryFrom<Value>`, which would overlap with the `From`
+    /// impl for everything that converts into a `Uuid`.)
+    pub fn from_avro_value(value: &Value) -> Result<UuidB64, ErrorKind> {
+        match *value {
+            Value::Uuid(ref uuid) => Ok(UuidB64::from_slice(uuid.as_bytes())?),
+            Value::Fixed(_, ref bytes) | Value::Bytes(ref bytes) => UuidB64::from_slice(bytes),
+            Value::String(ref s) => UuidB64::parse_any(s),
+            ref other => Err(ErrorKind::InvalidBinary(format!(
+                "expected a UUID, found {:?}",
+                other
+            ))),
+        }
+    }
+}
+
+impl AvroSchemaComponent for UuidB64 {
+    fn get_schema_in_ctxt(
+        named_schemas: &mut HashMap<Name, Schema>,
+        enclosing_namespace: &Namespace,
+    ) -> Schema {
+        let name = Name::new("UuidB64")
+            .expect("UuidB64 is a valid name")
+            .fully_qualified_name(enclosing_namespace);
+        if named_schemas.contains_key(&name) {
+            return Schema::Ref { name };
+        }
+        named_schemas.insert(name.clone(), Schema::Ref { name: name.clone() });
+        match fixed_schema() {
+            Schema::Fixed(fixed) => Schema::Fixed(FixedSchema { name, ..fixed }),
+            _ => unreachable!("fixed_schema is fixed"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::apache_avro::schema::derive::AvroSchemaComponent;
+    use super::apache_avro::schema::AvroSchema;
+    use super::apache_avro::types::Value;
+    use super::apache_avro::{from_avro_datum, from_value, to_avro_datum, to_value, Schema};
+    use super::{fixed_schema, uuid_schema};
+
+    use UuidB64;
+
+    fn roundtrip(schema: &Schema, value: Value) -> (usize, UuidB64) {
+        let datum = to_avro_datum(schema, value).unwrap();
+        let value = from_avro_datum(schema, &mut &datum[..], None).unwrap();
+        (datum.len(), UuidB64::from_avro_value(&value).unwrap())
+    }
+
+    #[test]
+    fn both_schemas_roundtrip() {
+        let id = UuidB64::new();
+        assert_eq!(roundtrip(&fixed_schema(), id.to_avro_fixed()), (16, id));
+        // a length byte and the 36-character hyphenated form
+        assert_eq!(roundtrip(&uuid_schema(), id.into()), (1 + 36, id));
+    }
+
+    #[test]
+    fn reads_other_values() {
+        let id: UuidB64 = "sMHuhm9GTxuNi3hJ51287g".parse().unwrap();
+        for value in &[
+            Value::Bytes(id.uuid().as_bytes().to_vec()),
+            Value::String("sMHuhm9GTxuNi3hJ51287g".into()),
+            Value::String("b0c1ee86-6f46-4f1b-8d8b-7849e75dbcee".into()),
+        ] {
+            assert_eq!(UuidB64::from_avro_value(value).unwrap(), id);
+        }
+        assert!(UuidB64::from_avro_value(&Value::Fixed(4, vec![0; 4])).is_err());
+        assert!(UuidB64::from_avro_value(&Value::Int(4)).is_err());
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Event {
+        id: UuidB64,
+        parent: UuidB64,
+    }
+
+    #[test]
+    fn serde_matches_schema() {
+        assert_eq!(UuidB64::get_schema(), fixed_schema());
+        let schema = Schema::parse_str(
+            r#"{"type": "record", "name": "Event", "fields": [
+                {"name": "id", "type": {"type": "fixed", "name": "UuidB64", "size": 16}},
+                {"name": "parent", "type": "UuidB64"}
+            ]}"#,
+        )
+        .unwrap();
+        let event = Event {
+            id: UuidB64::new(),
+            parent: UuidB64::new(),
+        };
+        let datum = to_avro_datum(&schema, to_value(&event).unwrap()).unwrap();
+        assert_eq!(datum.len(), 32);
+        let value = from_avro_datum(&schema, &mut &datum[..], None).unwrap();
+        assert_eq!(from_value::<Event>(&value).unwrap(), event);
+    }
+
+    #[test]
+    fn repeated_fields_refer_to_the_first() {
+        let mut names = HashMap::new();
+        let first = UuidB64::get_schema_in_ctxt(&mut names, &None);
+        assert_eq!(first, fixed_schema());
+        match UuidB64::get_schema_in_ctxt(&mut names, &None) {
+            Schema::Ref { ref name } => assert_eq!(name.name, "UuidB64"),
+            other => panic!("expected a reference, got {:?}", other),
+        }
+    }
+}
@@ -0,0 +1,134 @@
+//! axum's `FromRequestParts` for `IdPath`, and `IntoResponse` for its
+//! rejection
+
+extern crate axum;
+
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use self::axum::extract::rejection::RawPathParamsRejection;
+use self::axum::extract::{FromRequestParts, RawPathParams};
+use self::axum::http::request::Parts;
+use self::axum::http::{header, StatusCode, Uri};
+use self::axum::response::{IntoResponse, Response};
+
+use web::{from_params, IdPath, IdRejection};
+
+impl<S: Send + Sync> FromRequestParts<S> for IdPath {
+    type Rejection = IdRejection;
+
+    fn from_request_parts(
+        parts: &mut Parts,
+        state: &S,
+    ) -> impl Future<Output = Result<Self, IdRejection>> + Send {
+        Extract {
+            uri: parts.uri.clone(),
+            params: Box::pin(RawPathParams::from_request_parts(parts, state)),
+        }
+    }
+}
+
+/// Parses the ID once axum has handed over the path parameters
+struct Extract<F> {
+    /// For reporting parameters that aren't UTF-8 once decoded
+    uri: Uri,
+    params: Pin<Box<F>>,
+}
+
+impl<F> Future for Extract<F>
+where
+    F: Future<Output = Result<RawPathParams, RawPathParamsRejection>>,
+{
+    type Output = Result<IdPath, IdRejection>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let params = match self.params.as_mut().poll(cx) {
+            Poll::Ready(params) => params,
+            Poll::Pending => return Poll::Pending,
+        };
+        Poll::Ready(match params {
+            Ok(params) => from_params(params.iter().map(|(_, value)| value)),
+            Err(RawPathParamsRejection::MissingPathParams(_)) => {
+                Err(IdRejection::WrongParamCount(0))
+            }
+            Err(_) => Err(IdRejection::Invalid(self.uri.path().to_owned())),
+        })
+    }
+}
+
+impl IntoResponse for IdRejection {
+    fn into_response(self) -> Response {
+        let status = StatusCode::from_u16(self.status()).expect("rejections use valid statuses");
+        (
+            status,
+            [(header::CONTENT_TYPE, "application/json")],
+            self.body(),
+        )
+            .into_response()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate futures;
+    extern crate tower_service;
+
+    use std::future::ready;
+
+    use self::futures::executor::block_on;
+    use self::tower_service::Service;
+    use super::axum::body::{to_bytes, Body};
+    use super::axum::http::{Request, StatusCode};
+    use super::axum::routing::get;
+    use super::axum::Router;
+
+    use web::IdPath;
+
+    fn get_path(router: &mut Router, path: &str) -> (StatusCode, String) {
+        let request = Request::get(path).body(Body::empty()).unwrap();
+        let response = block_on(router.call(request)).unwrap();
+        let status = response.status();
+        let body = block_on(to_bytes(response.into_body(), 1024)).unwrap();
+        (status, String::from_utf8(body.to_vec()).unwrap())
+    }
+
+    fn echo(IdPath(id): IdPath) -> impl ::std::future::Future<Output = String> {
+        ready(id.uuid().hyphenated().to_string())
+    }
+
+    #[test]
+    fn extracts_ids() {
+        let mut router = Router::new().route("/users/{id}", get(echo));
+        assert_eq!(
+            get_path(&mut router, "/users/sMHuhm9GTxuNi3hJ51287g"),
+            (
+                StatusCode::OK,
+                "b0c1ee86-6f46-4f1b-8d8b-7849e75dbcee".to_owned()
+            )
+        );
+    }
+
+    #[test]
+    fn rejects_malformed_ids() {
+        let mut router = Router::new().route("/users/{id}", get(echo));
+        let (status, body) = get_path(&mut router, "/users/no%20pe");
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(
+            body,
+            r#"{"error":"invalid_id","message":"Invalid Base64 representation for UUID: 'no pe'","value":"no pe"}"#
+        );
+    }
+
+    #[test]
+    fn rejects_bad_routes() {
+        let mut router = Router::new()
+            .route("/none", get(echo))
+            .route("/{a}/{b}", get(echo));
+        for path in &["/none", "/a/b"] {
+            let (status, body) = get_path(&mut router, path);
+            assert_eq!(status, StatusCode::INTERNAL_SERVER_ERROR);
+            assert!(body.contains("bad_route"), "{}", body);
+        }
+    }
+}
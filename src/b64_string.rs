@@ -0,0 +1,125 @@
+//! A formatted ID that lives on the stack
+
+use std::fmt::{Debug, Display, Formatter, Result as FmtResult};
+use std::ops::Deref;
+use std::str::{self, FromStr};
+
+use errors::ParseUuidB64Error;
+use UuidB64;
+
+/// The 22 characters of an ID's text form, without a heap allocation
+///
+/// For keeping formatted IDs in structs, where a `String` per ID would be
+/// an allocation (and 24 bytes of pointer, length and capacity) each. It
+/// derefs to `str`, and is `Copy`.
+///
+/// ```
+/// # use uuid_b64::{B64String, UuidB64};
+/// let id: UuidB64 = "sMHuhm9GTxuNi3hJ51287g".parse().unwrap();
+/// let text: B64String = id.to_inline();
+/// assert_eq!(text, "sMHuhm9GTxuNi3hJ51287g");
+/// assert!(text.starts_with("sMH"));
+/// assert_eq!(format!("/users/{}", text), "/users/sMHuhm9GTxuNi3hJ51287g");
+/// assert_eq!(std::mem::size_of::<B64String>(), 22);
+/// ```
+///
+/// It can only be made from a valid ID, so parsing checks the text. With
+/// the `serde` feature it serializes as a string in every format.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct B64String([u8; 22]);
+
+impl B64String {
+    pub fn as_str(&self) -> &str {
+        str::from_utf8(&self.0).expect("Base64 is always ASCII")
+    }
+}
+
+impl UuidB64 {
+    /// The text form, as a `B64String` instead of a `String`
+    pub fn to_inline(&self) -> B64String {
+        B64String(self.to_b64_array())
+    }
+}
+
+impl From<UuidB64> for B64String {
+    fn from(id: UuidB64) -> B64String {
+        id.to_inline()
+    }
+}
+
+impl FromStr for B64String {
+    type Err = ParseUuidB64Error;
+
+    fn from_str(s: &str) -> Result<B64String, ParseUuidB64Error> {
+        s.parse::<UuidB64>().map(|id| id.to_inline())
+    }
+}
+
+impl Deref for B64String {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl AsRef<str> for B64String {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl PartialEq<str> for B64String {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl<'a> PartialEq<&'a str> for B64String {
+    fn eq(&self, other: &&'a str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+impl Display for B64String {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        // `pad` rather than `write_str`, so that `{:>24}` works as for `str`
+        f.pad(self.as_str())
+    }
+}
+
+impl Debug for B64String {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        write!(f, "B64String({})", self.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::B64String;
+    use UuidB64;
+
+    #[test]
+    fn same_text_as_display() {
+        let id = UuidB64::new();
+        let text = id.to_inline();
+        assert_eq!(text.as_str(), id.to_string());
+        assert_eq!(&*text, id.to_string());
+        assert_eq!(B64String::from(id), text);
+        assert_eq!(format!("{:?}", text), format!("B64String({})", id));
+        assert_eq!(format!("{:>23}", text), format!(" {}", id));
+    }
+
+    #[test]
+    fn parsing_checks_the_id() {
+        let text: B64String = "sMHuhm9GTxuNi3hJ51287g".parse().unwrap();
+        assert_eq!(text, "sMHuhm9GTxuNi3hJ51287g");
+        assert!("sMHuhm9GTxuNi3hJ51287".parse::<B64String>().is_err());
+        assert!("sMHuhm9GTxuNi3hJ51287h".parse::<B64String>().is_err());
+
+        let set: HashSet<B64String> = vec![text, text].into_iter().collect();
+        assert_eq!(set.len(), 1);
+    }
+}
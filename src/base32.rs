@@ -0,0 +1,249 @@
+//! Base32 forms: Crockford for reading IDs aloud, base32hex for DNS labels
+
+use errors::ErrorKind;
+use UuidB64;
+
+const ALPHABET: &[u8; 32] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+
+/// RFC 4648 base32hex, in lower case
+const HEX_ALPHABET: &[u8; 32] = b"0123456789abcdefghijklmnopqrstuv";
+
+/// The value of a Crockford digit, with the usual substitutions for the
+/// letters it leaves out
+fn digit_value(c: u8) -> Option<u8> {
+    let value = match c.to_ascii_uppercase() {
+        c @ b'0'..=b'9' => c - b'0',
+        b'O' => 0,
+        b'I' | b'L' => 1,
+        c => ALPHABET.iter().position(|&a| a == c)? as u8,
+    };
+    Some(value)
+}
+
+impl UuidB64 {
+    /// The 26-character Crockford base32 form, in upper case
+    ///
+    /// For IDs that people read out or type in: it isn't case-sensitive and
+    /// has no punctuation, and leaves out `I`, `L`, `O` and `U`. The first
+    /// character only carries 3 bits, so it's always `0` to `7`. Like the
+    /// bytes, the strings sort in the same order as `UuidB64`s.
+    ///
+    /// ```
+    /// # use uuid_b64::UuidB64;
+    /// let id: UuidB64 = "sMHuhm9GTxuNi3hJ51287g".parse().unwrap();
+    /// assert_eq!(id.to_base32_crockford(), "5GR7Q8CVT69WDRV2VR97KNVF7E");
+    /// ```
+    pub fn to_base32_crockford(&self) -> String {
+        let n = u128::from_be_bytes(*self.0.as_bytes());
+        (0..26)
+            .map(|i| ALPHABET[(n >> (125 - 5 * i)) as usize & 0x1f] as char)
+            .collect()
+    }
+
+    /// Parse the Crockford base32 form
+    ///
+    /// Any case is accepted, as are `I` and `L` for `1` and `O` for `0`.
+    /// Hyphens are ignored, so IDs can be split up for reading.
+    ///
+    /// ```
+    /// # use uuid_b64::UuidB64;
+    /// let id = UuidB64::from_base32_crockford("5gr7q8cvt6-9wdrv2vr97-knvf7e").unwrap();
+    /// assert_eq!(id.to_string(), "sMHuhm9GTxuNi3hJ51287g");
+    /// assert!(UuidB64::from_base32_crockford("8GR7Q8CVT69WDRV2VR97KNVF7E").is_err());
+    /// ```
+    pub fn from_base32_crockford(s: &str) -> Result<UuidB64, ErrorKind> {
+        let invalid = || ErrorKind::InvalidBase32(s.to_owned());
+        let mut n: u128 = 0;
+        let mut digits = 0;
+        for c in s.bytes().filter(|&c| c != b'-') {
+            let value = digit_value(c).ok_or_else(invalid)?;
+            // the first digit only has room for 3 bits
+            if digits == 0 && value > 7 {
+                return Err(invalid());
+            }
+            n = n << 5 | u128::from(value);
+            digits += 1;
+            if digits > 26 {
+                return Err(invalid());
+            }
+        }
+        if digits != 26 {
+            return Err(invalid());
+        }
+        Ok(UuidB64::from_bytes(n.to_be_bytes()))
+    }
+}
+
+impl UuidB64 {
+    /// The 26-character lower case base32hex form, for hostnames
+    ///
+    /// Valid as a DNS label and as a Kubernetes (RFC 1123 label) name, where
+    /// base64's upper case, `-` at the ends and `_` aren't allowed: for
+    /// deriving subdomains or object names from IDs. This is RFC 4648
+    /// base32hex without padding, so it sorts in the same order as
+    /// `UuidB64`s and the last character only carries 3 bits. It can start
+    /// with a digit, so add a prefix for names that must start with a letter.
+    ///
+    /// ```
+    /// # use uuid_b64::UuidB64;
+    /// let id: UuidB64 = "sMHuhm9GTxuNi3hJ51287g".parse().unwrap();
+    /// assert_eq!(id.to_dns_label(), "m30ut1jf8p7hn3cbf14uendsto");
+    /// ```
+    pub fn to_dns_label(&self) -> String {
+        let n = u128::from_be_bytes(*self.0.as_bytes());
+        // the last digit is the low 3 bits, shifted up to fill 5
+        (0..26)
+            .map(|i| {
+                let digit = if i < 25 { n >> (123 - 5 * i) } else { n << 2 };
+                HEX_ALPHABET[digit as usize & 0x1f] as char
+            })
+            .collect()
+    }
+
+    /// Parse the form made by [`to_dns_label`](#method.to_dns_label)
+    ///
+    /// DNS names aren't case-sensitive, so upper case is accepted too.
+    ///
+    /// ```
+    /// # use uuid_b64::UuidB64;
+    /// let id = UuidB64::from_dns_label("M30UT1JF8P7HN3CBF14UENDSTO").unwrap();
+    /// assert_eq!(id.to_string(), "sMHuhm9GTxuNi3hJ51287g");
+    /// assert!(UuidB64::from_dns_label("m30ut1jf8p7hn3cbf14uendstp").is_err());
+    /// ```
+    pub fn from_dns_label(s: &str) -> Result<UuidB64, ErrorKind> {
+        let invalid = || ErrorKind::InvalidDnsLabel(s.to_owned());
+        if s.len() != 26 {
+            return Err(invalid());
+        }
+        let mut n: u128 = 0;
+        for (i, c) in s.bytes().enumerate() {
+            let c = c.to_ascii_lowercase();
+            let value = HEX_ALPHABET
+                .iter()
+                .position(|&a| a == c)
+                .ok_or_else(invalid)? as u128;
+            if i < 25 {
+                n = n << 5 | value;
+            } else if value & 0b11 == 0 {
+                n = n << 3 | value >> 2;
+            } else {
+                // bits past the end of the UUID
+                return Err(invalid());
+            }
+        }
+        Ok(UuidB64::from_bytes(n.to_be_bytes()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use UuidB64;
+
+    #[test]
+    fn roundtrips() {
+        for id in &[
+            UuidB64::NIL,
+            UuidB64::MAX,
+            UuidB64::new(),
+            UuidB64::new_v7(),
+        ] {
+            let text = id.to_base32_crockford();
+            assert_eq!(text.len(), 26);
+            assert_eq!(UuidB64::from_base32_crockford(&text).unwrap(), *id);
+            assert_eq!(
+                UuidB64::from_base32_crockford(&text.to_lowercase()).unwrap(),
+                *id
+            );
+        }
+        assert_eq!(UuidB64::NIL.to_base32_crockford(), "0".repeat(26));
+        assert_eq!(
+            UuidB64::MAX.to_base32_crockford(),
+            format!("7{}", "Z".repeat(25))
+        );
+    }
+
+    #[test]
+    fn sorts_like_the_ids() {
+        let mut ids: Vec<_> = (0..100).map(|_| UuidB64::new()).collect();
+        let mut texts: Vec<_> = ids.iter().map(|id| id.to_base32_crockford()).collect();
+        ids.sort();
+        texts.sort();
+        let sorted: Vec<_> = ids.iter().map(|id| id.to_base32_crockford()).collect();
+        assert_eq!(texts, sorted);
+    }
+
+    #[test]
+    fn forgives_lookalikes() {
+        let id = UuidB64::from_base32_crockford("0000000000000000000000001O").unwrap();
+        assert_eq!(
+            id,
+            UuidB64::from_base32_crockford("00000000000000000000000010").unwrap()
+        );
+        assert_eq!(
+            UuidB64::from_base32_crockford("0000000000000000000000000i").unwrap(),
+            UuidB64::from_base32_crockford("0000000000000000000000000L").unwrap()
+        );
+    }
+
+    #[test]
+    fn rejects_bad_input() {
+        for bad in &[
+            "",
+            "0000000000000000000000000",
+            "000000000000000000000000000",
+            "000000000000000000000000U0",
+            "00000000000000000000000!00",
+            "80000000000000000000000000",
+        ] {
+            let err = UuidB64::from_base32_crockford(bad).unwrap_err();
+            assert_eq!(
+                err.to_string(),
+                format!(
+                    "Invalid Crockford base32 representation for UUID: '{}'",
+                    bad
+                )
+            );
+        }
+    }
+
+    #[test]
+    fn dns_labels() {
+        let mut ids = vec![UuidB64::NIL, UuidB64::MAX, UuidB64::new_v7()];
+        ids.extend((0..50).map(|_| UuidB64::new()));
+        for id in &ids {
+            let label = id.to_dns_label();
+            assert_eq!(label.len(), 26);
+            assert!(label
+                .bytes()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit()));
+            assert_eq!(UuidB64::from_dns_label(&label).unwrap(), *id);
+            assert_eq!(UuidB64::from_dns_label(&label.to_uppercase()).unwrap(), *id);
+        }
+        assert_eq!(UuidB64::NIL.to_dns_label(), "0".repeat(26));
+        assert_eq!(UuidB64::MAX.to_dns_label(), format!("{}s", "v".repeat(25)));
+
+        let mut labels: Vec<_> = ids.iter().map(|id| id.to_dns_label()).collect();
+        ids.sort();
+        labels.sort();
+        let sorted: Vec<_> = ids.iter().map(|id| id.to_dns_label()).collect();
+        assert_eq!(labels, sorted);
+    }
+
+    #[test]
+    fn rejects_bad_dns_labels() {
+        for bad in &[
+            "",
+            "0000000000000000000000000",
+            "000000000000000000000000000",
+            "000000000000000000000000w0",
+            "00000000000000000000000-00",
+            "0000000000000000000000000v",
+        ] {
+            let err = UuidB64::from_dns_label(bad).unwrap_err();
+            assert_eq!(
+                err.to_string(),
+                format!("Invalid DNS label for UUID: '{}'", bad)
+            );
+        }
+    }
+}
@@ -0,0 +1,72 @@
+//! Base58, for APIs that expect Bitcoin-style object IDs
+
+extern crate bs58;
+
+use errors::ErrorKind;
+use UuidB64;
+
+impl UuidB64 {
+    /// The Base58 form, in the Bitcoin alphabet
+    ///
+    /// Base58 treats the bytes as one big number, so the length varies: 22
+    /// characters for most IDs, fewer when the leading bytes are small. Each
+    /// leading zero byte is written as a `1`.
+    ///
+    /// ```
+    /// # use uuid_b64::UuidB64;
+    /// let id: UuidB64 = "sMHuhm9GTxuNi3hJ51287g".parse().unwrap();
+    /// assert_eq!(id.to_base58(), "NpxGnbuQjiMf1wUsTiwTY1");
+    /// assert_eq!(UuidB64::from_base58(&id.to_base58()).unwrap(), id);
+    /// ```
+    pub fn to_base58(&self) -> String {
+        bs58::encode(self.0.as_bytes()).into_string()
+    }
+
+    /// Parse the Base58 form, which has to decode to exactly 16 bytes
+    pub fn from_base58(s: &str) -> Result<UuidB64, ErrorKind> {
+        let mut bytes = [0; 16];
+        match bs58::decode(s).onto(&mut bytes[..]) {
+            Ok(16) => Ok(UuidB64::from_bytes(bytes)),
+            _ => Err(ErrorKind::InvalidBase58(s.to_owned())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use UuidB64;
+
+    #[test]
+    fn roundtrips() {
+        for id in &[
+            UuidB64::NIL,
+            UuidB64::MAX,
+            UuidB64::new(),
+            UuidB64::new_v7(),
+        ] {
+            assert_eq!(UuidB64::from_base58(&id.to_base58()).unwrap(), *id);
+        }
+        assert_eq!(UuidB64::NIL.to_base58(), "1".repeat(16));
+        assert_eq!(UuidB64::MAX.to_base58(), "YcVfxkQb6JRzqk5kF2tNLv");
+    }
+
+    #[test]
+    fn rejects_other_lengths_and_characters() {
+        for bad in &[
+            "",
+            "1",
+            &"1".repeat(15),
+            &"1".repeat(17),
+            // 2^128
+            "YcVfxkQb6JRzqk5kF2tNLw",
+            "NpxGnbuQjiMf1wUsTiwTY0",
+            "NpxGnbuQjiMf1wUsTiwTYl",
+        ] {
+            let err = UuidB64::from_base58(bad).unwrap_err();
+            assert_eq!(
+                err.to_string(),
+                format!("Invalid Base58 representation for UUID: '{}'", bad)
+            );
+        }
+    }
+}
@@ -0,0 +1,110 @@
+//! Base62, for places that only allow letters and digits
+
+use errors::ErrorKind;
+use UuidB64;
+
+/// In ASCII order, so that the fixed-width strings sort like the IDs
+const ALPHABET: &[u8; 62] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
+
+fn digit_value(c: u8) -> Option<u8> {
+    match c {
+        b'0'..=b'9' => Some(c - b'0'),
+        b'A'..=b'Z' => Some(c - b'A' + 10),
+        b'a'..=b'z' => Some(c - b'a' + 36),
+        _ => None,
+    }
+}
+
+impl UuidB64 {
+    /// The 22-character Base62 form, only letters and digits
+    ///
+    /// For URL routers, SMS links and the like that don't allow the `-` and
+    /// `_` of the usual form. It's padded with leading `0`s to a fixed
+    /// width, and the alphabet is in ASCII order, so the strings sort in the
+    /// same order as the IDs.
+    ///
+    /// ```
+    /// # use uuid_b64::UuidB64;
+    /// let id: UuidB64 = "sMHuhm9GTxuNi3hJ51287g".parse().unwrap();
+    /// assert_eq!(id.to_base62(), "5NXH9G03Qou9vbZ5Nk2VBO");
+    /// assert_eq!(UuidB64::from_base62("5NXH9G03Qou9vbZ5Nk2VBO").unwrap(), id);
+    /// ```
+    pub fn to_base62(&self) -> String {
+        let mut n = u128::from_be_bytes(*self.0.as_bytes());
+        let mut digits = [b'0'; 22];
+        for digit in digits.iter_mut().rev() {
+            *digit = ALPHABET[(n % 62) as usize];
+            n /= 62;
+        }
+        digits.iter().map(|&d| d as char).collect()
+    }
+
+    /// Parse the 22-character Base62 form
+    pub fn from_base62(s: &str) -> Result<UuidB64, ErrorKind> {
+        let invalid = || ErrorKind::InvalidBase62(s.to_owned());
+        if s.len() != 22 {
+            return Err(invalid());
+        }
+        let mut n: u128 = 0;
+        for c in s.bytes() {
+            let value = digit_value(c).ok_or_else(invalid)?;
+            // 22 digits hold a little more than 128 bits
+            n = n
+                .checked_mul(62)
+                .and_then(|n| n.checked_add(u128::from(value)))
+                .ok_or_else(invalid)?;
+        }
+        Ok(UuidB64::from_bytes(n.to_be_bytes()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use UuidB64;
+
+    #[test]
+    fn roundtrips() {
+        for id in &[
+            UuidB64::NIL,
+            UuidB64::MAX,
+            UuidB64::new(),
+            UuidB64::new_v7(),
+        ] {
+            let text = id.to_base62();
+            assert_eq!(text.len(), 22);
+            assert!(text.bytes().all(|c| c.is_ascii_alphanumeric()));
+            assert_eq!(UuidB64::from_base62(&text).unwrap(), *id);
+        }
+        assert_eq!(UuidB64::NIL.to_base62(), "0".repeat(22));
+        assert_eq!(UuidB64::MAX.to_base62(), "7n42DGM5Tflk9n8mt7Fhc7");
+    }
+
+    #[test]
+    fn sorts_like_the_ids() {
+        let mut ids: Vec<_> = (0..100).map(|_| UuidB64::new()).collect();
+        let mut texts: Vec<_> = ids.iter().map(|id| id.to_base62()).collect();
+        ids.sort();
+        texts.sort();
+        let sorted: Vec<_> = ids.iter().map(|id| id.to_base62()).collect();
+        assert_eq!(texts, sorted);
+    }
+
+    #[test]
+    fn rejects_bad_input() {
+        for bad in &[
+            "",
+            "5NXH9G03Qou9vbZ5Nk2VB",
+            "5NXH9G03Qou9vbZ5Nk2VBOO",
+            "5NXH9G03Qou9vbZ5Nk2V-O",
+            // 2^128
+            "7n42DGM5Tflk9n8mt7Fhc8",
+            "zzzzzzzzzzzzzzzzzzzzzz",
+        ] {
+            let err = UuidB64::from_base62(bad).unwrap_err();
+            assert_eq!(
+                err.to_string(),
+                format!("Invalid Base62 representation for UUID: '{}'", bad)
+            );
+        }
+    }
+}
@@ -0,0 +1,84 @@
+//! Generating many v4 IDs at once
+
+extern crate rand04;
+
+use self::rand04::Rng;
+use uuid::Uuid;
+
+use UuidB64;
+
+/// How many IDs' worth of random bytes to request from the generator at once
+const CHUNK: usize = 64;
+
+impl UuidB64 {
+    /// Generate `n` new v4 UUIDs
+    ///
+    /// The same as calling [`new`](#method.new) `n` times, but the random
+    /// bytes are drawn from one generator handle in bulk.
+    ///
+    /// ```
+    /// # use uuid_b64::UuidB64;
+    /// let ids = UuidB64::new_batch(1000);
+    /// assert_eq!(ids.len(), 1000);
+    /// assert!(ids.iter().all(|id| id.uuid().get_version_num() == 4));
+    /// ```
+    pub fn new_batch(n: usize) -> Vec<UuidB64> {
+        let mut ids = vec![UuidB64::NIL; n];
+        UuidB64::fill_new(&mut ids);
+        ids
+    }
+
+    /// Overwrite every element of `ids` with a new v4 UUID
+    ///
+    /// For reusing a buffer between batches.
+    pub fn fill_new(ids: &mut [UuidB64]) {
+        #[cfg(feature = "test-util")]
+        {
+            if ::test_util::in_scope() {
+                for id in ids {
+                    *id = UuidB64::new();
+                }
+                return;
+            }
+        }
+        let mut rng = rand04::thread_rng();
+        let mut bytes = [0; 16 * CHUNK];
+        for chunk in ids.chunks_mut(CHUNK) {
+            let bytes = &mut bytes[..16 * chunk.len()];
+            rng.fill_bytes(bytes);
+            for (id, random) in chunk.iter_mut().zip(bytes.chunks(16)) {
+                let mut random_bytes = [0; 16];
+                random_bytes.copy_from_slice(random);
+                *id = UuidB64(Uuid::from_random_bytes(random_bytes));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use uuid::{UuidVariant, UuidVersion};
+
+    use UuidB64;
+
+    #[test]
+    fn batches_are_distinct_v4_ids() {
+        let ids = UuidB64::new_batch(1000);
+        assert_eq!(ids.len(), 1000);
+        assert_eq!(ids.iter().collect::<HashSet<_>>().len(), 1000);
+        for id in &ids {
+            assert_eq!(id.uuid().get_version(), Some(UuidVersion::Random));
+            assert_eq!(id.uuid().get_variant(), Some(UuidVariant::RFC4122));
+        }
+        assert!(UuidB64::new_batch(0).is_empty());
+    }
+
+    #[test]
+    fn fill_overwrites_everything() {
+        let mut ids = [UuidB64::NIL; 70];
+        UuidB64::fill_new(&mut ids);
+        assert!(ids.iter().all(|id| !id.is_nil()));
+    }
+}
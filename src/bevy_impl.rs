@@ -0,0 +1,67 @@
+//! Bevy ECS integration
+//!
+//! `UuidB64` and the versioned IDs are components, so a networked entity's
+//! identifier can be attached to it directly, and they're reflected as
+//! opaque values, so inspectors show them in base64:
+//!
+//! ```rust,ignore
+//! app.register_type::<UuidB64>();
+//! commands.spawn((Player, UuidB64::from(server_id)));
+//! ```
+//!
+//! The components are immutable: an entity's ID is replaced by inserting a
+//! new one, never edited in place, so hooks and observers always see the
+//! change.
+
+extern crate bevy_ecs;
+extern crate bevy_reflect;
+
+use self::bevy_ecs::component::{Component, Immutable, StorageType};
+use self::bevy_ecs::reflect::ReflectComponent;
+use self::bevy_reflect::impl_reflect_opaque;
+
+use versioned::{UuidB64V4, UuidB64V7};
+use UuidB64;
+
+macro_rules! bevy_id {
+    ($name:ident) => {
+        impl Component for $name {
+            const STORAGE_TYPE: StorageType = StorageType::Table;
+            type Mutability = Immutable;
+        }
+
+        impl_reflect_opaque!((in uuid_b64) $name(Clone, Debug, Hash, PartialEq, Component));
+    };
+}
+
+bevy_id!(UuidB64);
+bevy_id!(UuidB64V4);
+bevy_id!(UuidB64V7);
+
+#[cfg(test)]
+mod tests {
+    use super::bevy_ecs::world::World;
+    use super::bevy_reflect::{PartialReflect, TypePath};
+
+    use UuidB64;
+
+    #[test]
+    fn attaches_to_entities() {
+        let mut world = World::new();
+        let id = UuidB64::new();
+        let entity = world.spawn(id).id();
+        assert_eq!(world.get::<UuidB64>(entity), Some(&id));
+    }
+
+    #[test]
+    fn reflects_as_base64() {
+        let id: UuidB64 = "sMHuhm9GTxuNi3hJ51287g".parse().unwrap();
+        assert_eq!(UuidB64::type_path(), "uuid_b64::UuidB64");
+        assert_eq!(
+            format!("{:?}", id.as_partial_reflect()),
+            "UuidB64(sMHuhm9GTxuNi3hJ51287g)"
+        );
+        let cloned = id.reflect_clone().unwrap();
+        assert_eq!(cloned.downcast_ref::<UuidB64>(), Some(&id));
+    }
+}
@@ -0,0 +1,282 @@
+//! `uuidb64`, for generating and converting IDs from the command line
+//!
+//! ```text
+//! uuidb64 new -n 100 --v7
+//! uuidb64 encode b0c1ee86-6f46-4f1b-8d8b-7849e75dbcee
+//! uuidb64 decode < ids.txt
+//! uuidb64 validate sMHuhm9GTxuNi3hJ51287g
+//! ```
+
+extern crate clap;
+extern crate uuid_b64;
+
+use std::io::{self, BufRead, BufWriter, Write};
+use std::process;
+
+use clap::{value_parser, Arg, ArgAction, ArgMatches, Command};
+use uuid_b64::{B64IdWriter, Uuid, UuidB64};
+
+/// What to do with each input
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum Mode {
+    /// Hex UUIDs, hyphenated or not, to Base64
+    Encode,
+    /// Base64 to hyphenated hex
+    Decode,
+    /// Only check the Base64
+    Validate,
+}
+
+impl Mode {
+    /// The output line for `input`, if this mode has one
+    fn convert(self, input: &str) -> Result<Option<String>, String> {
+        match self {
+            Mode::Encode => Uuid::parse_str(input)
+                .map(|uuid| Some(UuidB64::from(uuid).to_string()))
+                .map_err(|e| e.to_string()),
+            Mode::Decode => input
+                .parse::<UuidB64>()
+                .map(|id| Some(id.uuid().hyphenated().to_string()))
+                .map_err(|e| e.to_string()),
+            Mode::Validate => input
+                .parse::<UuidB64>()
+                .map(|_| None)
+                .map_err(|e| e.to_string()),
+        }
+    }
+}
+
+fn command() -> Command {
+    Command::new("uuidb64")
+        .about("Generate and convert Base64 UUIDs")
+        .subcommand_required(true)
+        .subcommand(
+            Command::new("new")
+                .about("Generate new IDs")
+                .arg(
+                    Arg::new("count")
+                        .short('n')
+                        .long("count")
+                        .value_name("N")
+                        .default_value("1")
+                        .value_parser(value_parser!(usize))
+                        .help("How many IDs to generate"),
+                )
+                .arg(
+                    Arg::new("v7")
+                        .long("v7")
+                        .action(ArgAction::SetTrue)
+                        .help("Generate time-ordered v7 IDs instead of random v4 ones"),
+                ),
+        )
+        .subcommand(
+            Command::new("encode")
+                .about("Convert hex UUIDs to Base64")
+                .arg(inputs("UUID")),
+        )
+        .subcommand(
+            Command::new("decode")
+                .about("Convert Base64 IDs to hyphenated hex UUIDs")
+                .arg(inputs("ID")),
+        )
+        .subcommand(
+            Command::new("validate")
+                .about("Check Base64 IDs, exiting with 1 if any are invalid")
+                .arg(inputs("ID")),
+        )
+}
+
+fn inputs(value_name: &'static str) -> Arg {
+    Arg::new("input")
+        .value_name(value_name)
+        .num_args(0..)
+        .help("Read from stdin, one per line, if none are given")
+}
+
+/// How many v4 IDs are generated at a time, so large counts are streamed
+const CHUNK: usize = 1024;
+
+/// Write `count` new IDs to `out`, one per line
+fn generate<W: Write>(args: &ArgMatches, out: W) -> io::Result<()> {
+    let count = *args.get_one::<usize>("count").expect("count has a default");
+    let mut writer = B64IdWriter::new(out);
+    if args.get_flag("v7") {
+        writer.write_all((0..count).map(|_| UuidB64::new_v7()))?;
+    } else {
+        let mut buf = vec![UuidB64::NIL; count.min(CHUNK)];
+        let mut left = count;
+        while left > 0 {
+            let chunk = &mut buf[..left.min(CHUNK)];
+            UuidB64::fill_new(chunk);
+            writer.write_all(chunk.iter().cloned())?;
+            left -= chunk.len();
+        }
+    }
+    writer.flush()
+}
+
+/// Run `mode` over every non-blank input, returning whether they were all
+/// valid
+///
+/// Invalid inputs are reported to `err`. Conversions stop at the first one,
+/// so that the output lines up with the input, but validation reports them
+/// all.
+fn run<I, W, E>(mode: Mode, inputs: I, mut out: W, mut err: E) -> io::Result<bool>
+where
+    I: IntoIterator<Item = io::Result<String>>,
+    W: Write,
+    E: Write,
+{
+    let mut all_valid = true;
+    for input in inputs {
+        let input = input?;
+        let input = input.trim();
+        if input.is_empty() {
+            continue;
+        }
+        match mode.convert(input) {
+            Ok(Some(line)) => writeln!(out, "{}", line)?,
+            Ok(None) => {}
+            Err(e) => {
+                writeln!(err, "uuidb64: '{}': {}", input, e)?;
+                all_valid = false;
+                if mode != Mode::Validate {
+                    break;
+                }
+            }
+        }
+    }
+    out.flush()?;
+    Ok(all_valid)
+}
+
+fn main() {
+    let matches = command().get_matches();
+    let stdout = io::stdout();
+    let out = BufWriter::new(stdout.lock());
+    let result = match matches.subcommand() {
+        Some(("new", args)) => generate(args, out).map(|()| true),
+        Some((name, args)) => {
+            let mode = match name {
+                "encode" => Mode::Encode,
+                "decode" => Mode::Decode,
+                _ => Mode::Validate,
+            };
+            match args.get_many::<String>("input") {
+                Some(values) => run(mode, values.cloned().map(Ok), out, io::stderr()),
+                None => run(mode, io::stdin().lock().lines(), out, io::stderr()),
+            }
+        }
+        None => unreachable!("a subcommand is required"),
+    };
+    match result {
+        Ok(true) => {}
+        Ok(false) => process::exit(1),
+        // e.g. piped into `head`
+        Err(ref e) if e.kind() == io::ErrorKind::BrokenPipe => {}
+        Err(e) => {
+            eprintln!("uuidb64: {}", e);
+            process::exit(1);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+    use std::io;
+
+    use super::{command, generate, run, Mode, CHUNK};
+    use uuid_b64::UuidB64;
+
+    fn lines(input: &str) -> Vec<io::Result<String>> {
+        input.lines().map(|line| Ok(line.to_owned())).collect()
+    }
+
+    #[test]
+    fn converts_both_ways() {
+        let mut out = Vec::new();
+        let input = "b0c1ee86-6f46-4f1b-8d8b-7849e75dbcee\n\n  b0c1ee866f464f1b8d8b7849e75dbcee\n";
+        assert!(run(Mode::Encode, lines(input), &mut out, io::sink()).unwrap());
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "sMHuhm9GTxuNi3hJ51287g\nsMHuhm9GTxuNi3hJ51287g\n"
+        );
+
+        let mut out = Vec::new();
+        assert!(run(
+            Mode::Decode,
+            lines("sMHuhm9GTxuNi3hJ51287g"),
+            &mut out,
+            io::sink()
+        )
+        .unwrap());
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "b0c1ee86-6f46-4f1b-8d8b-7849e75dbcee\n"
+        );
+    }
+
+    #[test]
+    fn conversions_stop_at_the_first_invalid_input() {
+        let (mut out, mut err) = (Vec::new(), Vec::new());
+        let input = "sMHuhm9GTxuNi3hJ51287g\nnope\nsMHuhm9GTxuNi3hJ51287g";
+        assert!(!run(Mode::Decode, lines(input), &mut out, &mut err).unwrap());
+        assert_eq!(out.iter().filter(|&&b| b == b'\n').count(), 1);
+        assert_eq!(
+            String::from_utf8(err).unwrap(),
+            "uuidb64: 'nope': Invalid Base64 representation for UUID: \
+             expected 22 characters, found 4\n"
+        );
+    }
+
+    #[test]
+    fn validation_reports_every_invalid_input() {
+        let (mut out, mut err) = (Vec::new(), Vec::new());
+        let input = "sMHuhm9GTxuNi3hJ51287g\nnope\nsMHuhm9GTxuNi3hJ51287h\n";
+        assert!(!run(Mode::Validate, lines(input), &mut out, &mut err).unwrap());
+        assert!(out.is_empty());
+        assert_eq!(String::from_utf8(err).unwrap().lines().count(), 2);
+
+        assert!(run(
+            Mode::Validate,
+            lines("sMHuhm9GTxuNi3hJ51287g"),
+            io::sink(),
+            io::sink()
+        )
+        .unwrap());
+    }
+
+    #[test]
+    fn generates_ids() {
+        let matches = command()
+            .try_get_matches_from(["uuidb64", "new", "-n", "3", "--v7"])
+            .unwrap();
+        let mut out = Vec::new();
+        generate(matches.subcommand_matches("new").unwrap(), &mut out).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        let ids: Vec<UuidB64> = out.lines().map(|line| line.parse().unwrap()).collect();
+        assert_eq!(ids.len(), 3);
+        assert!(ids.iter().all(|id| id.version() == 7));
+        assert!(ids.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+
+    #[test]
+    fn generates_v4_ids_in_chunks() {
+        let count = (CHUNK + 1).to_string();
+        let matches = command()
+            .try_get_matches_from(["uuidb64", "new", "-n", &count])
+            .unwrap();
+        let mut out = Vec::new();
+        generate(matches.subcommand_matches("new").unwrap(), &mut out).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        let ids: HashSet<UuidB64> = out.lines().map(|line| line.parse().unwrap()).collect();
+        assert_eq!(ids.len(), CHUNK + 1);
+        assert!(ids.iter().all(|id| id.version() == 4));
+    }
+
+    #[test]
+    fn command_is_valid() {
+        command().debug_assert();
+    }
+}
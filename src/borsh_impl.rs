@@ -0,0 +1,53 @@
+//! Borsh serialization, as the 16 raw bytes
+
+extern crate borsh;
+
+use std::io::{Read, Result, Write};
+
+use self::borsh::{BorshDeserialize, BorshSerialize};
+
+use UuidB64;
+
+impl BorshSerialize for UuidB64 {
+    fn serialize<W: Write>(&self, writer: &mut W) -> Result<()> {
+        writer.write_all(self.0.as_bytes())
+    }
+}
+
+impl BorshDeserialize for UuidB64 {
+    fn deserialize_reader<R: Read>(reader: &mut R) -> Result<Self> {
+        let bytes = <[u8; 16]>::deserialize_reader(reader)?;
+        Ok(UuidB64::from_bytes(bytes))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::borsh;
+
+    use UuidB64;
+
+    #[test]
+    fn fixed_16_bytes() {
+        let id: UuidB64 = "sMHuhm9GTxuNi3hJ51287g".parse().unwrap();
+        let encoded = borsh::to_vec(&id).unwrap();
+        assert_eq!(&encoded[..], id.uuid().as_bytes());
+        assert_eq!(borsh::from_slice::<UuidB64>(&encoded).unwrap(), id);
+    }
+
+    #[test]
+    fn in_collections() {
+        let ids = vec![UuidB64::new(), UuidB64::new()];
+        let encoded = borsh::to_vec(&(ids.clone(), Some(ids[0]))).unwrap();
+        // u32 length, two IDs, an option tag and a third ID
+        assert_eq!(encoded.len(), 4 + 32 + 1 + 16);
+        let decoded: (Vec<UuidB64>, Option<UuidB64>) = borsh::from_slice(&encoded).unwrap();
+        assert_eq!(decoded, (ids.clone(), Some(ids[0])));
+    }
+
+    #[test]
+    fn rejects_short_input() {
+        assert!(borsh::from_slice::<UuidB64>(&[0; 15]).is_err());
+        assert!(borsh::from_slice::<UuidB64>(&[0; 17]).is_err());
+    }
+}
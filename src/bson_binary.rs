@@ -0,0 +1,149 @@
+//! MongoDB support, storing IDs as BSON UUID binaries (subtype 4)
+//!
+//! `UuidB64` converts into `Bson` and `Binary` directly. In documents
+//! mapped with serde, use `#[serde(with = "uuid_b64::bson_binary")]` so
+//! the field is stored as a native UUID instead of a 22-character string,
+//! and shows up as one in `mongosh` and Compass:
+//!
+//! ```
+//! # extern crate bson;
+//! # #[macro_use] extern crate serde_derive;
+//! # extern crate uuid_b64;
+//! # use uuid_b64::UuidB64;
+//! #[derive(Deserialize, Serialize)]
+//! struct Order {
+//!     #[serde(rename = "_id", with = "uuid_b64::bson_binary")]
+//!     id: UuidB64,
+//! }
+//!
+//! # fn main() {
+//! let order = Order { id: UuidB64::new() };
+//! let doc = bson::to_document(&order).unwrap();
+//! assert!(matches!(doc.get("_id"), Some(bson::Bson::Binary(_))));
+//! let read: Order = bson::from_document(doc).unwrap();
+//! assert_eq!(read.id, order.id);
+//! # }
+//! ```
+
+extern crate bson;
+extern crate serde;
+
+use self::bson::spec::BinarySubtype;
+use self::bson::{Binary, Bson};
+use self::serde::de::{self, Deserialize, Deserializer};
+use self::serde::ser::{Serialize, Serializer};
+use uuid::Uuid;
+
+use errors::ErrorKind;
+use UuidB64;
+
+impl From<UuidB64> for Binary {
+    fn from(id: UuidB64) -> Binary {
+        Binary {
+            subtype: BinarySubtype::Uuid,
+            bytes: id.0.as_bytes().to_vec(),
+        }
+    }
+}
+
+impl From<UuidB64> for Bson {
+    fn from(id: UuidB64) -> Bson {
+        Bson::Binary(id.into())
+    }
+}
+
+impl UuidB64 {
+    /// Read an ID from a BSON UUID binary
+    ///
+    /// Only subtype 4 is accepted: the legacy subtype 3 has a different
+    /// byte order in each driver, so there's no telling which ID it holds.
+    /// (This can't be `TryFrom<Binary>`, which would overlap with the
+    /// `From` impl for everything that converts into a `Uuid`.)
+    pub fn from_bson_binary(binary: &Binary) -> Result<UuidB64, ErrorKind> {
+        if binary.subtype != BinarySubtype::Uuid {
+            return Err(ErrorKind::InvalidBinary(format!(
+                "expected a UUID binary (subtype 4), found {:?}",
+                binary.subtype
+            )));
+        }
+        Uuid::from_bytes(&binary.bytes).map(UuidB64).map_err(|_| {
+            ErrorKind::InvalidBinary(format!(
+                "expected 16 bytes for a UUID, found {}",
+                binary.bytes.len()
+            ))
+        })
+    }
+}
+
+/// Serialize an ID as a BSON UUID binary
+pub fn serialize<S>(id: &UuidB64, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    Binary::from(*id).serialize(serializer)
+}
+
+/// Deserialize an ID from a BSON UUID binary
+pub fn deserialize<'de, D>(deserializer: D) -> Result<UuidB64, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let binary = Binary::deserialize(deserializer)?;
+    UuidB64::from_bson_binary(&binary).map_err(de::Error::custom)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::bson::spec::BinarySubtype;
+    use super::bson::{self, doc, Binary, Bson};
+
+    use UuidB64;
+
+    const ID: &str = "sMHuhm9GTxuNi3hJ51287g";
+
+    #[derive(Debug, PartialEq, Deserialize, Serialize)]
+    struct Order {
+        #[serde(with = "::bson_binary")]
+        id: UuidB64,
+    }
+
+    #[test]
+    fn converts_to_uuid_binaries() {
+        let id: UuidB64 = ID.parse().unwrap();
+        match Bson::from(id) {
+            Bson::Binary(binary) => {
+                assert_eq!(binary.subtype, BinarySubtype::Uuid);
+                assert_eq!(&binary.bytes[..], id.uuid().as_bytes());
+                assert_eq!(UuidB64::from_bson_binary(&binary).unwrap(), id);
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+    }
+
+    #[test]
+    fn rejects_other_binaries() {
+        let id: UuidB64 = ID.parse().unwrap();
+        let legacy = Binary {
+            subtype: BinarySubtype::UuidOld,
+            bytes: id.uuid().as_bytes().to_vec(),
+        };
+        assert!(UuidB64::from_bson_binary(&legacy).is_err());
+        let short = Binary {
+            subtype: BinarySubtype::Uuid,
+            bytes: vec![1, 2],
+        };
+        assert!(UuidB64::from_bson_binary(&short).is_err());
+    }
+
+    #[test]
+    fn serde_roundtrips() {
+        let order = Order {
+            id: ID.parse().unwrap(),
+        };
+        let doc = bson::to_document(&order).unwrap();
+        assert_eq!(doc, doc! { "id": Bson::from(order.id) });
+        assert_eq!(bson::from_document::<Order>(doc).unwrap(), order);
+        let strings = doc! { "id": ID };
+        assert!(bson::from_document::<Order>(strings).is_err());
+    }
+}
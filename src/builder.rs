@@ -0,0 +1,146 @@
+//! Building UUIDs from arbitrary bytes
+
+use uuid::UuidVariant;
+
+use UuidB64;
+
+impl UuidB64 {
+    /// A v8 UUID carrying `bytes`, for application-specific layouts
+    ///
+    /// The version and variant bits (the top nibble of byte 6 and the top two
+    /// bits of byte 8) are overwritten; the other 122 bits are kept as is.
+    ///
+    /// ```
+    /// # use uuid_b64::UuidB64;
+    /// let mut payload = [0; 16];
+    /// payload[..4].copy_from_slice(&0xdead_beef_u32.to_be_bytes()); // tenant hash
+    /// payload[12..].copy_from_slice(&42_u32.to_be_bytes()); // counter
+    /// let id = UuidB64::new_v8(payload);
+    /// assert_eq!(
+    ///     id.uuid().hyphenated().to_string(),
+    ///     "deadbeef-0000-8000-8000-00000000002a"
+    /// );
+    /// ```
+    pub fn new_v8(bytes: [u8; 16]) -> UuidB64 {
+        UuidB64Builder::from_bytes(bytes)
+            .with_version(8)
+            .with_variant(UuidVariant::RFC4122)
+            .into_uuid_b64()
+    }
+}
+
+/// Sets the version and variant bits on arbitrary bytes
+///
+/// [`UuidB64::new_v8`][] covers the common case, this is for the others:
+///
+/// ```
+/// # use uuid_b64::{UuidB64Builder, UuidVariant};
+/// let id = UuidB64Builder::from_bytes([0xff; 16])
+///     .with_version(4)
+///     .with_variant(UuidVariant::RFC4122)
+///     .into_uuid_b64();
+/// assert_eq!(id.uuid().get_version_num(), 4);
+/// assert_eq!(id.uuid().get_variant(), Some(UuidVariant::RFC4122));
+/// ```
+///
+/// [`UuidB64::new_v8`]: struct.UuidB64.html#method.new_v8
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UuidB64Builder([u8; 16]);
+
+impl UuidB64Builder {
+    /// Start from `bytes`, unchanged
+    pub fn from_bytes(bytes: [u8; 16]) -> UuidB64Builder {
+        UuidB64Builder(bytes)
+    }
+
+    /// Set the version nibble
+    ///
+    /// # Panics
+    ///
+    /// If `version` doesn't fit in 4 bits.
+    pub fn with_version(mut self, version: u8) -> UuidB64Builder {
+        assert!(version <= 0xf, "UUID versions are 4 bits");
+        self.0[6] = (self.0[6] & 0x0f) | version << 4;
+        self
+    }
+
+    /// Set the variant bits
+    ///
+    /// Each variant takes as many of the top bits of byte 8 as it needs, so
+    /// `NCS` sets one bit, `RFC4122` two and the others three.
+    pub fn with_variant(mut self, variant: UuidVariant) -> UuidB64Builder {
+        self.0[8] = match variant {
+            UuidVariant::NCS => self.0[8] & 0x7f,
+            UuidVariant::RFC4122 => (self.0[8] & 0x3f) | 0x80,
+            UuidVariant::Microsoft => (self.0[8] & 0x1f) | 0xc0,
+            UuidVariant::Future => (self.0[8] & 0x1f) | 0xe0,
+        };
+        self
+    }
+
+    /// The bytes built so far
+    pub fn as_bytes(&self) -> &[u8; 16] {
+        &self.0
+    }
+
+    /// Finish building
+    pub fn into_uuid_b64(self) -> UuidB64 {
+        UuidB64::from_bytes(self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use uuid::UuidVariant;
+
+    use super::UuidB64Builder;
+    use UuidB64;
+
+    #[test]
+    fn v8_keeps_the_payload() {
+        for fill in &[0x00, 0xff, 0xa5] {
+            let id = UuidB64::new_v8([*fill; 16]);
+            assert_eq!(id.uuid().get_version_num(), 8);
+            assert_eq!(id.uuid().get_variant(), Some(UuidVariant::RFC4122));
+            let bytes = id.uuid().as_bytes().to_owned();
+            for (i, &b) in bytes.iter().enumerate() {
+                let mask = match i {
+                    6 => 0x0f,
+                    8 => 0x3f,
+                    _ => 0xff,
+                };
+                assert_eq!(b & mask, fill & mask, "byte {}", i);
+            }
+        }
+    }
+
+    #[test]
+    fn sets_every_variant() {
+        for variant in &[
+            UuidVariant::NCS,
+            UuidVariant::RFC4122,
+            UuidVariant::Microsoft,
+            UuidVariant::Future,
+        ] {
+            for fill in &[0x00, 0xff] {
+                let id = UuidB64Builder::from_bytes([*fill; 16])
+                    .with_variant(*variant)
+                    .into_uuid_b64();
+                assert_eq!(id.uuid().get_variant(), Some(*variant));
+            }
+        }
+    }
+
+    #[test]
+    fn sets_version() {
+        let builder = UuidB64Builder::from_bytes([0xff; 16]).with_version(1);
+        assert_eq!(builder.as_bytes()[6], 0x1f);
+        assert_eq!(builder.into_uuid_b64().uuid().get_version_num(), 1);
+    }
+
+    #[test]
+    #[should_panic]
+    fn rejects_oversized_version() {
+        UuidB64Builder::from_bytes([0; 16]).with_version(16);
+    }
+}
@@ -0,0 +1,135 @@
+//! An ID that keeps its text form next to it
+
+use std::fmt::{Debug, Display, Formatter, Result as FmtResult};
+use std::str::FromStr;
+
+use b64_string::B64String;
+use errors::ParseUuidB64Error;
+use UuidB64;
+
+/// A `UuidB64` that is encoded once, when it's made, instead of on every
+/// `Display`
+///
+/// For IDs that get written out over and over, like the handful that show
+/// up in every response of a busy endpoint: formatting and, with the `serde`
+/// feature, serializing to human-readable formats copies the stored text.
+/// It formats, parses and (de)serializes exactly like `UuidB64`, so it can
+/// replace one in a struct without changing any output. It costs 22 more
+/// bytes than a `UuidB64`.
+///
+/// ```
+/// # use uuid_b64::{CachedUuidB64, UuidB64};
+/// let id: UuidB64 = "sMHuhm9GTxuNi3hJ51287g".parse().unwrap();
+/// let cached = CachedUuidB64::new(id);
+/// assert_eq!(cached.as_str(), "sMHuhm9GTxuNi3hJ51287g");
+/// assert_eq!(cached.to_string(), id.to_string());
+/// assert_eq!(cached.id(), id);
+/// ```
+#[derive(Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct CachedUuidB64 {
+    id: UuidB64,
+    text: B64String,
+}
+
+impl CachedUuidB64 {
+    /// Encode `id` and keep the result
+    pub fn new(id: UuidB64) -> CachedUuidB64 {
+        CachedUuidB64 {
+            id,
+            text: id.to_inline(),
+        }
+    }
+
+    pub fn id(&self) -> UuidB64 {
+        self.id
+    }
+
+    /// The stored text form
+    pub fn as_str(&self) -> &str {
+        self.text.as_str()
+    }
+
+    /// The stored text form, as a `B64String`
+    pub fn text(&self) -> B64String {
+        self.text
+    }
+}
+
+impl UuidB64 {
+    /// Encode this ID once and keep the text next to it, see
+    /// [`CachedUuidB64`](struct.CachedUuidB64.html)
+    pub fn cached(self) -> CachedUuidB64 {
+        CachedUuidB64::new(self)
+    }
+}
+
+impl Default for CachedUuidB64 {
+    fn default() -> CachedUuidB64 {
+        CachedUuidB64::new(UuidB64::NIL)
+    }
+}
+
+impl From<UuidB64> for CachedUuidB64 {
+    fn from(id: UuidB64) -> CachedUuidB64 {
+        CachedUuidB64::new(id)
+    }
+}
+
+impl From<CachedUuidB64> for UuidB64 {
+    fn from(cached: CachedUuidB64) -> UuidB64 {
+        cached.id
+    }
+}
+
+impl FromStr for CachedUuidB64 {
+    type Err = ParseUuidB64Error;
+
+    fn from_str(s: &str) -> Result<CachedUuidB64, ParseUuidB64Error> {
+        s.parse().map(CachedUuidB64::new)
+    }
+}
+
+impl Display for CachedUuidB64 {
+    /// The same as for `UuidB64`, including `{:#}`
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        if f.alternate() {
+            Display::fmt(&self.id, f)
+        } else {
+            f.write_str(self.as_str())
+        }
+    }
+}
+
+impl Debug for CachedUuidB64 {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        write!(f, "CachedUuidB64({})", self.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CachedUuidB64;
+    use UuidB64;
+
+    #[test]
+    fn formats_like_the_id() {
+        let id = UuidB64::new();
+        let cached = id.cached();
+        assert_eq!(cached.to_string(), id.to_string());
+        assert_eq!(format!("{:#}", cached), format!("{:#}", id));
+        assert_eq!(format!("{:?}", cached), format!("CachedUuidB64({})", id));
+        assert_eq!(cached.text(), id.to_inline());
+        assert_eq!(UuidB64::from(cached), id);
+        assert_eq!(CachedUuidB64::default().id(), UuidB64::NIL);
+    }
+
+    #[test]
+    fn parses_like_the_id() {
+        let cached: CachedUuidB64 = "sMHuhm9GTxuNi3hJ51287g".parse().unwrap();
+        assert_eq!(cached, CachedUuidB64::new(cached.id()));
+        assert_eq!(
+            "sMHuhm9GTxuNi3hJ51287h".parse::<CachedUuidB64>(),
+            Err("sMHuhm9GTxuNi3hJ51287h".parse::<UuidB64>().unwrap_err())
+        );
+    }
+}
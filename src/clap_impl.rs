@@ -0,0 +1,99 @@
+//! clap argument parsing
+//!
+//! ```rust,ignore
+//! #[derive(Parser)]
+//! struct Args {
+//!     #[arg(long)]
+//!     id: UuidB64,
+//! }
+//! ```
+//!
+//! or, with the builder API, `Arg::new("id").value_parser(value_parser!(UuidB64))`.
+
+extern crate clap;
+
+use std::ffi::OsStr;
+
+use self::clap::builder::{TypedValueParser, ValueParserFactory};
+use self::clap::error::{Error, ErrorKind};
+use self::clap::{Arg, Command};
+
+use errors::ParseUuidB64Error;
+use UuidB64;
+
+/// Parses clap arguments into `UuidB64`s
+///
+/// `value_parser!(UuidB64)` picks this up, there's no need to name it.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct UuidB64ValueParser;
+
+impl TypedValueParser for UuidB64ValueParser {
+    type Value = UuidB64;
+
+    fn parse_ref(&self, cmd: &Command, arg: Option<&Arg>, value: &OsStr) -> Result<UuidB64, Error> {
+        let arg = arg.map_or_else(|| "...".to_owned(), |arg| arg.to_string());
+        let value = value.to_str().ok_or_else(|| {
+            Error::raw(
+                ErrorKind::InvalidUtf8,
+                format!("invalid UTF-8 for '{}'\n", arg),
+            )
+            .with_cmd(cmd)
+        })?;
+        value.parse().map_err(|e: ParseUuidB64Error| {
+            Error::raw(
+                ErrorKind::ValueValidation,
+                format!("invalid value '{}' for '{}': {}\n", value, arg, e),
+            )
+            .with_cmd(cmd)
+        })
+    }
+}
+
+impl ValueParserFactory for UuidB64 {
+    type Parser = UuidB64ValueParser;
+
+    fn value_parser() -> UuidB64ValueParser {
+        UuidB64ValueParser
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::clap::error::ErrorKind;
+    use super::clap::{value_parser, Arg, Command};
+
+    use UuidB64;
+
+    fn command() -> Command {
+        Command::new("show").arg(
+            Arg::new("id")
+                .long("id")
+                .value_name("ID")
+                .value_parser(value_parser!(UuidB64)),
+        )
+    }
+
+    #[test]
+    fn parses_ids() {
+        let matches = command()
+            .try_get_matches_from(["show", "--id", "sMHuhm9GTxuNi3hJ51287g"])
+            .unwrap();
+        assert_eq!(
+            matches.get_one::<UuidB64>("id").unwrap().to_string(),
+            "sMHuhm9GTxuNi3hJ51287g"
+        );
+    }
+
+    #[test]
+    fn explains_malformed_ids() {
+        let err = command()
+            .try_get_matches_from(["show", "--id", "nope"])
+            .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::ValueValidation);
+        assert_eq!(
+            err.to_string(),
+            "error: invalid value 'nope' for '--id <ID>': \
+             Invalid Base64 representation for UUID: expected 22 characters, found 4\n"
+        );
+    }
+}
@@ -0,0 +1,99 @@
+//! Serde support for comma-separated lists of IDs
+//!
+//! Use with `#[serde(with = "uuid_b64::comma_separated")]` on a
+//! `Vec<UuidB64>` field to (de)serialize it as a single `"a,b,c"` string,
+//! which is how list parameters usually show up in query strings.
+//!
+//! ```
+//! # extern crate serde_json;
+//! # #[macro_use] extern crate serde_derive;
+//! # extern crate uuid_b64;
+//! # use uuid_b64::UuidB64;
+//! #[derive(Deserialize, Serialize)]
+//! struct ListQuery {
+//!     #[serde(with = "uuid_b64::comma_separated")]
+//!     ids: Vec<UuidB64>,
+//! }
+//!
+//! # fn main() {
+//! let query: ListQuery =
+//!     serde_json::from_str(r#"{"ids": "sMHuhm9GTxuNi3hJ51287g,AAAAAQACAAMBAgMEBQYHCA"}"#).unwrap();
+//! assert_eq!(query.ids.len(), 2);
+//! # }
+//! ```
+
+extern crate serde;
+
+use std::fmt::{Formatter, Result as FmtResult};
+
+use self::serde::de::{self, Deserializer, Visitor};
+use self::serde::ser::Serializer;
+
+use UuidB64;
+
+/// Serialize IDs as a single comma-separated string
+pub fn serialize<S>(ids: &[UuidB64], serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    let mut list = String::with_capacity(ids.len() * 23);
+    for (i, id) in ids.iter().enumerate() {
+        if i > 0 {
+            list.push(',');
+        }
+        id.to_buf(&mut list);
+    }
+    serializer.serialize_str(&list)
+}
+
+/// Deserialize IDs from a single comma-separated string
+pub fn deserialize<'de, D>(deserializer: D) -> Result<Vec<UuidB64>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    deserializer.deserialize_str(ListVisitor)
+}
+
+struct ListVisitor;
+
+impl<'de> Visitor<'de> for ListVisitor {
+    type Value = Vec<UuidB64>;
+
+    fn expecting(&self, f: &mut Formatter) -> FmtResult {
+        write!(f, "a comma-separated list of Base64-encoded strings")
+    }
+
+    fn visit_str<E>(self, s: &str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        UuidB64::parse_list(s).map_err(de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use UuidB64;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Query {
+        #[serde(with = "::comma_separated")]
+        ids: Vec<UuidB64>,
+    }
+
+    #[test]
+    fn roundtrips() {
+        let query = Query {
+            ids: vec![UuidB64::new(), UuidB64::new()],
+        };
+        let json = ::serde_json::to_string(&query).unwrap();
+        assert_eq!(json, format!(r#"{{"ids":"{},{}"}}"#, query.ids[0], query.ids[1]));
+        assert_eq!(::serde_json::from_str::<Query>(&json).unwrap(), query);
+    }
+
+    #[test]
+    fn empty() {
+        let query: Query = ::serde_json::from_str(r#"{"ids":""}"#).unwrap();
+        assert_eq!(query.ids, vec![]);
+    }
+}
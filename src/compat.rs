@@ -0,0 +1,125 @@
+//! Serde support for migrating from hyphenated UUIDs
+//!
+//! Use with `#[serde(with = "uuid_b64::compat")]` on a `UuidB64` field to
+//! accept both the usual Base64 form and the 36-character hyphenated form
+//! when deserializing, while still serializing as Base64. This gives
+//! clients that send hyphenated UUIDs a transition period.
+//!
+//! ```
+//! # extern crate serde_json;
+//! # #[macro_use] extern crate serde_derive;
+//! # extern crate uuid_b64;
+//! # use uuid_b64::UuidB64;
+//! #[derive(Deserialize, Serialize)]
+//! struct User {
+//!     #[serde(with = "uuid_b64::compat")]
+//!     id: UuidB64,
+//! }
+//!
+//! # fn main() {
+//! let old: User = serde_json::from_str(r#"{"id": "b0c1ee86-6f46-4f1b-8d8b-7849e75dbcee"}"#).unwrap();
+//! let new: User = serde_json::from_str(r#"{"id": "sMHuhm9GTxuNi3hJ51287g"}"#).unwrap();
+//! assert_eq!(old.id, new.id);
+//! assert_eq!(serde_json::to_string(&old).unwrap(), r#"{"id":"sMHuhm9GTxuNi3hJ51287g"}"#);
+//! # }
+//! ```
+
+extern crate serde;
+
+use std::fmt::{Formatter, Result as FmtResult};
+
+use self::serde::de::{self, Deserialize, Deserializer, Visitor};
+use self::serde::ser::{Serialize, Serializer};
+use uuid::Uuid;
+
+use UuidB64;
+
+/// Serialize an ID as usual
+pub fn serialize<S>(id: &UuidB64, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    id.serialize(serializer)
+}
+
+/// Deserialize an ID from either its Base64 or its hyphenated form
+///
+/// Binary formats have no hyphenated form, so they deserialize as usual.
+pub fn deserialize<'de, D>(deserializer: D) -> Result<UuidB64, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    if deserializer.is_human_readable() {
+        deserializer.deserialize_str(CompatVisitor)
+    } else {
+        UuidB64::deserialize(deserializer)
+    }
+}
+
+struct CompatVisitor;
+
+impl<'de> Visitor<'de> for CompatVisitor {
+    type Value = UuidB64;
+
+    fn expecting(&self, f: &mut Formatter) -> FmtResult {
+        write!(f, "a Base64-encoded string or a hyphenated UUID")
+    }
+
+    fn visit_str<E>(self, s: &str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        if s.len() == 36 {
+            Uuid::parse_str(s).map(UuidB64).map_err(de::Error::custom)
+        } else {
+            s.parse().map_err(de::Error::custom)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use UuidB64;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct User {
+        #[serde(with = "::compat")]
+        id: UuidB64,
+    }
+
+    #[test]
+    fn accepts_both_forms() {
+        let id: UuidB64 = "sMHuhm9GTxuNi3hJ51287g".parse().unwrap();
+        for json in &[
+            r#"{"id":"sMHuhm9GTxuNi3hJ51287g"}"#,
+            r#"{"id":"b0c1ee86-6f46-4f1b-8d8b-7849e75dbcee"}"#,
+            r#"{"id":"B0C1EE86-6F46-4F1B-8D8B-7849E75DBCEE"}"#,
+        ] {
+            let user: User = ::serde_json::from_str(json).unwrap();
+            assert_eq!(user, User { id });
+        }
+        assert_eq!(
+            ::serde_json::to_string(&User { id }).unwrap(),
+            r#"{"id":"sMHuhm9GTxuNi3hJ51287g"}"#
+        );
+    }
+
+    #[test]
+    fn rejects_other_forms() {
+        for json in &[
+            r#"{"id":"b0c1ee866f464f1b8d8b7849e75dbcee"}"#,
+            r#"{"id":"b0c1ee86-6f46-4f1b-8d8b-7849e75dbceg"}"#,
+            r#"{"id":""}"#,
+        ] {
+            assert!(::serde_json::from_str::<User>(json).is_err(), "{}", json);
+        }
+    }
+
+    #[test]
+    fn binary_formats_unchanged() {
+        let user = User { id: UuidB64::new() };
+        let encoded = ::bincode::serialize(&user).unwrap();
+        assert_eq!(encoded, ::bincode::serialize(&user.id).unwrap());
+        assert_eq!(::bincode::deserialize::<User>(&encoded).unwrap(), user);
+    }
+}
@@ -0,0 +1,280 @@
+//! SQL functions for converting IDs in DataFusion
+//!
+//! ```rust,ignore
+//! let ctx = SessionContext::new();
+//! ctx.register_udf(uuid_b64::b64uuid_udf());
+//! ctx.register_udf(uuid_b64::uuid_b64_udf());
+//!
+//! ctx.sql("SELECT uuid_b64(id) FROM 'orders.parquet' WHERE id = b64uuid('sMHuhm9GTxuNi3hJ51287g')")
+//!     .await?;
+//! ```
+//!
+//! UUIDs are `FixedSizeBinary(16)`, which is how Parquet's `UUID` columns
+//! are read, so `b64uuid` can be compared against them directly.
+
+extern crate datafusion_common;
+extern crate datafusion_expr;
+
+use std::convert::TryFrom;
+use std::sync::Arc;
+
+use uuid::Uuid;
+
+use self::datafusion_common::arrow::array::{Array, ArrayRef, FixedSizeBinaryArray, StringArray};
+use self::datafusion_common::arrow::compute::cast;
+use self::datafusion_common::arrow::datatypes::DataType;
+use self::datafusion_common::{DataFusionError, Result};
+use self::datafusion_expr::{
+    ColumnarValue, ScalarFunctionArgs, ScalarUDF, ScalarUDFImpl, Signature, Volatility,
+};
+
+use UuidB64;
+
+const UUID_TYPE: DataType = DataType::FixedSizeBinary(16);
+
+/// `b64uuid(text)`: the UUID for an ID, as `FixedSizeBinary(16)`
+///
+/// Fails on text that isn't an ID, `NULL`s stay `NULL`.
+pub fn b64uuid_udf() -> ScalarUDF {
+    ScalarUDF::from(B64Uuid {
+        signature: Signature::string(1, Volatility::Immutable),
+    })
+}
+
+/// `uuid_b64(uuid)`: the ID for a UUID, as text
+///
+/// Accepts `FixedSizeBinary(16)` or hyphenated UUID text, and fails on
+/// anything that isn't a UUID. `NULL`s stay `NULL`.
+pub fn uuid_b64_udf() -> ScalarUDF {
+    ScalarUDF::from(UuidToB64 {
+        signature: Signature::uniform(
+            1,
+            vec![
+                UUID_TYPE,
+                DataType::Utf8,
+                DataType::LargeUtf8,
+                DataType::Utf8View,
+            ],
+            Volatility::Immutable,
+        ),
+    })
+}
+
+#[derive(Debug, PartialEq, Eq, Hash)]
+struct B64Uuid {
+    signature: Signature,
+}
+
+impl ScalarUDFImpl for B64Uuid {
+    fn name(&self) -> &str {
+        "b64uuid"
+    }
+
+    fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    fn return_type(&self, _: &[DataType]) -> Result<DataType> {
+        Ok(UUID_TYPE)
+    }
+
+    fn invoke_with_args(&self, args: ScalarFunctionArgs) -> Result<ColumnarValue> {
+        let text = single_arg(args)?;
+        let text = cast(&text, &DataType::Utf8)?;
+        let text = text
+            .as_any()
+            .downcast_ref::<StringArray>()
+            .expect("cast to Utf8");
+        let uuids = text
+            .iter()
+            .map(|id| {
+                id.map(|id| {
+                    id.parse::<UuidB64>()
+                        .map(|id| *id.0.as_bytes())
+                        .map_err(|e| DataFusionError::Execution(format!("b64uuid: {}", e)))
+                })
+                .transpose()
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let uuids = FixedSizeBinaryArray::try_from_sparse_iter_with_size(uuids.into_iter(), 16)?;
+        Ok(ColumnarValue::Array(Arc::new(uuids)))
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Hash)]
+struct UuidToB64 {
+    signature: Signature,
+}
+
+impl ScalarUDFImpl for UuidToB64 {
+    fn name(&self) -> &str {
+        "uuid_b64"
+    }
+
+    fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    fn return_type(&self, _: &[DataType]) -> Result<DataType> {
+        Ok(DataType::Utf8)
+    }
+
+    fn invoke_with_args(&self, args: ScalarFunctionArgs) -> Result<ColumnarValue> {
+        let uuids = single_arg(args)?;
+        let invalid = |uuid: &str| {
+            DataFusionError::Execution(format!("uuid_b64: '{}' is not a valid UUID", uuid))
+        };
+        let ids: StringArray = if *uuids.data_type() == UUID_TYPE {
+            let uuids = uuids
+                .as_any()
+                .downcast_ref::<FixedSizeBinaryArray>()
+                .expect("checked the type");
+            uuids
+                .iter()
+                .map(|uuid| uuid.map(|uuid| from_uuid_bytes(uuid).to_string()))
+                .collect()
+        } else {
+            let uuids = cast(&uuids, &DataType::Utf8)?;
+            let uuids = uuids
+                .as_any()
+                .downcast_ref::<StringArray>()
+                .expect("cast to Utf8");
+            uuids
+                .iter()
+                .map(|uuid| {
+                    uuid.map(|uuid| {
+                        Uuid::parse_str(uuid)
+                            .map(|uuid| UuidB64(uuid).to_string())
+                            .map_err(|_| invalid(uuid))
+                    })
+                    .transpose()
+                })
+                .collect::<Result<_>>()?
+        };
+        Ok(ColumnarValue::Array(Arc::new(ids)))
+    }
+}
+
+fn single_arg(args: ScalarFunctionArgs) -> Result<ArrayRef> {
+    let rows = args.number_rows;
+    match <[ColumnarValue; 1]>::try_from(args.args) {
+        Ok([arg]) => arg.into_array(rows),
+        Err(args) => Err(DataFusionError::Plan(format!(
+            "expected 1 argument, got {}",
+            args.len()
+        ))),
+    }
+}
+
+fn from_uuid_bytes(bytes: &[u8]) -> UuidB64 {
+    UuidB64(Uuid::from_bytes(bytes).expect("FixedSizeBinary(16) is 16 bytes"))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::datafusion_common::arrow::array::{
+        Array, ArrayRef, FixedSizeBinaryArray, StringArray, StringViewArray,
+    };
+    use super::datafusion_common::arrow::datatypes::Field;
+    use super::datafusion_common::config::ConfigOptions;
+    use super::datafusion_common::{Result, ScalarValue};
+    use super::datafusion_expr::{ColumnarValue, ScalarFunctionArgs, ScalarUDF};
+    use super::{b64uuid_udf, uuid_b64_udf};
+
+    const UUID: &str = "b0c1ee86-6f46-4f1b-8d8b-7849e75dbcee";
+    const ID: &str = "sMHuhm9GTxuNi3hJ51287g";
+
+    fn call(udf: &ScalarUDF, arg: ColumnarValue, rows: usize) -> Result<ArrayRef> {
+        let field = Arc::new(Field::new("arg", arg.data_type(), true));
+        let return_field = udf.return_field_from_args(super::datafusion_expr::ReturnFieldArgs {
+            arg_fields: ::std::slice::from_ref(&field),
+            scalar_arguments: &[None],
+        })?;
+        let result = udf.invoke_with_args(ScalarFunctionArgs {
+            args: vec![arg],
+            arg_fields: vec![field],
+            number_rows: rows,
+            return_field,
+            config_options: Arc::new(ConfigOptions::default()),
+        })?;
+        result.into_array(rows)
+    }
+
+    fn uuid_bytes() -> Vec<u8> {
+        ::uuid::Uuid::parse_str(UUID).unwrap().as_bytes().to_vec()
+    }
+
+    #[test]
+    fn b64uuid_decodes() {
+        let ids = StringArray::from(vec![Some(ID), None]);
+        let uuids = call(&b64uuid_udf(), ColumnarValue::Array(Arc::new(ids)), 2).unwrap();
+        let uuids = uuids
+            .as_any()
+            .downcast_ref::<FixedSizeBinaryArray>()
+            .unwrap();
+        assert_eq!(uuids.value(0), &uuid_bytes()[..]);
+        assert!(uuids.is_null(1));
+    }
+
+    #[test]
+    fn b64uuid_takes_string_views_and_scalars() {
+        let ids = StringViewArray::from(vec![ID]);
+        let uuids = call(&b64uuid_udf(), ColumnarValue::Array(Arc::new(ids)), 1).unwrap();
+        assert_eq!(uuids.len(), 1);
+
+        let scalar = ColumnarValue::Scalar(ScalarValue::Utf8(Some(ID.to_owned())));
+        let uuids = call(&b64uuid_udf(), scalar, 3).unwrap();
+        assert_eq!(uuids.len(), 3);
+    }
+
+    #[test]
+    fn b64uuid_rejects_garbage() {
+        let ids = StringArray::from(vec!["nope"]);
+        let err = call(&b64uuid_udf(), ColumnarValue::Array(Arc::new(ids)), 1).unwrap_err();
+        assert!(err.to_string().contains("expected 22 characters"), "{}", err);
+    }
+
+    #[test]
+    fn uuid_b64_encodes_binary_and_text() {
+        let uuids = FixedSizeBinaryArray::try_from_sparse_iter_with_size(
+            vec![Some(uuid_bytes()), None].into_iter(),
+            16,
+        )
+        .unwrap();
+        let ids = call(&uuid_b64_udf(), ColumnarValue::Array(Arc::new(uuids)), 2).unwrap();
+        let ids = ids.as_any().downcast_ref::<StringArray>().unwrap();
+        assert_eq!(
+            ids.value(0)
+                .parse::<::UuidB64>()
+                .unwrap()
+                .uuid()
+                .hyphenated()
+                .to_string(),
+            UUID
+        );
+        assert!(ids.is_null(1));
+
+        let text = StringArray::from(vec![UUID]);
+        let from_text = call(&uuid_b64_udf(), ColumnarValue::Array(Arc::new(text)), 1).unwrap();
+        let from_text = from_text.as_any().downcast_ref::<StringArray>().unwrap();
+        assert_eq!(from_text.value(0), ids.value(0));
+    }
+
+    #[test]
+    fn roundtrip() {
+        let id = ::UuidB64::new();
+        let ids = StringArray::from(vec![id.to_string()]);
+        let uuids = call(&b64uuid_udf(), ColumnarValue::Array(Arc::new(ids)), 1).unwrap();
+        let back = call(&uuid_b64_udf(), ColumnarValue::Array(uuids), 1).unwrap();
+        let back = back.as_any().downcast_ref::<StringArray>().unwrap();
+        assert_eq!(back.value(0), id.to_string());
+    }
+
+    #[test]
+    fn uuid_b64_rejects_garbage() {
+        let text = StringArray::from(vec!["nope"]);
+        assert!(call(&uuid_b64_udf(), ColumnarValue::Array(Arc::new(text)), 1).is_err());
+    }
+}
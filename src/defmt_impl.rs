@@ -0,0 +1,32 @@
+//! defmt logging, for embedded targets
+
+extern crate defmt;
+
+use self::defmt::{Format, Formatter};
+
+use UuidB64;
+
+impl Format for UuidB64 {
+    /// Logs the 22 Base64 characters, encoded on the target without
+    /// `core::fmt`
+    fn format(&self, f: Formatter) {
+        defmt::write!(f, "{=str}", self.encode_to(&mut [0; 22]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::defmt::Format;
+
+    use UuidB64;
+
+    fn assert_format<T: Format>() {}
+
+    // actually logging needs a global logger, which only targets have
+    #[test]
+    fn is_format() {
+        assert_format::<UuidB64>();
+        assert_format::<Option<UuidB64>>();
+        assert_format::<[UuidB64; 2]>();
+    }
+}
@@ -0,0 +1,155 @@
+//! Deterministic IDs: name-based (v3 and v5) UUIDs and child IDs
+
+use uuid::Uuid;
+
+use UuidB64;
+
+impl UuidB64 {
+    /// The RFC 9562 namespace for fully-qualified domain names
+    pub const NAMESPACE_DNS: UuidB64 = UuidB64::from_bytes([
+        0x6b, 0xa7, 0xb8, 0x10, 0x9d, 0xad, 0x11, 0xd1, 0x80, 0xb4, 0x00, 0xc0, 0x4f, 0xd4, 0x30,
+        0xc8,
+    ]);
+    /// The RFC 9562 namespace for URLs
+    pub const NAMESPACE_URL: UuidB64 = UuidB64::from_bytes([
+        0x6b, 0xa7, 0xb8, 0x11, 0x9d, 0xad, 0x11, 0xd1, 0x80, 0xb4, 0x00, 0xc0, 0x4f, 0xd4, 0x30,
+        0xc8,
+    ]);
+    /// The RFC 9562 namespace for ISO OIDs
+    pub const NAMESPACE_OID: UuidB64 = UuidB64::from_bytes([
+        0x6b, 0xa7, 0xb8, 0x12, 0x9d, 0xad, 0x11, 0xd1, 0x80, 0xb4, 0x00, 0xc0, 0x4f, 0xd4, 0x30,
+        0xc8,
+    ]);
+    /// The RFC 9562 namespace for X.500 DNs
+    pub const NAMESPACE_X500: UuidB64 = UuidB64::from_bytes([
+        0x6b, 0xa7, 0xb8, 0x14, 0x9d, 0xad, 0x11, 0xd1, 0x80, 0xb4, 0x00, 0xc0, 0x4f, 0xd4, 0x30,
+        0xc8,
+    ]);
+
+    /// The v5 (SHA-1) UUID for `name` in `namespace`
+    ///
+    /// `namespace` can be a `Uuid` or a `UuidB64`.
+    ///
+    /// ```
+    /// # use uuid_b64::UuidB64;
+    /// let id = UuidB64::new_v5(UuidB64::NAMESPACE_DNS, "example.com");
+    /// assert_eq!(
+    ///     id.uuid().hyphenated().to_string(),
+    ///     "cfbff0d1-9375-5685-968c-48ce8b15ae17"
+    /// );
+    /// ```
+    pub fn new_v5<N: Into<UuidB64>>(namespace: N, name: &str) -> UuidB64 {
+        UuidB64(Uuid::new_v5(&namespace.into().0, name))
+    }
+
+    /// The v3 (MD5) UUID for `name` in `namespace`
+    ///
+    /// Only for compatibility with systems that already use v3, new
+    /// deterministic IDs should be [v5](#method.new_v5).
+    pub fn new_v3<N: Into<UuidB64>>(namespace: N, name: &str) -> UuidB64 {
+        UuidB64(Uuid::new_v3(&namespace.into().0, name))
+    }
+
+    /// A deterministic child of this ID, for resources that can be
+    /// computed from their parent instead of stored
+    ///
+    /// This is a standard v5 UUID with this ID as the namespace, so the
+    /// same child can be computed anywhere that has a v5 implementation,
+    /// Python's `uuid.uuid5(parent, name)` for example.
+    ///
+    /// ```
+    /// # use uuid_b64::UuidB64;
+    /// let tenant: UuidB64 = "sMHuhm9GTxuNi3hJ51287g".parse().unwrap();
+    /// let settings = tenant.derive_child("settings");
+    /// assert_eq!(
+    ///     settings.uuid().hyphenated().to_string(),
+    ///     "cb0012fa-21d2-5d51-a1cd-33e7078073b3",
+    /// );
+    /// assert_eq!(settings, tenant.derive_child("settings"));
+    /// ```
+    pub fn derive_child(&self, name: &str) -> UuidB64 {
+        UuidB64(Uuid::new_v5(&self.0, name))
+    }
+
+    /// Apply `derive_child` once for each name in `path`, in order
+    ///
+    /// An empty path gives back this ID.
+    ///
+    /// ```
+    /// # use uuid_b64::UuidB64;
+    /// let tenant: UuidB64 = "sMHuhm9GTxuNi3hJ51287g".parse().unwrap();
+    /// assert_eq!(
+    ///     tenant.derive_path(&["tenant-defaults", "inbox"]),
+    ///     tenant.derive_child("tenant-defaults").derive_child("inbox"),
+    /// );
+    /// ```
+    pub fn derive_path<I, S>(&self, path: I) -> UuidB64
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        path.into_iter()
+            .fold(*self, |parent, name| parent.derive_child(name.as_ref()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use UuidB64;
+
+    fn known() -> UuidB64 {
+        "sMHuhm9GTxuNi3hJ51287g".parse().unwrap()
+    }
+
+    #[test]
+    fn namespaces_match_uuid() {
+        use uuid::{NAMESPACE_DNS, NAMESPACE_OID, NAMESPACE_URL, NAMESPACE_X500};
+
+        assert_eq!(UuidB64::NAMESPACE_DNS.uuid(), NAMESPACE_DNS);
+        assert_eq!(UuidB64::NAMESPACE_URL.uuid(), NAMESPACE_URL);
+        assert_eq!(UuidB64::NAMESPACE_OID.uuid(), NAMESPACE_OID);
+        assert_eq!(UuidB64::NAMESPACE_X500.uuid(), NAMESPACE_X500);
+    }
+
+    #[test]
+    fn name_based() {
+        let v5 = UuidB64::new_v5(UuidB64::NAMESPACE_DNS, "example.com");
+        let v3 = UuidB64::new_v3(UuidB64::NAMESPACE_DNS, "example.com");
+        assert_eq!(v5.uuid().get_version_num(), 5);
+        assert_eq!(
+            v3.uuid().hyphenated().to_string(),
+            "9073926b-929f-31c2-abc9-fad77ae3e8eb"
+        );
+        assert_eq!(v5, UuidB64::new_v5(UuidB64::NAMESPACE_DNS.uuid(), "example.com"));
+        assert_eq!(v5, UuidB64::NAMESPACE_DNS.derive_child("example.com"));
+    }
+
+    #[test]
+    fn children_are_v5() {
+        let child = known().derive_child("settings");
+        assert_eq!(child.uuid().get_version_num(), 5);
+        assert_eq!(
+            child.uuid().hyphenated().to_string(),
+            "cb0012fa-21d2-5d51-a1cd-33e7078073b3"
+        );
+    }
+
+    #[test]
+    fn siblings_and_cousins_differ() {
+        let parent = known();
+        assert_ne!(parent.derive_child("a"), parent.derive_child("b"));
+        assert_ne!(parent.derive_child("a"), UuidB64::new().derive_child("a"));
+    }
+
+    #[test]
+    fn paths() {
+        let parent = known();
+        assert_eq!(parent.derive_path(Vec::<String>::new()), parent);
+        let inbox = parent.derive_path(vec!["tenant-defaults".to_owned(), "inbox".to_owned()]);
+        assert_eq!(
+            inbox.uuid().hyphenated().to_string(),
+            "90d01ee3-1fc1-555b-b027-ec09e1b81d8a"
+        );
+        assert_ne!(inbox, parent.derive_path(["inbox", "tenant-defaults"].iter()));
+    }
+}
@@ -0,0 +1,276 @@
+//! Diesel integration
+//!
+//! IDs map to three SQL types:
+//!
+//! * `Uuid`, on Postgres, with the `diesel-uuid` feature. `Vec<UuidB64>`
+//!   maps to `Array<Uuid>` too.
+//! * `Binary`, the raw 16 bytes, for `BLOB`s on SQLite and `BINARY(16)` on
+//!   MySQL.
+//! * `Text`, written as the 22-character Base64 form. Hyphenated UUIDs are
+//!   read as well, so existing `CHAR(36)` columns keep working.
+//!
+//! ```rust,ignore
+//! table! {
+//!     users {
+//!         id -> Binary,
+//!         name -> Text,
+//!     }
+//! }
+//!
+//! #[derive(Queryable, Insertable)]
+//! #[table_name = "users"]
+//! struct User {
+//!     id: UuidB64,
+//!     name: String,
+//! }
+//! ```
+
+extern crate diesel;
+
+use std::error::Error;
+use std::io::Write;
+
+use self::diesel::backend::Backend;
+use self::diesel::deserialize::{self, FromSql, FromSqlRow, Queryable};
+use self::diesel::expression::bound::Bound;
+use self::diesel::expression::AsExpression;
+use self::diesel::row::Row;
+use self::diesel::serialize::{self, IsNull, Output, ToSql};
+use self::diesel::sql_types::{Binary, Nullable, Text};
+use uuid::Uuid;
+
+use UuidB64;
+
+macro_rules! as_expression {
+    ($sql_type:ty) => {
+        impl AsExpression<$sql_type> for UuidB64 {
+            type Expression = Bound<$sql_type, UuidB64>;
+
+            fn as_expression(self) -> Self::Expression {
+                Bound::new(self)
+            }
+        }
+
+        impl<'a> AsExpression<$sql_type> for &'a UuidB64 {
+            type Expression = Bound<$sql_type, &'a UuidB64>;
+
+            fn as_expression(self) -> Self::Expression {
+                Bound::new(self)
+            }
+        }
+
+        impl AsExpression<Nullable<$sql_type>> for UuidB64 {
+            type Expression = Bound<Nullable<$sql_type>, UuidB64>;
+
+            fn as_expression(self) -> Self::Expression {
+                Bound::new(self)
+            }
+        }
+
+        impl<'a> AsExpression<Nullable<$sql_type>> for &'a UuidB64 {
+            type Expression = Bound<Nullable<$sql_type>, &'a UuidB64>;
+
+            fn as_expression(self) -> Self::Expression {
+                Bound::new(self)
+            }
+        }
+    };
+}
+
+as_expression!(Binary);
+as_expression!(Text);
+
+impl<DB: Backend> ToSql<Binary, DB> for UuidB64 {
+    fn to_sql<W: Write>(&self, out: &mut Output<W, DB>) -> serialize::Result {
+        out.write_all(self.0.as_bytes())?;
+        Ok(IsNull::No)
+    }
+}
+
+impl<DB> FromSql<Binary, DB> for UuidB64
+where
+    DB: Backend,
+    *const [u8]: FromSql<Binary, DB>,
+{
+    fn from_sql(bytes: Option<&DB::RawValue>) -> deserialize::Result<Self> {
+        let bytes = <*const [u8] as FromSql<Binary, DB>>::from_sql(bytes)?;
+        // Diesel guarantees the pointer is valid for the length of this call
+        let bytes = unsafe { &*bytes };
+        if bytes.len() != 16 {
+            return Err(format!("expected 16 bytes for a UUID, got {}", bytes.len()).into());
+        }
+        Ok(UuidB64(Uuid::from_bytes(bytes)?))
+    }
+}
+
+impl<DB: Backend> ToSql<Text, DB> for UuidB64 {
+    fn to_sql<W: Write>(&self, out: &mut Output<W, DB>) -> serialize::Result {
+        out.write_all(self.to_istring().as_bytes())?;
+        Ok(IsNull::No)
+    }
+}
+
+impl<DB> FromSql<Text, DB> for UuidB64
+where
+    DB: Backend,
+    *const str: FromSql<Text, DB>,
+{
+    fn from_sql(bytes: Option<&DB::RawValue>) -> deserialize::Result<Self> {
+        let text = <*const str as FromSql<Text, DB>>::from_sql(bytes)?;
+        // Diesel guarantees the pointer is valid for the length of this call
+        let text = unsafe { &*text };
+        if text.len() == 22 {
+            text.parse()
+                .map_err(|e: ::errors::ParseUuidB64Error| e.to_string().into())
+        } else {
+            Ok(UuidB64(Uuid::parse_str(text).map_err(
+                |e| -> Box<dyn Error + Send + Sync> { e.to_string().into() },
+            )?))
+        }
+    }
+}
+
+impl<ST, DB> FromSqlRow<ST, DB> for UuidB64
+where
+    DB: Backend,
+    UuidB64: FromSql<ST, DB>,
+{
+    fn build_from_row<R: Row<DB>>(row: &mut R) -> deserialize::Result<Self> {
+        FromSql::<ST, DB>::from_sql(row.take())
+    }
+}
+
+impl<ST, DB> Queryable<ST, DB> for UuidB64
+where
+    DB: Backend,
+    UuidB64: FromSql<ST, DB>,
+{
+    type Row = Self;
+
+    fn build(row: Self::Row) -> Self {
+        row
+    }
+}
+
+#[cfg(feature = "diesel-uuid")]
+mod pg {
+    use std::io::Write;
+
+    use super::diesel::deserialize::{self, FromSql};
+    use super::diesel::expression::bound::Bound;
+    use super::diesel::expression::AsExpression;
+    use super::diesel::pg::Pg;
+    use super::diesel::serialize::{self, Output, ToSql};
+    use super::diesel::sql_types::{Nullable, Uuid as SqlUuid};
+    use uuid::Uuid;
+
+    use UuidB64;
+
+    as_expression!(SqlUuid);
+
+    impl ToSql<SqlUuid, Pg> for UuidB64 {
+        fn to_sql<W: Write>(&self, out: &mut Output<W, Pg>) -> serialize::Result {
+            ToSql::<SqlUuid, Pg>::to_sql(&self.0, out)
+        }
+    }
+
+    impl FromSql<SqlUuid, Pg> for UuidB64 {
+        fn from_sql(bytes: Option<&[u8]>) -> deserialize::Result<Self> {
+            <Uuid as FromSql<SqlUuid, Pg>>::from_sql(bytes).map(UuidB64)
+        }
+    }
+}
+
+// Binary and Text work the same way on every backend, but Postgres is the
+// only one that `run-tests.sh` provides
+#[cfg(all(test, feature = "diesel-uuid"))]
+#[allow(non_local_definitions)] // diesel 1's derives and `table!`
+mod tests {
+    use std::env;
+
+    use super::diesel::dsl::sql;
+    use super::diesel::pg::PgConnection;
+    use super::diesel::prelude::*;
+    use super::diesel::sql_query;
+    use super::diesel::sql_types::{Binary, Text};
+
+    use UuidB64;
+
+    table! {
+        blobs (id) {
+            id -> Binary,
+            parent -> Nullable<Binary>,
+        }
+    }
+
+    table! {
+        texts (id) {
+            id -> Text,
+        }
+    }
+
+    fn connection() -> PgConnection {
+        let db_url = env::var("PG_DATABASE_URL").expect("PG_DB_URL must be in the environment");
+        let conn = PgConnection::establish(&db_url).unwrap();
+        sql_query("CREATE TEMPORARY TABLE blobs (id BYTEA PRIMARY KEY, parent BYTEA)")
+            .execute(&conn)
+            .unwrap();
+        sql_query("CREATE TEMPORARY TABLE texts (id TEXT PRIMARY KEY)")
+            .execute(&conn)
+            .unwrap();
+        conn
+    }
+
+    #[test]
+    fn binary_roundtrips() {
+        let conn = connection();
+        let id = UuidB64::new();
+        let parent = UuidB64::new();
+        ::diesel::insert_into(blobs::table)
+            .values((blobs::id.eq(id), blobs::parent.eq(Some(parent))))
+            .execute(&conn)
+            .unwrap();
+        let found: (UuidB64, Option<UuidB64>) =
+            blobs::table.filter(blobs::id.eq(&id)).first(&conn).unwrap();
+        assert_eq!(found, (id, Some(parent)));
+        let raw: Vec<u8> = blobs::table.select(blobs::id).first(&conn).unwrap();
+        assert_eq!(&raw[..], id.uuid().as_bytes());
+    }
+
+    #[test]
+    fn text_roundtrips() {
+        let conn = connection();
+        let id = UuidB64::new();
+        ::diesel::insert_into(texts::table)
+            .values(texts::id.eq(id))
+            .execute(&conn)
+            .unwrap();
+        let raw: String = texts::table.select(texts::id).first(&conn).unwrap();
+        assert_eq!(raw, id.to_string());
+        let found: UuidB64 = texts::table
+            .select(texts::id)
+            .filter(texts::id.eq(id))
+            .first(&conn)
+            .unwrap();
+        assert_eq!(found, id);
+    }
+
+    #[test]
+    fn reads_hyphenated_text() {
+        let conn = connection();
+        let found: UuidB64 =
+            ::diesel::select(sql::<Text>("'b0c1ee86-6f46-4f1b-8d8b-7849e75dbcee'"))
+                .first(&conn)
+                .unwrap();
+        assert_eq!(found.to_string(), "sMHuhm9GTxuNi3hJ51287g");
+    }
+
+    #[test]
+    fn rejects_wrong_lengths() {
+        let conn = connection();
+        let found = ::diesel::select(sql::<Binary>("'\\x0102'::bytea")).first::<UuidB64>(&conn);
+        assert!(found.is_err());
+        let found = ::diesel::select(sql::<Text>("'nope'")).first::<UuidB64>(&conn);
+        assert!(found.is_err());
+    }
+}
@@ -0,0 +1,510 @@
+//! Runtime-configurable encodings, for matching other systems' formats
+
+use std::fmt::{Display, Formatter, Result as FmtResult};
+
+use base64::display::Base64Display;
+use base64::{self, CharacterSet, Config, LineWrap};
+
+use errors::ErrorKind;
+use UuidB64;
+
+const STANDARD_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+pub(crate) const URL_SAFE_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+/// The alphabet that `Display` and `FromStr` use
+#[cfg(not(feature = "standard-alphabet"))]
+pub(crate) const BUILD_ALPHABET: Alphabet = Alphabet::UrlSafe;
+#[cfg(feature = "standard-alphabet")]
+pub(crate) const BUILD_ALPHABET: Alphabet = Alphabet::Standard;
+
+/// Which base64 alphabet to use
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Alphabet {
+    /// `A-Z`, `a-z`, `0-9`, `-` and `_`
+    UrlSafe,
+    /// `A-Z`, `a-z`, `0-9`, `+` and `/`
+    Standard,
+}
+
+impl Alphabet {
+    fn symbols(self) -> &'static [u8; 64] {
+        match self {
+            Alphabet::UrlSafe => URL_SAFE_ALPHABET,
+            Alphabet::Standard => STANDARD_ALPHABET,
+        }
+    }
+
+    pub(crate) fn value_of(self, c: char) -> Option<usize> {
+        self.symbols().iter().position(|&s| char::from(s) == c)
+    }
+
+    fn base64_config(self) -> Config {
+        let char_set = match self {
+            Alphabet::UrlSafe => CharacterSet::UrlSafe,
+            Alphabet::Standard => CharacterSet::Standard,
+        };
+        Config::new(char_set, false, false, LineWrap::NoWrap)
+    }
+}
+
+/// A description of a non-default text format for IDs
+///
+/// The default configuration produces exactly the same output as `Display`.
+/// Each option changes the 22-character canonical form, in this order:
+///
+/// * `alphabet` picks which base64 alphabet is used.
+/// * `padding` appends the `==` that standard base64 would add.
+/// * `check_digit` appends one [Luhn mod 64][luhn] check character, which
+///   catches any single mistyped character and most swapped pairs.
+/// * `grouping` splits the result into groups of the given size, joined by a
+///   separator character.
+///
+/// ```
+/// # extern crate uuid;
+/// # extern crate uuid_b64;
+/// # use uuid::Uuid;
+/// # use uuid_b64::{Alphabet, EncoderConfig, UuidB64};
+/// # fn main() {
+/// let id = UuidB64::from(Uuid::parse_str("b0c1ee86-6f46-4f1b-8d8b-7849e75dbcee").unwrap());
+/// let partner = EncoderConfig::new()
+///     .alphabet(Alphabet::Standard)
+///     .check_digit(true)
+///     .grouping(6, ' ');
+/// let encoded = id.encode_with(&partner);
+/// assert_eq!(encoded, "sMHuhm 9GTxuN i3hJ51 287gQ");
+/// assert_eq!(UuidB64::parse_with(&encoded, &partner).unwrap(), id);
+/// # }
+/// ```
+///
+/// [luhn]: https://en.wikipedia.org/wiki/Luhn_mod_N_algorithm
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct EncoderConfig {
+    alphabet: Alphabet,
+    padding: bool,
+    check_digit: bool,
+    grouping: Option<(usize, char)>,
+}
+
+impl Default for EncoderConfig {
+    fn default() -> EncoderConfig {
+        EncoderConfig::new()
+    }
+}
+
+impl EncoderConfig {
+    /// The canonical format: the build's alphabet (url-safe unless the
+    /// `standard-alphabet` feature is enabled), no padding, no check digit,
+    /// no groups
+    pub fn new() -> EncoderConfig {
+        EncoderConfig {
+            alphabet: BUILD_ALPHABET,
+            padding: false,
+            check_digit: false,
+            grouping: None,
+        }
+    }
+
+    /// Use the given alphabet
+    ///
+    /// # Panics
+    ///
+    /// If a [`grouping`](#method.grouping) separator is already set and is
+    /// part of `alphabet`.
+    pub fn alphabet(mut self, alphabet: Alphabet) -> EncoderConfig {
+        if let Some((_, separator)) = self.grouping {
+            check_separator(alphabet, separator);
+        }
+        self.alphabet = alphabet;
+        self
+    }
+
+    /// Append (and require) `==` padding
+    pub fn padding(mut self, padding: bool) -> EncoderConfig {
+        self.padding = padding;
+        self
+    }
+
+    /// Append (and verify) a check character
+    pub fn check_digit(mut self, check_digit: bool) -> EncoderConfig {
+        self.check_digit = check_digit;
+        self
+    }
+
+    /// Split the output into groups of `size` characters joined by `separator`
+    ///
+    /// # Panics
+    ///
+    /// If `size` is zero, or if `separator` could be confused with part of
+    /// the ID: it must not be in the alphabet or be `=`. Changing the
+    /// alphabet afterwards checks the separator again.
+    pub fn grouping(mut self, size: usize, separator: char) -> EncoderConfig {
+        assert!(size > 0, "group size must be at least 1");
+        check_separator(self.alphabet, separator);
+        self.grouping = Some((size, separator));
+        self
+    }
+}
+
+/// Panic if `separator` would be confused with part of an ID in `alphabet`
+fn check_separator(alphabet: Alphabet, separator: char) {
+    assert!(
+        separator != '=' && alphabet.value_of(separator).is_none(),
+        "separator {:?} is part of the encoding",
+        separator
+    );
+}
+
+/// Displays an ID as classic padded base64, see [`UuidB64::std_b64`][]
+///
+/// [`UuidB64::std_b64`]: struct.UuidB64.html#method.std_b64
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct StdB64(UuidB64);
+
+impl Display for StdB64 {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        let config = Config::new(CharacterSet::Standard, true, false, LineWrap::NoWrap);
+        // can only hit this error if we use an invalid line length
+        let wrapper = Base64Display::with_config((self.0).0.as_bytes(), config).unwrap();
+        write!(f, "{}", wrapper)
+    }
+}
+
+/// Luhn mod 64 check character value for the given symbol values
+fn luhn_check(values: &[usize]) -> usize {
+    let mut sum = 0;
+    for (i, &value) in values.iter().rev().enumerate() {
+        let addend = if i % 2 == 0 { value * 2 } else { value };
+        sum += addend / 64 + addend % 64;
+    }
+    (64 - sum % 64) % 64
+}
+
+impl UuidB64 {
+    /// Display this ID in the standard base64 alphabet, with padding
+    ///
+    /// This is the 24-character form that most base64 libraries produce by
+    /// default, for partners that require `+`, `/` and `==` on output.
+    ///
+    /// ```
+    /// # extern crate uuid;
+    /// # extern crate uuid_b64;
+    /// # use uuid::Uuid;
+    /// # use uuid_b64::UuidB64;
+    /// # fn main() {
+    /// let id = UuidB64::from(Uuid::parse_str("fbffbffb-ffbf-fbff-bffb-ffbffbffbfff").unwrap());
+    /// assert_eq!(id.std_b64().to_string(), "+/+/+/+/+/+/+/+/+/+//w==");
+    /// # }
+    /// ```
+    pub fn std_b64(&self) -> StdB64 {
+        StdB64(*self)
+    }
+
+    /// Encode this ID according to `config`
+    ///
+    /// See [`EncoderConfig`](struct.EncoderConfig.html) for the available
+    /// options.
+    pub fn encode_with(&self, config: &EncoderConfig) -> String {
+        let mut body = base64::encode_config(self.0.as_bytes(), config.alphabet.base64_config());
+        if config.padding {
+            body.push_str("==");
+        }
+        if config.check_digit {
+            let values: Vec<usize> = body
+                .chars()
+                .take(22)
+                .filter_map(|c| config.alphabet.value_of(c))
+                .collect();
+            body.push(char::from(config.alphabet.symbols()[luhn_check(&values)]));
+        }
+        match config.grouping {
+            None => body,
+            Some((size, separator)) => {
+                let mut grouped = String::with_capacity(body.len() + body.len() / size);
+                for (i, c) in body.chars().enumerate() {
+                    if i > 0 && i % size == 0 {
+                        grouped.push(separator);
+                    }
+                    grouped.push(c);
+                }
+                grouped
+            }
+        }
+    }
+
+    /// Parse an ID that was encoded according to `config`
+    ///
+    /// Group separators may appear anywhere, padding and the check
+    /// character are required if they are enabled.
+    pub fn parse_with(s: &str, config: &EncoderConfig) -> Result<UuidB64, ErrorKind> {
+        let mut body: String = match config.grouping {
+            Some((_, separator)) => s.chars().filter(|&c| c != separator).collect(),
+            None => s.into(),
+        };
+        if config.check_digit {
+            let check = body
+                .pop()
+                .and_then(|c| config.alphabet.value_of(c))
+                .ok_or_else(|| ErrorKind::ParseError(s.into()))?;
+            let values = body
+                .chars()
+                .take(22)
+                .map(|c| config.alphabet.value_of(c))
+                .collect::<Option<Vec<_>>>()
+                .ok_or_else(|| ErrorKind::ParseError(s.into()))?;
+            if luhn_check(&values) != check {
+                return Err(ErrorKind::ChecksumMismatch(s.into()));
+            }
+        }
+        if config.padding {
+            if !body.ends_with("==") {
+                return Err(ErrorKind::ParseError(s.into()));
+            }
+            body.truncate(body.len() - 2);
+        }
+        // like `FromStr`, the unused bits at the end must be zero
+        if body.len() != 22 || !body.ends_with(&['A', 'Q', 'g', 'w'][..]) {
+            return Err(ErrorKind::ParseError(s.into()));
+        }
+        base64::decode_config(&body, config.alphabet.base64_config())
+            .ok()
+            .and_then(|bytes| ::uuid::Uuid::from_bytes(&bytes).ok())
+            .map(UuidB64)
+            .ok_or_else(|| ErrorKind::ParseError(s.into()))
+    }
+
+    /// The usual text form followed by a check character, for IDs that
+    /// people copy by hand
+    ///
+    /// This is `encode_with` using `EncoderConfig::new().check_digit(true)`.
+    /// The check character catches any one mistyped character and most
+    /// swapped pairs, so a typo in a support ticket fails to parse instead
+    /// of finding some other record.
+    ///
+    /// ```
+    /// # use uuid_b64::UuidB64;
+    /// let id: UuidB64 = "sMHuhm9GTxuNi3hJ51287g".parse().unwrap();
+    /// assert_eq!(id.to_checked_string(), "sMHuhm9GTxuNi3hJ51287gQ");
+    /// assert_eq!(UuidB64::from_checked_str("sMHuhm9GTxuNi3hJ51287gQ").unwrap(), id);
+    /// assert!(UuidB64::from_checked_str("sMHuhm9GTxuNi3hJ51387gQ").is_err());
+    /// ```
+    pub fn to_checked_string(&self) -> String {
+        self.encode_with(&EncoderConfig::new().check_digit(true))
+    }
+
+    /// Parse a string made by [`to_checked_string`](#method.to_checked_string)
+    ///
+    /// Fails with `ErrorKind::ChecksumMismatch` if the check character
+    /// doesn't match the rest.
+    pub fn from_checked_str(s: &str) -> Result<UuidB64, ErrorKind> {
+        UuidB64::parse_with(s, &EncoderConfig::new().check_digit(true))
+    }
+
+    /// Parse an ID in whichever common format it's in
+    ///
+    /// Accepts base64 in either alphabet, with or without `==` padding,
+    /// 32-character simple hex, 36-character hyphenated hex and
+    /// `urn:uuid:` URNs. For data from other systems, where the format isn't
+    /// up to us; `FromStr` stays strict.
+    ///
+    /// ```
+    /// # use uuid_b64::UuidB64;
+    /// let id: UuidB64 = "sMHuhm9GTxuNi3hJ51287g".parse().unwrap();
+    /// for s in &[
+    ///     "sMHuhm9GTxuNi3hJ51287g==",
+    ///     "b0c1ee866f464f1b8d8b7849e75dbcee",
+    ///     "b0c1ee86-6f46-4f1b-8d8b-7849e75dbcee",
+    ///     "urn:uuid:b0c1ee86-6f46-4f1b-8d8b-7849e75dbcee",
+    /// ] {
+    ///     assert_eq!(UuidB64::parse_any(s).unwrap(), id);
+    /// }
+    /// ```
+    pub fn parse_any(s: &str) -> Result<UuidB64, ErrorKind> {
+        let hex = match s.get(..9) {
+            Some(scheme) if scheme.eq_ignore_ascii_case("urn:uuid:") => &s[9..],
+            _ => s,
+        };
+        if hex.len() == 32 || hex.len() == 36 {
+            return ::uuid::Uuid::parse_str(hex)
+                .map(UuidB64)
+                .map_err(|_| ErrorKind::ParseError(s.into()));
+        }
+        let alphabet = if s.contains(&['+', '/'][..]) {
+            Alphabet::Standard
+        } else {
+            Alphabet::UrlSafe
+        };
+        let config = EncoderConfig::new()
+            .alphabet(alphabet)
+            .padding(s.ends_with("=="));
+        UuidB64::parse_with(s, &config)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use uuid::Uuid;
+
+    use super::{Alphabet, EncoderConfig};
+    use errors::ErrorKind;
+    use UuidB64;
+
+    fn known() -> UuidB64 {
+        UuidB64::from(Uuid::parse_str("fbffbffb-ffbf-fbff-bffb-ffbffbffbfff").unwrap())
+    }
+
+    #[test]
+    fn default_matches_display() {
+        let id = UuidB64::new();
+        let config = EncoderConfig::default();
+        assert_eq!(id.encode_with(&config), id.to_string());
+        assert_eq!(UuidB64::parse_with(&id.to_string(), &config).unwrap(), id);
+    }
+
+    #[test]
+    fn alphabet_and_padding() {
+        let config = EncoderConfig::new()
+            .alphabet(Alphabet::Standard)
+            .padding(true);
+        let encoded = known().encode_with(&config);
+        assert_eq!(encoded, "+/+/+/+/+/+/+/+/+/+//w==");
+        assert_eq!(UuidB64::parse_with(&encoded, &config).unwrap(), known());
+        assert!(UuidB64::parse_with("+/+/+/+/+/+/+/+/+/+//w", &config).is_err());
+    }
+
+    #[test]
+    fn rejects_non_canonical_last_characters() {
+        for config in &[
+            EncoderConfig::new(),
+            EncoderConfig::new()
+                .alphabet(Alphabet::Standard)
+                .padding(true),
+            EncoderConfig::new().grouping(4, ' '),
+        ] {
+            let id: UuidB64 = "sMHuhm9GTxuNi3hJ51287g".parse().unwrap();
+            let encoded = id.encode_with(config);
+            assert_eq!(UuidB64::parse_with(&encoded, config).unwrap(), id);
+            let lenient = encoded.replacen("7g", "7h", 1);
+            assert!(
+                UuidB64::parse_with(&lenient, config).is_err(),
+                "{}",
+                lenient
+            );
+        }
+        let checked = EncoderConfig::new().check_digit(true);
+        let encoded = UuidB64::MAX.encode_with(&checked);
+        assert_eq!(
+            UuidB64::parse_with(&encoded, &checked).unwrap(),
+            UuidB64::MAX
+        );
+    }
+
+    #[test]
+    fn check_digit_catches_typos() {
+        let config = EncoderConfig::new().check_digit(true);
+        for _ in 0..50 {
+            let id = UuidB64::new();
+            let encoded = id.encode_with(&config);
+            assert_eq!(encoded.len(), 23);
+            assert_eq!(UuidB64::parse_with(&encoded, &config).unwrap(), id);
+
+            let mut typo: Vec<char> = encoded.chars().collect();
+            typo[5] = if typo[5] == 'A' { 'B' } else { 'A' };
+            let typo: String = typo.into_iter().collect();
+            match UuidB64::parse_with(&typo, &config) {
+                Err(ErrorKind::ChecksumMismatch(_)) => {}
+                other => panic!("typo {} was not caught: {:?}", typo, other),
+            }
+        }
+    }
+
+    #[test]
+    fn checked_strings() {
+        for _ in 0..50 {
+            let id = UuidB64::new();
+            let checked = id.to_checked_string();
+            assert_eq!(
+                checked,
+                id.encode_with(&EncoderConfig::new().check_digit(true))
+            );
+            assert_eq!(UuidB64::from_checked_str(&checked).unwrap(), id);
+            // the check character is required
+            assert!(UuidB64::from_checked_str(&id.to_string()).is_err());
+        }
+        match UuidB64::from_checked_str("sMHuhm9GTxuNi3hJ51387gQ") {
+            Err(ErrorKind::ChecksumMismatch(_)) => {}
+            other => panic!("typo was not caught: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn grouping_roundtrips() {
+        let config = EncoderConfig::new()
+            .alphabet(Alphabet::UrlSafe)
+            .padding(true)
+            .grouping(4, '.');
+        let encoded = known().encode_with(&config);
+        assert_eq!(encoded, "-_-_.-_-_.-_-_.-_-_.-_-_._w==");
+        assert_eq!(UuidB64::parse_with(&encoded, &config).unwrap(), known());
+    }
+
+    #[test]
+    fn std_b64_matches_padded_standard_config() {
+        let config = EncoderConfig::new()
+            .alphabet(Alphabet::Standard)
+            .padding(true);
+        for _ in 0..10 {
+            let id = UuidB64::new();
+            assert_eq!(id.std_b64().to_string(), id.encode_with(&config));
+        }
+        assert_eq!(known().std_b64().to_string(), "+/+/+/+/+/+/+/+/+/+//w==");
+    }
+
+    #[test]
+    fn parse_any_accepts_common_formats() {
+        for s in &[
+            "-_-_-_-_-_-_-_-_-_-__w",
+            "-_-_-_-_-_-_-_-_-_-__w==",
+            "+/+/+/+/+/+/+/+/+/+//w",
+            "+/+/+/+/+/+/+/+/+/+//w==",
+            "fbffbffbffbffbffbffbffbffbffbfff",
+            "FBFFBFFB-FFBF-FBFF-BFFB-FFBFFBFFBFFF",
+            "urn:uuid:fbffbffb-ffbf-fbff-bffb-ffbffbffbfff",
+            "URN:UUID:fbffbffb-ffbf-fbff-bffb-ffbffbffbfff",
+        ] {
+            assert_eq!(UuidB64::parse_any(s).unwrap(), known(), "{}", s);
+        }
+    }
+
+    #[test]
+    fn parse_any_rejects_garbage() {
+        for s in &[
+            "",
+            "-_-_-_-_-_-_-_-_-_-+/w",
+            "-_-_-_-_-_-_-_-_-_-__w=",
+            "fbffbffb-ffbf-fbff-bffb-ffbffbffbffg",
+            "urn:uuid:",
+            "urn:uuid:-_-_-_-_-_-_-_-_-_-__w",
+        ] {
+            assert!(UuidB64::parse_any(s).is_err(), "{}", s);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn separator_cannot_be_in_alphabet() {
+        EncoderConfig::new()
+            .alphabet(Alphabet::UrlSafe)
+            .grouping(4, '-');
+    }
+
+    #[test]
+    #[should_panic(expected = "is part of the encoding")]
+    fn alphabet_is_checked_against_the_separator() {
+        EncoderConfig::new()
+            .alphabet(Alphabet::Standard)
+            .grouping(4, '-')
+            .alphabet(Alphabet::UrlSafe);
+    }
+}
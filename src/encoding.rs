@@ -0,0 +1,345 @@
+//! Choosing an ID's text format with a type parameter
+//!
+//! `UuidB64` always displays and parses in the canonical form. Where a
+//! codebase needs other forms too, [`EncodedUuid<E>`][EncodedUuid] carries
+//! its encoding in its type, so `Display`, `FromStr` and serde follow it and
+//! IDs in different formats can't be mixed up.
+//!
+//! [EncodedUuid]: struct.EncodedUuid.html
+
+use std::cmp::Ordering;
+use std::fmt::{Debug, Display, Formatter, Result as FmtResult};
+use std::hash::{Hash, Hasher};
+use std::marker::PhantomData;
+use std::str::FromStr;
+
+use uuid::Uuid;
+
+use encoder::{Alphabet, EncoderConfig};
+use errors::ErrorKind;
+use UuidB64;
+
+/// A text format for IDs
+///
+/// Implemented by the marker types in this module, and open for formats of
+/// your own.
+pub trait Encoding {
+    /// A name for the format, used in error messages
+    const NAME: &'static str;
+
+    /// Write `id` in this format
+    fn fmt(id: &UuidB64, f: &mut Formatter) -> FmtResult;
+
+    /// Parse an ID written in this format
+    fn parse(s: &str) -> Result<UuidB64, ErrorKind>;
+}
+
+/// The canonical form: url-safe base64 without padding
+///
+/// Unlike `UuidB64`, this doesn't change with the `standard-alphabet`
+/// feature.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum UrlSafeNoPad {}
+
+/// Standard (`+` and `/`) base64 with `==` padding, 24 characters
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum StandardPadded {}
+
+/// Crockford base32, see `UuidB64::to_base32_crockford`
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Base32Crockford {}
+
+/// Base62, see `UuidB64::to_base62`
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Base62 {}
+
+/// The 32 lower-case hex digits of the simple UUID form
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Hex {}
+
+/// The classic hyphenated UUID form
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Hyphenated {}
+
+fn base64_config(alphabet: Alphabet, padding: bool) -> EncoderConfig {
+    EncoderConfig::new().alphabet(alphabet).padding(padding)
+}
+
+impl Encoding for UrlSafeNoPad {
+    const NAME: &'static str = "url-safe base64";
+
+    fn fmt(id: &UuidB64, f: &mut Formatter) -> FmtResult {
+        f.write_str(&id.encode_with(&base64_config(Alphabet::UrlSafe, false)))
+    }
+
+    fn parse(s: &str) -> Result<UuidB64, ErrorKind> {
+        UuidB64::parse_with(s, &base64_config(Alphabet::UrlSafe, false))
+    }
+}
+
+impl Encoding for StandardPadded {
+    const NAME: &'static str = "padded base64";
+
+    fn fmt(id: &UuidB64, f: &mut Formatter) -> FmtResult {
+        Display::fmt(&id.std_b64(), f)
+    }
+
+    fn parse(s: &str) -> Result<UuidB64, ErrorKind> {
+        UuidB64::parse_with(s, &base64_config(Alphabet::Standard, true))
+    }
+}
+
+impl Encoding for Base32Crockford {
+    const NAME: &'static str = "Crockford base32";
+
+    fn fmt(id: &UuidB64, f: &mut Formatter) -> FmtResult {
+        f.write_str(&id.to_base32_crockford())
+    }
+
+    fn parse(s: &str) -> Result<UuidB64, ErrorKind> {
+        UuidB64::from_base32_crockford(s)
+    }
+}
+
+impl Encoding for Base62 {
+    const NAME: &'static str = "base62";
+
+    fn fmt(id: &UuidB64, f: &mut Formatter) -> FmtResult {
+        f.write_str(&id.to_base62())
+    }
+
+    fn parse(s: &str) -> Result<UuidB64, ErrorKind> {
+        UuidB64::from_base62(s)
+    }
+}
+
+impl Encoding for Hex {
+    const NAME: &'static str = "hex";
+
+    fn fmt(id: &UuidB64, f: &mut Formatter) -> FmtResult {
+        write!(f, "{:x}", id)
+    }
+
+    fn parse(s: &str) -> Result<UuidB64, ErrorKind> {
+        parse_uuid(s, 32)
+    }
+}
+
+impl Encoding for Hyphenated {
+    const NAME: &'static str = "hyphenated";
+
+    fn fmt(id: &UuidB64, f: &mut Formatter) -> FmtResult {
+        write!(f, "{:#}", id)
+    }
+
+    fn parse(s: &str) -> Result<UuidB64, ErrorKind> {
+        parse_uuid(s, 36)
+    }
+}
+
+/// `Uuid::parse_str` takes both hex forms, only accept the one of `len`
+fn parse_uuid(s: &str, len: usize) -> Result<UuidB64, ErrorKind> {
+    if s.len() != len {
+        return Err(ErrorKind::InvalidHex(s.to_owned()));
+    }
+    Uuid::parse_str(s)
+        .map(UuidB64)
+        .map_err(|_| ErrorKind::InvalidHex(s.to_owned()))
+}
+
+/// An ID that displays, parses and serializes in the encoding `E`
+///
+/// ```
+/// # use uuid_b64::encoding::{Base32Crockford, Hyphenated};
+/// # use uuid_b64::{EncodedUuid, UuidB64};
+/// type SupportId = EncodedUuid<Base32Crockford>;
+/// type LegacyId = EncodedUuid<Hyphenated>;
+///
+/// let id: UuidB64 = "sMHuhm9GTxuNi3hJ51287g".parse().unwrap();
+/// assert_eq!(SupportId::from(id).to_string(), "5GR7Q8CVT69WDRV2VR97KNVF7E");
+/// let legacy: LegacyId = "b0c1ee86-6f46-4f1b-8d8b-7849e75dbcee".parse().unwrap();
+/// assert_eq!(legacy.as_uuid_b64(), id);
+/// assert!("sMHuhm9GTxuNi3hJ51287g".parse::<LegacyId>().is_err());
+/// ```
+///
+/// `UuidB64` itself stays a separate type rather than becoming an alias for
+/// `EncodedUuid<UrlSafeNoPad>`, so everything implemented for it keeps
+/// working. Convert with `From` in either direction.
+///
+/// Like `Id<T>`, `E` is only a marker, so `EncodedUuid<E>` is `Copy`, `Send`,
+/// `Hash` and so on whatever `E` is.
+pub struct EncodedUuid<E>(UuidB64, PhantomData<fn() -> E>);
+
+impl<E> EncodedUuid<E> {
+    /// Generate a new v4 ID
+    #[allow(clippy::new_without_default)]
+    pub fn new() -> EncodedUuid<E> {
+        EncodedUuid::from_uuid_b64(UuidB64::new())
+    }
+
+    /// Wrap an ID to be written in the `E` encoding, usable in `const`s
+    pub const fn from_uuid_b64(id: UuidB64) -> EncodedUuid<E> {
+        EncodedUuid(id, PhantomData)
+    }
+
+    /// The ID, in the canonical encoding
+    pub fn as_uuid_b64(&self) -> UuidB64 {
+        self.0
+    }
+
+    /// Copy the raw UUID out
+    pub fn uuid(&self) -> Uuid {
+        self.0.uuid()
+    }
+
+    /// The same ID in another encoding
+    pub fn reencode<F>(self) -> EncodedUuid<F> {
+        EncodedUuid::from_uuid_b64(self.0)
+    }
+}
+
+// These are written out by hand because deriving them would require `E` to
+// implement them too
+
+impl<E> Clone for EncodedUuid<E> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<E> Copy for EncodedUuid<E> {}
+
+impl<E> PartialEq for EncodedUuid<E> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl<E> Eq for EncodedUuid<E> {}
+
+impl<E> PartialOrd for EncodedUuid<E> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<E> Ord for EncodedUuid<E> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.cmp(&other.0)
+    }
+}
+
+impl<E> Hash for EncodedUuid<E> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.hash(state)
+    }
+}
+
+impl<E> From<UuidB64> for EncodedUuid<E> {
+    fn from(id: UuidB64) -> Self {
+        EncodedUuid::from_uuid_b64(id)
+    }
+}
+
+impl<E> From<Uuid> for EncodedUuid<E> {
+    fn from(id: Uuid) -> Self {
+        EncodedUuid::from_uuid_b64(UuidB64(id))
+    }
+}
+
+// UuidB64's blanket `From<T: Into<Uuid>>` turns this into a conversion to
+// UuidB64 as well
+impl<E> From<EncodedUuid<E>> for Uuid {
+    fn from(id: EncodedUuid<E>) -> Self {
+        (id.0).0
+    }
+}
+
+impl<E: Encoding> FromStr for EncodedUuid<E> {
+    type Err = ErrorKind;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        E::parse(s).map(EncodedUuid::from_uuid_b64)
+    }
+}
+
+impl<E: Encoding> Display for EncodedUuid<E> {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        E::fmt(&self.0, f)
+    }
+}
+
+impl<E: Encoding> Debug for EncodedUuid<E> {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        write!(f, "EncodedUuid({})", self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+
+    fn roundtrip<E: Encoding>(id: UuidB64, expected: &str) {
+        let encoded = EncodedUuid::<E>::from(id);
+        assert_eq!(encoded.to_string(), expected, "{}", E::NAME);
+        assert_eq!(expected.parse::<EncodedUuid<E>>().unwrap(), encoded);
+    }
+
+    #[test]
+    fn every_encoding() {
+        let id: UuidB64 = "sMHuhm9GTxuNi3hJ51287g".parse().unwrap();
+        roundtrip::<UrlSafeNoPad>(id, "sMHuhm9GTxuNi3hJ51287g");
+        roundtrip::<StandardPadded>(id, "sMHuhm9GTxuNi3hJ51287g==");
+        roundtrip::<Base32Crockford>(id, "5GR7Q8CVT69WDRV2VR97KNVF7E");
+        roundtrip::<Base62>(id, "5NXH9G03Qou9vbZ5Nk2VBO");
+        roundtrip::<Hex>(id, "b0c1ee866f464f1b8d8b7849e75dbcee");
+        roundtrip::<Hyphenated>(id, "b0c1ee86-6f46-4f1b-8d8b-7849e75dbcee");
+
+        let max = UuidB64::MAX;
+        roundtrip::<UrlSafeNoPad>(max, "_____________________w");
+        roundtrip::<StandardPadded>(max, "/////////////////////w==");
+    }
+
+    #[test]
+    fn parsing_is_strict_about_the_form() {
+        assert!("sMHuhm9GTxuNi3hJ51287g"
+            .parse::<EncodedUuid<StandardPadded>>()
+            .is_err());
+        assert!("sMHuhm9GTxuNi3hJ51287g=="
+            .parse::<EncodedUuid<UrlSafeNoPad>>()
+            .is_err());
+        assert!("/////////////////////w"
+            .parse::<EncodedUuid<UrlSafeNoPad>>()
+            .is_err());
+        assert!("sMHuhm9GTxuNi3hJ51287h"
+            .parse::<EncodedUuid<UrlSafeNoPad>>()
+            .is_err());
+        assert!("sMHuhm9GTxuNi3hJ51287h=="
+            .parse::<EncodedUuid<StandardPadded>>()
+            .is_err());
+        assert!("b0c1ee86-6f46-4f1b-8d8b-7849e75dbcee"
+            .parse::<EncodedUuid<Hex>>()
+            .is_err());
+        let err = "b0c1ee866f464f1b8d8b7849e75dbcee"
+            .parse::<EncodedUuid<Hyphenated>>()
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid hex representation for UUID: 'b0c1ee866f464f1b8d8b7849e75dbcee'"
+        );
+    }
+
+    #[test]
+    fn converts_between_encodings() {
+        let id = EncodedUuid::<Hex>::new();
+        let other: EncodedUuid<Base62> = id.reencode();
+        assert_eq!(other.as_uuid_b64(), id.as_uuid_b64());
+        assert_eq!(UuidB64::from(id), id.as_uuid_b64());
+        assert_eq!(Uuid::from(other), id.uuid());
+        assert_eq!(format!("{:?}", id), format!("EncodedUuid({})", id));
+        let set: HashSet<_> = vec![id, id].into_iter().collect();
+        assert_eq!(set.len(), 1);
+    }
+}
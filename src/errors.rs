@@ -1,8 +1,233 @@
-error_chain! {
-    errors {
-        ParseError(t: String) {
-            description("Unable to parse UUID")
-            display("Invalid Base64 representation for UUID: '{}'", t)
+use std::error::Error;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+use std::io;
+
+/// Why a string isn't a `UuidB64`
+///
+/// This is the `FromStr::Err` for `UuidB64`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseUuidB64Error {
+    /// The string isn't 22 bytes long
+    InvalidLength { found: usize },
+    /// A character outside the Base64 alphabet, or a last character with
+    /// bits set past the end of the UUID
+    ///
+    /// `index` is a byte offset into the string.
+    InvalidCharacter { char: char, index: usize },
+    /// The string decoded to something other than 16 bytes
+    InvalidUuidBytes,
+}
+
+impl Display for ParseUuidB64Error {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        match *self {
+            ParseUuidB64Error::InvalidLength { found } => write!(
+                f,
+                "Invalid Base64 representation for UUID: expected 22 characters, found {}",
+                found
+            ),
+            ParseUuidB64Error::InvalidCharacter { char, index } => write!(
+                f,
+                "Invalid Base64 representation for UUID: invalid character {:?} at index {}",
+                char, index
+            ),
+            ParseUuidB64Error::InvalidUuidBytes => {
+                write!(f, "Invalid Base64 representation for UUID: not 16 bytes")
+            }
         }
     }
 }
+
+impl Error for ParseUuidB64Error {}
+
+/// One string that [`UuidB64::parse_many`] couldn't parse
+///
+/// [`UuidB64::parse_many`]: struct.UuidB64.html#method.parse_many
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InvalidEntry {
+    /// Position of the string in the input
+    pub index: usize,
+    /// The string itself
+    pub input: String,
+    /// Why it isn't an ID
+    pub error: ParseUuidB64Error,
+}
+
+/// Every string that [`UuidB64::parse_many`] couldn't parse, in input order
+///
+/// There is always at least one.
+///
+/// [`UuidB64::parse_many`]: struct.UuidB64.html#method.parse_many
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BulkParseError {
+    pub invalid: Vec<InvalidEntry>,
+}
+
+impl Display for BulkParseError {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        write!(f, "{} invalid IDs:", self.invalid.len())?;
+        for (i, entry) in self.invalid.iter().enumerate() {
+            let sep = if i == 0 { " " } else { "; " };
+            write!(
+                f,
+                "{}'{}' at index {} ({})",
+                sep, entry.input, entry.index, entry.error
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl Error for BulkParseError {}
+
+/// Why an ID couldn't be read, for everything except `FromStr`
+///
+/// Parsing other text forms, binary forms and streams of IDs returns this.
+/// Variants holding a `String` hold the offending input. More variants may
+/// be added, so matches need a wildcard arm.
+///
+/// ```
+/// # use uuid_b64::{ErrorKind, UuidB64};
+/// match UuidB64::parse_list("sMHuhm9GTxuNi3hJ51287g,nope") {
+///     Err(ErrorKind::ListParseError(index, ref item)) => {
+///         assert_eq!((index, item.as_str()), (1, "nope"));
+///     }
+///     other => panic!("unexpected result: {:?}", other),
+/// }
+/// ```
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum ErrorKind {
+    /// Reading or writing IDs failed
+    Io(io::Error),
+    /// A Base64 ID was malformed
+    InvalidId(ParseUuidB64Error),
+    /// The text isn't an ID in the expected form
+    ParseError(String),
+    /// A chunk of a packed string isn't an ID, with the chunk's index
+    PackedParseError(usize, String),
+    /// A line isn't an ID, with its 1-based line number
+    LineParseError(usize, String),
+    /// An item of a list isn't an ID, with its index
+    ListParseError(usize, String),
+    /// The text isn't a Relay global ID
+    InvalidGlobalId(String),
+    /// A Relay global ID was for the wrong type: expected, then found
+    GlobalIdTypeMismatch(String, String),
+    /// A prefixed ID had the wrong prefix: expected, then found
+    PrefixMismatch(String, String),
+    /// A signed ID's signature doesn't match the ID
+    InvalidSignature(String),
+    /// The ID has the wrong version: expected, then found
+    WrongVersion(usize, usize),
+    /// Packed binary IDs are malformed, and why
+    InvalidPackedIds(String),
+    /// A binary ID is malformed, and why
+    InvalidBinary(String),
+    /// A check character doesn't match the rest of the ID
+    ChecksumMismatch(String),
+    /// The text isn't Crockford base32 for an ID
+    InvalidBase32(String),
+    /// The text isn't Base58 for an ID
+    InvalidBase58(String),
+    /// The text isn't Base62 for an ID
+    InvalidBase62(String),
+    /// The text isn't a DNS label for an ID
+    InvalidDnsLabel(String),
+    /// The text isn't a hex UUID
+    InvalidHex(String),
+    /// The text isn't a ULID
+    InvalidUlid(String),
+}
+
+impl Display for ErrorKind {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        match *self {
+            ErrorKind::Io(ref e) => e.fmt(f),
+            ErrorKind::InvalidId(ref e) => e.fmt(f),
+            ErrorKind::ParseError(ref t) => {
+                write!(f, "Invalid Base64 representation for UUID: '{}'", t)
+            }
+            ErrorKind::PackedParseError(i, ref t) => write!(
+                f,
+                "Invalid Base64 representation for UUID at position {}: '{}'",
+                i, t
+            ),
+            ErrorKind::LineParseError(line, ref t) => write!(
+                f,
+                "Invalid Base64 representation for UUID on line {}: '{}'",
+                line, t
+            ),
+            ErrorKind::ListParseError(i, ref t) => write!(
+                f,
+                "Invalid Base64 representation for UUID at list index {}: '{}'",
+                i, t
+            ),
+            ErrorKind::InvalidGlobalId(ref t) => write!(f, "Invalid Relay global ID: '{}'", t),
+            ErrorKind::GlobalIdTypeMismatch(ref expected, ref found) => write!(
+                f,
+                "Expected a Relay global ID for '{}', found one for '{}'",
+                expected, found
+            ),
+            ErrorKind::PrefixMismatch(ref expected, ref found) => write!(
+                f,
+                "Expected an ID prefixed with '{}', found one prefixed with '{}'",
+                expected, found
+            ),
+            ErrorKind::InvalidSignature(ref t) => {
+                write!(f, "Signature does not match for UUID: '{}'", t)
+            }
+            ErrorKind::WrongVersion(expected, found) => {
+                write!(f, "Expected a v{} UUID, found a v{} UUID", expected, found)
+            }
+            ErrorKind::InvalidPackedIds(ref reason) => write!(f, "Invalid packed IDs: {}", reason),
+            ErrorKind::InvalidBinary(ref reason) => write!(f, "Invalid UUID binary: {}", reason),
+            ErrorKind::ChecksumMismatch(ref t) => {
+                write!(f, "Check digit does not match for UUID: '{}'", t)
+            }
+            ErrorKind::InvalidBase32(ref t) => {
+                write!(
+                    f,
+                    "Invalid Crockford base32 representation for UUID: '{}'",
+                    t
+                )
+            }
+            ErrorKind::InvalidBase58(ref t) => {
+                write!(f, "Invalid Base58 representation for UUID: '{}'", t)
+            }
+            ErrorKind::InvalidBase62(ref t) => {
+                write!(f, "Invalid Base62 representation for UUID: '{}'", t)
+            }
+            ErrorKind::InvalidDnsLabel(ref t) => {
+                write!(f, "Invalid DNS label for UUID: '{}'", t)
+            }
+            ErrorKind::InvalidHex(ref t) => {
+                write!(f, "Invalid hex representation for UUID: '{}'", t)
+            }
+            ErrorKind::InvalidUlid(ref t) => write!(f, "Invalid ULID: '{}'", t),
+        }
+    }
+}
+
+impl Error for ErrorKind {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match *self {
+            // both display their inner error, so it isn't a separate cause
+            ErrorKind::Io(ref e) => e.source(),
+            ErrorKind::InvalidId(ref e) => e.source(),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for ErrorKind {
+    fn from(e: io::Error) -> ErrorKind {
+        ErrorKind::Io(e)
+    }
+}
+
+impl From<ParseUuidB64Error> for ErrorKind {
+    fn from(e: ParseUuidB64Error) -> ErrorKind {
+        ErrorKind::InvalidId(e)
+    }
+}
@@ -0,0 +1,210 @@
+//! Sorting and deduplicating ID sets that don't fit in memory
+
+use std::cell::RefCell;
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::fs::{self, File};
+use std::io::{BufRead, BufReader, BufWriter, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+
+use errors::ErrorKind;
+use packed::{write_packed, PackedIdReader, PACKED_MAGIC};
+use UuidB64;
+
+/// Merge several sorted streams of IDs into one sorted, deduplicated stream
+///
+/// Each input must already be sorted, otherwise the output won't be. Errors
+/// from any input are passed through.
+///
+/// ```
+/// # use uuid_b64::{MergeDedup, UuidB64};
+/// let mut ids: Vec<UuidB64> = (0..6).map(|_| UuidB64::new()).collect();
+/// ids.sort();
+/// let a = vec![ids[0], ids[2], ids[4]];
+/// let b = vec![ids[1], ids[2], ids[5]];
+/// let merged: Vec<UuidB64> = MergeDedup::new(vec![a.into_iter(), b.into_iter()]).collect();
+/// assert_eq!(merged, vec![ids[0], ids[1], ids[2], ids[4], ids[5]]);
+/// ```
+pub struct MergeDedup<I> {
+    inputs: Vec<I>,
+    heap: BinaryHeap<Reverse<(UuidB64, usize)>>,
+    last: Option<UuidB64>,
+}
+
+impl<I: Iterator<Item = UuidB64>> MergeDedup<I> {
+    /// Merge `inputs`, which must each be sorted
+    pub fn new(mut inputs: Vec<I>) -> MergeDedup<I> {
+        let mut heap = BinaryHeap::with_capacity(inputs.len());
+        for (i, input) in inputs.iter_mut().enumerate() {
+            if let Some(id) = input.next() {
+                heap.push(Reverse((id, i)));
+            }
+        }
+        MergeDedup {
+            inputs,
+            heap,
+            last: None,
+        }
+    }
+}
+
+impl<I: Iterator<Item = UuidB64>> Iterator for MergeDedup<I> {
+    type Item = UuidB64;
+
+    fn next(&mut self) -> Option<UuidB64> {
+        while let Some(Reverse((id, i))) = self.heap.pop() {
+            if let Some(next) = self.inputs[i].next() {
+                self.heap.push(Reverse((next, i)));
+            }
+            if self.last != Some(id) {
+                self.last = Some(id);
+                return Some(id);
+            }
+        }
+        None
+    }
+}
+
+/// Sort and deduplicate a packed file of IDs of any size
+///
+/// `input` is read in chunks of at most `max_ids_in_memory` IDs (16 bytes
+/// each), which are sorted, deduplicated and written to temporary run files
+/// next to `output`. The runs are then merged into `output`, which is
+/// written in the packed format with a header. Run files are removed
+/// afterwards, even on error.
+///
+/// `input` may or may not have a header. Returns the number of unique IDs.
+///
+/// # Panics
+///
+/// If `max_ids_in_memory` is zero.
+pub fn sort_dedup_file<P, Q>(input: P, output: Q, max_ids_in_memory: usize) -> Result<u64, ErrorKind>
+where
+    P: AsRef<Path>,
+    Q: AsRef<Path>,
+{
+    assert!(max_ids_in_memory > 0, "must be able to hold at least one ID");
+    let output = output.as_ref();
+    let mut runs = Vec::new();
+    let result = write_runs(input.as_ref(), output, max_ids_in_memory, &mut runs)
+        .and_then(|()| merge_runs(&runs, output));
+    for run in &runs {
+        let _ = fs::remove_file(run);
+    }
+    result
+}
+
+/// Split `input` into sorted, deduplicated run files
+fn write_runs(input: &Path, output: &Path, chunk: usize, runs: &mut Vec<PathBuf>) -> Result<(), ErrorKind> {
+    let mut reader = BufReader::new(File::open(input).map_err(ErrorKind::Io)?);
+    if reader.fill_buf().map_err(ErrorKind::Io)?.starts_with(&PACKED_MAGIC) {
+        reader.consume(16);
+    }
+    let mut ids = PackedIdReader::new(reader).peekable();
+    let mut buf = Vec::with_capacity(chunk);
+    while ids.peek().is_some() {
+        buf.clear();
+        for id in ids.by_ref().take(chunk) {
+            buf.push(id?);
+        }
+        buf.sort_unstable();
+        buf.dedup();
+
+        let run = run_path(output, runs.len());
+        runs.push(run.clone());
+        let file = BufWriter::new(File::create(&run).map_err(ErrorKind::Io)?);
+        write_packed(file, &buf, false).map_err(ErrorKind::Io)?;
+    }
+    Ok(())
+}
+
+fn run_path(output: &Path, i: usize) -> PathBuf {
+    let mut name = output.file_name().map(|n| n.to_os_string()).unwrap_or_default();
+    name.push(format!(".run{}", i));
+    output.with_file_name(name)
+}
+
+/// Merge the run files into `output`, returning the number of unique IDs
+fn merge_runs(runs: &[PathBuf], output: &Path) -> Result<u64, ErrorKind> {
+    // the merge needs plain iterators of IDs, so the first error from any
+    // run is stashed here and ends that run
+    let error = RefCell::new(None);
+    let mut readers = Vec::with_capacity(runs.len());
+    for run in runs {
+        let file = BufReader::new(File::open(run).map_err(ErrorKind::Io)?);
+        let error = &error;
+        readers.push(PackedIdReader::new(file).map_while(move |id| {
+            id.map_err(|e| *error.borrow_mut() = Some(e)).ok()
+        }));
+    }
+
+    let mut out = BufWriter::new(File::create(output).map_err(ErrorKind::Io)?);
+    write_packed(&mut out, &[], true).map_err(ErrorKind::Io)?;
+    let mut count = 0u64;
+    for id in MergeDedup::new(readers) {
+        out.write_all(id.uuid().as_bytes()).map_err(ErrorKind::Io)?;
+        count += 1;
+    }
+    if let Some(e) = error.into_inner() {
+        return Err(e);
+    }
+
+    // fill in the count now that it is known
+    let mut file = out.into_inner().map_err(|e| ErrorKind::Io(e.into_error()))?;
+    file.seek(SeekFrom::Start(8)).map_err(ErrorKind::Io)?;
+    file.write_all(&count.to_le_bytes()).map_err(ErrorKind::Io)?;
+    Ok(count)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+    use std::fs::{self, File};
+    use std::io::Read;
+
+    use super::sort_dedup_file;
+    use packed::{read_packed, write_packed};
+    use UuidB64;
+
+    #[test]
+    fn sorts_and_dedups_across_runs() {
+        let unique: Vec<UuidB64> = (0..100).map(|_| UuidB64::new()).collect();
+        let mut input = unique.clone();
+        input.extend_from_slice(&unique[..50]);
+        input.extend_from_slice(&unique[25..75]);
+
+        let dir = env::temp_dir();
+        let in_path = dir.join(format!("uuid-b64-{}.in", UuidB64::new().uuid().simple()));
+        let out_path = dir.join(format!("uuid-b64-{}.out", UuidB64::new().uuid().simple()));
+        write_packed(File::create(&in_path).unwrap(), &input, true).unwrap();
+
+        let count = sort_dedup_file(&in_path, &out_path, 16).unwrap();
+        assert_eq!(count, 100);
+
+        let mut buf = Vec::new();
+        File::open(&out_path).unwrap().read_to_end(&mut buf).unwrap();
+        let mut expected = unique;
+        expected.sort();
+        assert_eq!(read_packed(&buf).unwrap(), &expected[..]);
+
+        // run files are cleaned up
+        for i in 0..10 {
+            let run = format!("{}.run{}", out_path.display(), i);
+            assert!(fs::metadata(run).is_err());
+        }
+        fs::remove_file(&in_path).unwrap();
+        fs::remove_file(&out_path).unwrap();
+    }
+
+    #[test]
+    fn handles_empty_input() {
+        let dir = env::temp_dir();
+        let in_path = dir.join(format!("uuid-b64-{}.in", UuidB64::new().uuid().simple()));
+        let out_path = dir.join(format!("uuid-b64-{}.out", UuidB64::new().uuid().simple()));
+        File::create(&in_path).unwrap();
+
+        assert_eq!(sort_dedup_file(&in_path, &out_path, 16).unwrap(), 0);
+        fs::remove_file(&in_path).unwrap();
+        fs::remove_file(&out_path).unwrap();
+    }
+}
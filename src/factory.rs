@@ -0,0 +1,341 @@
+//! A configurable source of new IDs, to pass around instead of calling
+//! `UuidB64::new`
+
+#[cfg(feature = "rand")]
+extern crate rand_core;
+
+#[cfg(feature = "rand")]
+use std::fmt;
+use std::sync::Arc;
+#[cfg(feature = "rand")]
+use std::sync::{Mutex, MutexGuard};
+
+#[cfg(feature = "rand")]
+use self::rand_core::RngCore;
+
+#[cfg(feature = "test-util")]
+use test_util::DeterministicGenerator;
+use v1::ClockSequence;
+use v7::V7Generator;
+use UuidB64;
+
+/// Generates IDs of one version, from one source of randomness
+///
+/// For services that take their ID generator as a dependency rather than
+/// calling `UuidB64::new` directly, so that tests can hand them a different
+/// one. It's an endless iterator of IDs.
+///
+/// ```
+/// # use uuid_b64::UuidB64Factory;
+/// let mut ids = UuidB64Factory::v7();
+/// let first = ids.generate();
+/// let second = ids.next().unwrap();
+/// assert!(first < second);
+/// assert_eq!(ids.version(), 7);
+/// ```
+///
+/// Clones carry on from the same state independently, except that v1 and
+/// v6 factories share their [`ClockSequence`](struct.ClockSequence.html) so
+/// clones can't repeat each other's IDs, even when
+/// [seeded](#method.with_seed). Clones of a seeded v4 factory generate the
+/// same IDs, and clones of a seeded v7 factory use the same random bits.
+/// Clones of a factory [with its own generator](#method.with_rng) share it.
+#[derive(Debug, Clone)]
+pub struct UuidB64Factory {
+    kind: Kind,
+    source: Source,
+}
+
+#[derive(Debug, Clone)]
+enum Kind {
+    V4,
+    V7(V7Generator),
+    Gregorian {
+        version: u8,
+        clock: Arc<ClockSequence>,
+        node_id: [u8; 6],
+    },
+}
+
+/// Where the random bits come from
+#[derive(Debug, Clone)]
+enum Source {
+    Os,
+    #[cfg(feature = "test-util")]
+    Seeded(DeterministicGenerator),
+    #[cfg(feature = "rand")]
+    Rng(SharedRng),
+}
+
+/// A caller's generator, shared between clones of the factory
+#[cfg(feature = "rand")]
+#[derive(Clone)]
+struct SharedRng(Arc<Mutex<Box<dyn RngCore + Send>>>);
+
+#[cfg(feature = "rand")]
+impl SharedRng {
+    fn lock(&self) -> MutexGuard<'_, Box<dyn RngCore + Send>> {
+        self.0.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+#[cfg(feature = "rand")]
+impl fmt::Debug for SharedRng {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("SharedRng")
+    }
+}
+
+impl UuidB64Factory {
+    /// Random (v4) IDs, the same as `UuidB64::new`
+    pub fn v4() -> UuidB64Factory {
+        UuidB64Factory::with_kind(Kind::V4)
+    }
+
+    /// Time-ordered (v7) IDs, monotonic for this factory
+    pub fn v7() -> UuidB64Factory {
+        UuidB64Factory::from_v7_generator(V7Generator::new())
+    }
+
+    /// v7 IDs from `generator`, for choosing its precision and counter width
+    pub fn from_v7_generator(generator: V7Generator) -> UuidB64Factory {
+        UuidB64Factory::with_kind(Kind::V7(generator))
+    }
+
+    /// v1 IDs for the node `node_id`, see `UuidB64::new_v1`
+    pub fn v1(node_id: [u8; 6]) -> UuidB64Factory {
+        UuidB64Factory::gregorian(1, node_id)
+    }
+
+    /// v6 IDs for the node `node_id`, see `UuidB64::new_v6`
+    pub fn v6(node_id: [u8; 6]) -> UuidB64Factory {
+        UuidB64Factory::gregorian(6, node_id)
+    }
+
+    fn gregorian(version: u8, node_id: [u8; 6]) -> UuidB64Factory {
+        UuidB64Factory::with_kind(Kind::Gregorian {
+            version,
+            clock: Arc::new(ClockSequence::new()),
+            node_id,
+        })
+    }
+
+    fn with_kind(kind: Kind) -> UuidB64Factory {
+        UuidB64Factory {
+            kind,
+            source: Source::Os,
+        }
+    }
+
+    /// Take random bits from a `DeterministicGenerator` seeded with `seed`
+    /// instead of the OS
+    ///
+    /// v4 factories then generate the same IDs for the same seed. v7, v1 and
+    /// v6 IDs still include the current time, so only their random bits (or,
+    /// for v1 and v6, the starting clock sequence) repeat.
+    ///
+    /// ```
+    /// # use uuid_b64::UuidB64Factory;
+    /// let first: Vec<_> = UuidB64Factory::v4().with_seed(42).take(3).collect();
+    /// let again: Vec<_> = UuidB64Factory::v4().with_seed(42).take(3).collect();
+    /// assert_eq!(first, again);
+    /// ```
+    #[cfg(feature = "test-util")]
+    pub fn with_seed(mut self, seed: u64) -> UuidB64Factory {
+        let mut rng = DeterministicGenerator::from_seed(seed);
+        if let Kind::Gregorian { ref mut clock, .. } = self.kind {
+            let bytes = rng.random_bytes();
+            *clock = Arc::new(ClockSequence::starting_at(u16::from_be_bytes([
+                bytes[0], bytes[1],
+            ])));
+        }
+        self.source = Source::Seeded(rng);
+        self
+    }
+
+    /// Take random bits from `rng` instead of the OS
+    ///
+    /// v4 IDs are made by [`UuidB64::new_with_rng`][], and v7 IDs take their
+    /// random bits (and each new tick's starting counter) from `rng`. v1 and
+    /// v6 IDs don't use it, their clock sequence is started as usual.
+    ///
+    /// ```rust,ignore
+    /// let ids = UuidB64Factory::v7().with_rng(ChaCha20Rng::seed_from_u64(7));
+    /// ```
+    ///
+    /// [`UuidB64::new_with_rng`]: struct.UuidB64.html#method.new_with_rng
+    #[cfg(feature = "rand")]
+    pub fn with_rng<R: RngCore + Send + 'static>(mut self, rng: R) -> UuidB64Factory {
+        self.source = Source::Rng(SharedRng(Arc::new(Mutex::new(Box::new(rng)))));
+        self
+    }
+
+    /// The version of the IDs this generates
+    pub fn version(&self) -> usize {
+        match self.kind {
+            Kind::V4 => 4,
+            Kind::V7(_) => 7,
+            Kind::Gregorian { version, .. } => usize::from(version),
+        }
+    }
+
+    /// The next ID
+    pub fn generate(&mut self) -> UuidB64 {
+        match (&mut self.kind, &mut self.source) {
+            (&mut Kind::V4, &mut Source::Os) => UuidB64::new(),
+            (&mut Kind::V7(ref mut generator), &mut Source::Os) => generator.generate(),
+            #[cfg(feature = "test-util")]
+            (&mut Kind::V4, &mut Source::Seeded(ref mut rng)) => rng.generate(),
+            #[cfg(feature = "test-util")]
+            (&mut Kind::V7(ref mut generator), &mut Source::Seeded(ref mut rng)) => {
+                generator.generate_with(u128::from_be_bytes(rng.random_bytes()))
+            }
+            #[cfg(feature = "rand")]
+            (&mut Kind::V4, &mut Source::Rng(ref rng)) => UuidB64::new_with_rng(&mut **rng.lock()),
+            #[cfg(feature = "rand")]
+            (&mut Kind::V7(ref mut generator), &mut Source::Rng(ref rng)) => {
+                let mut bytes = [0; 16];
+                rng.lock().fill_bytes(&mut bytes);
+                generator.generate_with(u128::from_be_bytes(bytes))
+            }
+            (
+                &mut Kind::Gregorian {
+                    version,
+                    ref clock,
+                    node_id,
+                },
+                _,
+            ) => {
+                if version == 1 {
+                    UuidB64::new_v1(clock, node_id)
+                } else {
+                    UuidB64::new_v6(clock, node_id)
+                }
+            }
+        }
+    }
+}
+
+impl Default for UuidB64Factory {
+    /// A v4 factory, the same as `UuidB64::new`
+    fn default() -> UuidB64Factory {
+        UuidB64Factory::v4()
+    }
+}
+
+impl Iterator for UuidB64Factory {
+    type Item = UuidB64;
+
+    fn next(&mut self) -> Option<UuidB64> {
+        Some(self.generate())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::UuidB64Factory;
+    use UuidB64;
+
+    const NODE: [u8; 6] = [0x9f, 0x6b, 0xde, 0xce, 0xd8, 0x46];
+
+    #[test]
+    fn generates_each_version() {
+        for (mut factory, version) in [
+            (UuidB64Factory::default(), 4),
+            (UuidB64Factory::v7(), 7),
+            (UuidB64Factory::v1(NODE), 1),
+            (UuidB64Factory::v6(NODE), 6),
+        ] {
+            assert_eq!(factory.version(), version);
+            let ids: Vec<UuidB64> = factory.by_ref().take(10).collect();
+            for id in &ids {
+                assert_eq!(id.uuid().get_version_num(), version);
+            }
+            assert_ne!(ids[0], ids[1]);
+            assert_ne!(factory.generate(), ids[9]);
+        }
+    }
+
+    #[test]
+    fn v7_is_monotonic() {
+        let ids: Vec<UuidB64> = UuidB64Factory::v7().take(1000).collect();
+        assert!(ids.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+
+    #[test]
+    fn gregorian_clones_share_the_clock() {
+        let mut factory = UuidB64Factory::v6(NODE);
+        let mut clone = factory.clone();
+        let seq = |id: UuidB64| u16::from_be_bytes([id.as_bytes()[8], id.as_bytes()[9]]) & 0x3fff;
+        let first = seq(factory.generate());
+        assert_eq!(seq(clone.generate()), (first + 1) & 0x3fff);
+        assert_eq!(&factory.generate().as_bytes()[10..], &NODE);
+    }
+
+    #[cfg(feature = "test-util")]
+    #[test]
+    fn seeded() {
+        use DeterministicGenerator;
+
+        let expected: Vec<UuidB64> = DeterministicGenerator::from_seed(3).take(3).collect();
+        let ids: Vec<UuidB64> = UuidB64Factory::v4().with_seed(3).take(3).collect();
+        assert_eq!(ids, expected);
+
+        let mut v7 = UuidB64Factory::v7().with_seed(3);
+        assert_eq!(v7.version(), 7);
+        assert!(v7.generate() < v7.generate());
+
+        let seq = |id: UuidB64| u16::from_be_bytes([id.as_bytes()[8], id.as_bytes()[9]]) & 0x3fff;
+        let a = UuidB64Factory::v1(NODE).with_seed(9).generate();
+        let b = UuidB64Factory::v1(NODE).with_seed(9).generate();
+        assert_eq!(seq(a), seq(b));
+
+        let mut v4 = UuidB64Factory::v4().with_seed(5);
+        assert_eq!(v4.clone().generate(), v4.generate());
+        let mut v1 = UuidB64Factory::v1(NODE).with_seed(5);
+        assert_ne!(seq(v1.clone().generate()), seq(v1.generate()));
+    }
+
+    #[cfg(feature = "rand")]
+    #[test]
+    fn uses_the_callers_rng() {
+        use super::rand_core::RngCore;
+
+        /// Counts up from the byte it starts at
+        struct Counter(u8);
+
+        impl RngCore for Counter {
+            fn next_u32(&mut self) -> u32 {
+                let mut bytes = [0; 4];
+                self.fill_bytes(&mut bytes);
+                u32::from_le_bytes(bytes)
+            }
+
+            fn next_u64(&mut self) -> u64 {
+                let mut bytes = [0; 8];
+                self.fill_bytes(&mut bytes);
+                u64::from_le_bytes(bytes)
+            }
+
+            fn fill_bytes(&mut self, dest: &mut [u8]) {
+                for byte in dest {
+                    *byte = self.0;
+                    self.0 = self.0.wrapping_add(1);
+                }
+            }
+        }
+
+        let mut v4 = UuidB64Factory::v4().with_rng(Counter(0));
+        assert_eq!(v4.generate(), UuidB64::new_with_rng(&mut Counter(0)));
+        // clones share the generator
+        assert_eq!(
+            v4.clone().generate(),
+            UuidB64::new_with_rng(&mut Counter(16))
+        );
+
+        // the bottom 62 random bits are all of `rand_b` but its variant
+        let id = UuidB64Factory::v7().with_rng(Counter(0)).generate();
+        assert_eq!(id.uuid().get_version_num(), 7);
+        assert_eq!(&id.as_bytes()[10..], &[10, 11, 12, 13, 14, 15]);
+    }
+}
@@ -0,0 +1,186 @@
+//! A C ABI for generating, parsing and formatting IDs
+//!
+//! The functions are exported unmangled, so a `cdylib` or `staticlib` crate
+//! that depends on `uuid-b64` with the `ffi` feature links into C and C++
+//! programs as-is, with `include/uuid_b64.h` as the header:
+//!
+//! ```c
+//! uuidb64_t id = uuidb64_new();
+//! char text[UUIDB64_FORMAT_LEN];
+//! uuidb64_format(&id, text);
+//!
+//! uuidb64_t parsed;
+//! if (uuidb64_parse(text, strlen(text), &parsed) != UUIDB64_OK) { ... }
+//! ```
+//!
+//! IDs cross the boundary as their 16 bytes in network order, the same as
+//! `uuid_t`, so the strings are exactly the ones Rust produces.
+
+use std::os::raw::{c_char, c_int};
+use std::{ptr, slice};
+
+use errors::ParseUuidB64Error;
+use UuidB64;
+
+/// The buffer size `uuidb64_format` needs: 22 characters and a NUL
+pub const UUIDB64_FORMAT_LEN: usize = 23;
+
+/// Success
+pub const UUIDB64_OK: c_int = 0;
+/// A required pointer was null
+pub const UUIDB64_NULL_POINTER: c_int = 1;
+/// The input wasn't 22 bytes long
+pub const UUIDB64_INVALID_LENGTH: c_int = 2;
+/// The input had a character outside the Base64 alphabet
+pub const UUIDB64_INVALID_CHARACTER: c_int = 3;
+/// The input decoded to something other than 16 bytes
+pub const UUIDB64_INVALID_UUID_BYTES: c_int = 4;
+
+/// An ID as its 16 raw bytes, `uuidb64_t` in C
+#[repr(C)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct UuidB64Bytes {
+    pub bytes: [u8; 16],
+}
+
+impl From<UuidB64> for UuidB64Bytes {
+    fn from(id: UuidB64) -> UuidB64Bytes {
+        UuidB64Bytes {
+            bytes: *id.uuid().as_bytes(),
+        }
+    }
+}
+
+impl From<UuidB64Bytes> for UuidB64 {
+    fn from(id: UuidB64Bytes) -> UuidB64 {
+        UuidB64::from_bytes(id.bytes)
+    }
+}
+
+/// Generate a new v4 ID
+#[no_mangle]
+pub extern "C" fn uuidb64_new() -> UuidB64Bytes {
+    UuidB64::new().into()
+}
+
+/// Parse `len` bytes of Base64 at `text` into `*out`
+///
+/// Returns `UUIDB64_OK`, or one of the other status codes without touching
+/// `*out`. `text` doesn't need to be NUL-terminated.
+///
+/// # Safety
+///
+/// `text` must point to `len` readable bytes, and `out` to a writable
+/// `uuidb64_t`. Either may be null, which is reported as
+/// `UUIDB64_NULL_POINTER`.
+#[no_mangle]
+pub unsafe extern "C" fn uuidb64_parse(
+    text: *const c_char,
+    len: usize,
+    out: *mut UuidB64Bytes,
+) -> c_int {
+    if text.is_null() || out.is_null() {
+        return UUIDB64_NULL_POINTER;
+    }
+    let text = slice::from_raw_parts(text as *const u8, len);
+    // non-UTF-8 input can't be Base64 either
+    let parsed = match ::std::str::from_utf8(text) {
+        Ok(text) => text.parse::<UuidB64>(),
+        Err(e) => Err(ParseUuidB64Error::InvalidCharacter {
+            char: char::REPLACEMENT_CHARACTER,
+            index: e.valid_up_to(),
+        }),
+    };
+    match parsed {
+        Ok(id) => {
+            ptr::write(out, id.into());
+            UUIDB64_OK
+        }
+        Err(ParseUuidB64Error::InvalidLength { .. }) => UUIDB64_INVALID_LENGTH,
+        Err(ParseUuidB64Error::InvalidCharacter { .. }) => UUIDB64_INVALID_CHARACTER,
+        Err(ParseUuidB64Error::InvalidUuidBytes) => UUIDB64_INVALID_UUID_BYTES,
+    }
+}
+
+/// Write the Base64 form of `*id` to `out`, NUL-terminated
+///
+/// Returns `UUIDB64_OK`, or `UUIDB64_NULL_POINTER` without writing anything.
+///
+/// # Safety
+///
+/// `id` must point to a `uuidb64_t`, and `out` to `UUIDB64_FORMAT_LEN`
+/// writable bytes. Either may be null.
+#[no_mangle]
+pub unsafe extern "C" fn uuidb64_format(id: *const UuidB64Bytes, out: *mut c_char) -> c_int {
+    if id.is_null() || out.is_null() {
+        return UUIDB64_NULL_POINTER;
+    }
+    let mut buf = [0; 22];
+    UuidB64::from(*id).encode_to(&mut buf);
+    ptr::copy_nonoverlapping(buf.as_ptr(), out as *mut u8, buf.len());
+    *out.add(buf.len()) = 0;
+    UUIDB64_OK
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ffi::CStr;
+    use std::os::raw::c_char;
+    use std::ptr;
+
+    use super::*;
+
+    #[test]
+    fn round_trips() {
+        let id: UuidB64 = "sMHuhm9GTxuNi3hJ51287g".parse().unwrap();
+        let raw = UuidB64Bytes::from(id);
+        assert_eq!(&raw.bytes, id.uuid().as_bytes());
+
+        let mut text = [0x7f as c_char; UUIDB64_FORMAT_LEN];
+        assert_eq!(
+            unsafe { uuidb64_format(&raw, text.as_mut_ptr()) },
+            UUIDB64_OK
+        );
+        let text = unsafe { CStr::from_ptr(text.as_ptr()) };
+        assert_eq!(text.to_str().unwrap(), "sMHuhm9GTxuNi3hJ51287g");
+
+        let mut parsed = UuidB64Bytes { bytes: [0; 16] };
+        let status = unsafe { uuidb64_parse(text.as_ptr(), 22, &mut parsed) };
+        assert_eq!(status, UUIDB64_OK);
+        assert_eq!(UuidB64::from(parsed), id);
+
+        let new = uuidb64_new();
+        assert_eq!(UuidB64::from(new).uuid().get_version_num(), 4);
+    }
+
+    #[test]
+    fn reports_errors() {
+        let mut out = UuidB64Bytes { bytes: [9; 16] };
+        let parse = |text: &[u8], out: &mut UuidB64Bytes| unsafe {
+            uuidb64_parse(text.as_ptr() as *const c_char, text.len(), out)
+        };
+        assert_eq!(
+            parse(b"sMHuhm9GTxuNi3hJ5128", &mut out),
+            UUIDB64_INVALID_LENGTH
+        );
+        assert_eq!(
+            parse(b"sMHuhm9GTxuNi3hJ5128!g", &mut out),
+            UUIDB64_INVALID_CHARACTER
+        );
+        assert_eq!(
+            parse(b"sMHuhm9GTxuNi3hJ5128\xffg", &mut out),
+            UUIDB64_INVALID_CHARACTER
+        );
+        // failures leave the output alone
+        assert_eq!(out.bytes, [9; 16]);
+
+        assert_eq!(
+            unsafe { uuidb64_parse(ptr::null(), 0, &mut out) },
+            UUIDB64_NULL_POINTER
+        );
+        assert_eq!(
+            unsafe { uuidb64_format(&out, ptr::null_mut()) },
+            UUIDB64_NULL_POINTER
+        );
+    }
+}
@@ -0,0 +1,114 @@
+//! The classic UUID text forms, for interop
+
+use std::fmt::{Display, Formatter, Result as FmtResult};
+
+use UuidB64;
+
+/// An ID displayed in the hyphenated form,
+/// `b0c1ee86-6f46-4f1b-8d8b-7849e75dbcee`
+///
+/// Made by [`UuidB64::format_hyphenated`][]. This is also what `{:#}`
+/// writes.
+///
+/// [`UuidB64::format_hyphenated`]: struct.UuidB64.html#method.format_hyphenated
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Hyphenated(UuidB64);
+
+/// An ID displayed as 32 hex digits, `b0c1ee866f464f1b8d8b7849e75dbcee`
+///
+/// Made by [`UuidB64::format_simple`][]. This is also what `{:x}` writes.
+///
+/// [`UuidB64::format_simple`]: struct.UuidB64.html#method.format_simple
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Simple(UuidB64);
+
+/// An ID displayed as a URN,
+/// `urn:uuid:b0c1ee86-6f46-4f1b-8d8b-7849e75dbcee`
+///
+/// Made by [`UuidB64::format_urn`][].
+///
+/// [`UuidB64::format_urn`]: struct.UuidB64.html#method.format_urn
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Urn(UuidB64);
+
+impl UuidB64 {
+    /// Display this ID in the hyphenated UUID form
+    ///
+    /// These adapters keep the `UuidB64` (they're `Copy`, and `into_inner`
+    /// gives it back) rather than going through the inner `Uuid`.
+    ///
+    /// ```
+    /// # use uuid_b64::UuidB64;
+    /// let id: UuidB64 = "sMHuhm9GTxuNi3hJ51287g".parse().unwrap();
+    /// assert_eq!(
+    ///     id.format_hyphenated().to_string(),
+    ///     "b0c1ee86-6f46-4f1b-8d8b-7849e75dbcee"
+    /// );
+    /// assert_eq!(id.format_simple().to_string(), "b0c1ee866f464f1b8d8b7849e75dbcee");
+    /// assert_eq!(
+    ///     format!("<{}>", id.format_urn()),
+    ///     "<urn:uuid:b0c1ee86-6f46-4f1b-8d8b-7849e75dbcee>"
+    /// );
+    /// ```
+    pub fn format_hyphenated(&self) -> Hyphenated {
+        Hyphenated(*self)
+    }
+
+    /// Display this ID as 32 hex digits
+    pub fn format_simple(&self) -> Simple {
+        Simple(*self)
+    }
+
+    /// Display this ID as a `urn:uuid:` URN
+    pub fn format_urn(&self) -> Urn {
+        Urn(*self)
+    }
+}
+
+macro_rules! adapter {
+    ($name:ident, $method:ident) => {
+        impl $name {
+            /// The ID being displayed
+            pub fn into_inner(self) -> UuidB64 {
+                self.0
+            }
+        }
+
+        impl Display for $name {
+            fn fmt(&self, f: &mut Formatter) -> FmtResult {
+                Display::fmt(&(self.0).0.$method(), f)
+            }
+        }
+
+        impl From<$name> for UuidB64 {
+            fn from(adapter: $name) -> UuidB64 {
+                adapter.0
+            }
+        }
+    };
+}
+
+adapter!(Hyphenated, hyphenated);
+adapter!(Simple, simple);
+adapter!(Urn, urn);
+
+#[cfg(test)]
+mod tests {
+    use UuidB64;
+
+    #[test]
+    fn match_the_uuid_forms() {
+        let id = UuidB64::new();
+        let uuid = id.uuid();
+        assert_eq!(
+            id.format_hyphenated().to_string(),
+            uuid.hyphenated().to_string()
+        );
+        assert_eq!(id.format_hyphenated().to_string(), format!("{:#}", id));
+        assert_eq!(id.format_simple().to_string(), uuid.simple().to_string());
+        assert_eq!(id.format_simple().to_string(), format!("{:x}", id));
+        assert_eq!(id.format_urn().to_string(), uuid.urn().to_string());
+        assert_eq!(id.format_urn().into_inner(), id);
+        assert_eq!(UuidB64::from(id.format_simple()), id);
+    }
+}
@@ -0,0 +1,58 @@
+extern crate garde;
+
+use self::garde::error::{Path, Report};
+use self::garde::{Error, Validate};
+
+use UuidB64;
+
+/// A `garde` custom rule for strings holding IDs
+///
+/// ```rust,ignore
+/// #[derive(Validate)]
+/// struct CreateOrder {
+///     #[garde(custom(uuid_b64::garde_uuid_b64))]
+///     customer_id: String,
+/// }
+/// ```
+///
+/// Works with any context type, so it can be used in structs with a custom
+/// `#[garde(context(...))]`.
+pub fn garde_uuid_b64<T, C>(value: &T, _context: &C) -> garde::Result
+where
+    T: AsRef<str> + ?Sized,
+{
+    match value.as_ref().parse::<UuidB64>() {
+        Ok(_) => Ok(()),
+        Err(_) => Err(Error::new("must be a 22-character Base64-encoded UUID")),
+    }
+}
+
+/// `UuidB64`s are always valid, this lets them be used with `#[garde(dive)]`
+/// alongside other validated fields
+impl Validate for UuidB64 {
+    type Context = ();
+
+    fn validate_into(&self, _: &(), _: &mut dyn FnMut() -> Path, _: &mut Report) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::garde::Validate;
+
+    use super::garde_uuid_b64;
+    use UuidB64;
+
+    #[test]
+    fn checks_strings() {
+        assert!(garde_uuid_b64(&UuidB64::new().to_string(), &()).is_ok());
+        assert!(garde_uuid_b64("sMHuhm9GTxuNi3hJ51287g", &()).is_ok());
+        assert!(garde_uuid_b64("", &()).is_err());
+        let error = garde_uuid_b64("nope", &42).unwrap_err();
+        assert!(error.message().contains("Base64"));
+    }
+
+    #[test]
+    fn ids_are_valid() {
+        assert!(UuidB64::new().validate().is_ok());
+    }
+}
@@ -0,0 +1,69 @@
+//! Microsoft's GUID byte order
+
+use UuidB64;
+
+/// Swap the first three fields of a UUID between big and little endian,
+/// which converts in either direction
+fn swap_fields(bytes: &mut [u8; 16]) {
+    bytes[..4].reverse();
+    bytes[4..6].reverse();
+    bytes[6..8].reverse();
+}
+
+impl UuidB64 {
+    /// The 16 bytes in Microsoft's GUID layout
+    ///
+    /// Windows, .NET's `Guid.ToByteArray` and SQL Server's
+    /// `CAST(... AS BINARY(16))` store the first three fields of a UUID
+    /// little-endian, and the rest as is. Use this where those expect raw
+    /// bytes, the hyphenated forms are the same either way.
+    ///
+    /// ```
+    /// # use uuid_b64::UuidB64;
+    /// let id: UuidB64 = "AAECAwQFBgcICQoLDA0ODw".parse().unwrap();
+    /// assert_eq!(
+    ///     id.to_guid_bytes(),
+    ///     [3, 2, 1, 0, 5, 4, 7, 6, 8, 9, 10, 11, 12, 13, 14, 15]
+    /// );
+    /// ```
+    pub fn to_guid_bytes(&self) -> [u8; 16] {
+        let mut bytes = *self.0.as_bytes();
+        swap_fields(&mut bytes);
+        bytes
+    }
+
+    /// Read an ID from bytes in Microsoft's GUID layout
+    ///
+    /// The inverse of [`to_guid_bytes`](#method.to_guid_bytes).
+    pub fn from_guid_bytes(mut bytes: [u8; 16]) -> UuidB64 {
+        swap_fields(&mut bytes);
+        UuidB64::from_bytes(bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use uuid::Uuid;
+
+    use UuidB64;
+
+    #[test]
+    fn matches_dotnet() {
+        // new Guid("b0c1ee86-6f46-4f1b-8d8b-7849e75dbcee").ToByteArray()
+        let dotnet = [
+            0x86, 0xee, 0xc1, 0xb0, 0x46, 0x6f, 0x1b, 0x4f, 0x8d, 0x8b, 0x78, 0x49, 0xe7, 0x5d,
+            0xbc, 0xee,
+        ];
+        let id = UuidB64::from(Uuid::parse_str("b0c1ee86-6f46-4f1b-8d8b-7849e75dbcee").unwrap());
+        assert_eq!(id.to_guid_bytes(), dotnet);
+        assert_eq!(UuidB64::from_guid_bytes(dotnet), id);
+    }
+
+    #[test]
+    fn roundtrips() {
+        for _ in 0..100 {
+            let id = UuidB64::new();
+            assert_eq!(UuidB64::from_guid_bytes(id.to_guid_bytes()), id);
+        }
+    }
+}
@@ -0,0 +1,89 @@
+extern crate headers;
+
+use self::headers::{Error, Header, HeaderName, HeaderValue};
+
+use UuidB64;
+
+static X_REQUEST_ID: HeaderName = HeaderName::from_static("x-request-id");
+
+/// The `x-request-id` header, holding a `UuidB64`
+///
+/// This implements `headers::Header`, so in axum `TypedHeader<RequestId>`
+/// extracts it, and requests with a missing or malformed ID are rejected
+/// with a `400 Bad Request`. Use `Option<TypedHeader<RequestId>>` to make it
+/// optional.
+///
+/// ```
+/// # extern crate headers;
+/// # extern crate uuid_b64;
+/// # use headers::HeaderMapExt;
+/// # use uuid_b64::RequestId;
+/// # fn main() {
+/// let mut map = headers::HeaderMap::new();
+/// map.insert("x-request-id", "sMHuhm9GTxuNi3hJ51287g".parse().unwrap());
+/// let RequestId(id) = map.typed_get().unwrap();
+/// assert_eq!(id.to_string(), "sMHuhm9GTxuNi3hJ51287g");
+/// # }
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct RequestId(pub UuidB64);
+
+impl Header for RequestId {
+    fn name() -> &'static HeaderName {
+        &X_REQUEST_ID
+    }
+
+    fn decode<'i, I>(values: &mut I) -> Result<Self, Error>
+    where
+        I: Iterator<Item = &'i HeaderValue>,
+    {
+        let value = values.next().ok_or_else(Error::invalid)?;
+        if values.next().is_some() {
+            return Err(Error::invalid());
+        }
+        value
+            .to_str()
+            .ok()
+            .and_then(|s| s.parse().ok())
+            .map(RequestId)
+            .ok_or_else(Error::invalid)
+    }
+
+    fn encode<E>(&self, values: &mut E)
+    where
+        E: Extend<HeaderValue>,
+    {
+        let value = HeaderValue::from_str(&self.0.to_istring())
+            .expect("base64 is always a valid header value");
+        values.extend(::std::iter::once(value));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::headers::{HeaderMap, HeaderMapExt};
+
+    use super::RequestId;
+    use UuidB64;
+
+    #[test]
+    fn roundtrips() {
+        let id = RequestId(UuidB64::new());
+        let mut map = HeaderMap::new();
+        map.typed_insert(id);
+        assert_eq!(map["x-request-id"], id.0.to_string());
+        assert_eq!(map.typed_get::<RequestId>(), Some(id));
+    }
+
+    #[test]
+    fn rejects_garbage() {
+        let mut map = HeaderMap::new();
+        map.insert("x-request-id", "not-an-id".parse().unwrap());
+        assert!(map.typed_try_get::<RequestId>().is_err());
+
+        let mut map = HeaderMap::new();
+        map.append("x-request-id", UuidB64::new().to_string().parse().unwrap());
+        map.append("x-request-id", UuidB64::new().to_string().parse().unwrap());
+        assert!(map.typed_try_get::<RequestId>().is_err());
+    }
+}
@@ -0,0 +1,96 @@
+//! Finding out what kind of UUID an ID is
+
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use uuid::UuidVariant;
+
+use UuidB64;
+
+impl UuidB64 {
+    /// The version number, from the top four bits of byte 6
+    ///
+    /// For RFC 9562 UUIDs this says how the ID was made: 4 is random, 1, 6
+    /// and 7 are time-based, 3 and 5 are name-based and 8 is custom. Other
+    /// variants don't have versions, but their bits are returned all the
+    /// same.
+    ///
+    /// ```
+    /// # use uuid_b64::UuidB64;
+    /// assert_eq!(UuidB64::new().version(), 4);
+    /// assert_eq!(UuidB64::new_v7().version(), 7);
+    /// ```
+    pub fn version(&self) -> usize {
+        self.0.get_version_num()
+    }
+
+    /// The variant, from the top bits of byte 8
+    ///
+    /// Every ID this crate generates is `UuidVariant::RFC4122`.
+    pub fn variant(&self) -> Option<UuidVariant> {
+        self.0.get_variant()
+    }
+
+    /// When a time-based (v1, v6 or v7) ID was created
+    ///
+    /// Returns `None` for other versions, which carry no timestamp. v7 IDs
+    /// have millisecond precision, v1 and v6 IDs 100ns.
+    ///
+    /// ```
+    /// # use std::time::{Duration, SystemTime};
+    /// # use uuid_b64::UuidB64;
+    /// let created = UuidB64::new_v7().get_timestamp().unwrap();
+    /// assert!(SystemTime::now().duration_since(created).unwrap() < Duration::from_secs(60));
+    /// assert_eq!(UuidB64::new().get_timestamp(), None);
+    /// ```
+    pub fn get_timestamp(&self) -> Option<SystemTime> {
+        if let Some(millis) = self.v7_millis() {
+            return Some(UNIX_EPOCH + Duration::from_millis(millis));
+        }
+        match self.v1_time()? {
+            Ok(after) => Some(UNIX_EPOCH + after),
+            Err(before) => UNIX_EPOCH.checked_sub(before),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, UNIX_EPOCH};
+
+    use uuid::{Uuid, UuidVariant};
+
+    use {ClockSequence, UuidB64};
+
+    #[test]
+    fn versions() {
+        let clock = ClockSequence::new();
+        assert_eq!(UuidB64::new().version(), 4);
+        assert_eq!(UuidB64::new_v1(&clock, [0; 6]).version(), 1);
+        assert_eq!(UuidB64::new_v6(&clock, [0; 6]).version(), 6);
+        assert_eq!(UuidB64::new_v7().version(), 7);
+        assert_eq!(UuidB64::new_v5(UuidB64::NAMESPACE_DNS, "a").version(), 5);
+        assert_eq!(UuidB64::NIL.version(), 0);
+    }
+
+    #[test]
+    fn variants() {
+        assert_eq!(UuidB64::new().variant(), Some(UuidVariant::RFC4122));
+        assert_eq!(UuidB64::NIL.variant(), Some(UuidVariant::NCS));
+        assert_eq!(UuidB64::MAX.variant(), Some(UuidVariant::Future));
+    }
+
+    #[test]
+    fn timestamps() {
+        let v7 = Uuid::parse_str("017f22e2-79b0-7cc3-98c4-dc0c0c07398f").unwrap();
+        assert_eq!(
+            UuidB64::from(v7).get_timestamp(),
+            Some(UNIX_EPOCH + Duration::from_millis(1_645_557_742_000))
+        );
+        let v6 = Uuid::parse_str("1ec9414c-232a-6b00-b3c8-9f6bdeced846").unwrap();
+        let v1 = Uuid::parse_str("c232ab00-9414-11ec-b3c8-9f6bdeced846").unwrap();
+        let expected = UNIX_EPOCH + Duration::from_secs(1_645_557_742);
+        assert_eq!(UuidB64::from(v6).get_timestamp(), Some(expected));
+        assert_eq!(UuidB64::from(v1).get_timestamp(), Some(expected));
+        assert_eq!(UuidB64::new_v8([0; 16]).get_timestamp(), None);
+    }
+}
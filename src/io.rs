@@ -0,0 +1,230 @@
+//! Reading and writing newline-delimited streams of IDs
+
+use std::io::{self, BufRead, Read, Write};
+use std::str;
+
+use errors::ErrorKind;
+use UuidB64;
+
+/// Longer lines can't hold an ID, so they aren't read into memory
+const MAX_LINE_LEN: u64 = 64;
+
+/// An iterator over the IDs in a newline-delimited stream
+///
+/// Each line is parsed as one ID. Surrounding whitespace (including `\r`
+/// from `\r\n` line endings) is ignored, as are blank lines. Parse errors
+/// carry the 1-based line number, and iteration can continue past them.
+/// Only one line is held in memory at a time, and its buffer is reused.
+/// Lines longer than 64 bytes are reported as errors without being read
+/// into memory, so a stream without newlines can't exhaust it.
+///
+/// ```
+/// # use uuid_b64::B64IdReader;
+/// let dump = "sMHuhm9GTxuNi3hJ51287g\nAAAAAQACAAMBAgMEBQYHCA\n";
+/// let ids = B64IdReader::new(dump.as_bytes())
+///     .collect::<Result<Vec<_>, _>>()
+///     .unwrap();
+/// assert_eq!(ids.len(), 2);
+/// ```
+#[derive(Debug)]
+pub struct B64IdReader<R> {
+    reader: R,
+    line: Vec<u8>,
+    line_number: usize,
+}
+
+impl<R: BufRead> B64IdReader<R> {
+    /// Read IDs from `reader`
+    pub fn new(reader: R) -> B64IdReader<R> {
+        B64IdReader {
+            reader,
+            line: Vec::with_capacity(24),
+            line_number: 0,
+        }
+    }
+
+    /// Unwrap the underlying reader
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+
+    /// Discard the rest of the current line
+    fn skip_line(&mut self) -> io::Result<()> {
+        loop {
+            let (found, used) = {
+                let buf = self.reader.fill_buf()?;
+                if buf.is_empty() {
+                    return Ok(());
+                }
+                match buf.iter().position(|&b| b == b'\n') {
+                    Some(i) => (true, i + 1),
+                    None => (false, buf.len()),
+                }
+            };
+            self.reader.consume(used);
+            if found {
+                return Ok(());
+            }
+        }
+    }
+}
+
+impl<R: BufRead> Iterator for B64IdReader<R> {
+    type Item = Result<UuidB64, ErrorKind>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            self.line.clear();
+            match (&mut self.reader)
+                .take(MAX_LINE_LEN)
+                .read_until(b'\n', &mut self.line)
+            {
+                Ok(0) => return None,
+                Ok(_) => self.line_number += 1,
+                Err(e) => return Some(Err(ErrorKind::Io(e))),
+            }
+            if self.line.len() as u64 == MAX_LINE_LEN && !self.line.ends_with(b"\n") {
+                if let Err(e) = self.skip_line() {
+                    return Some(Err(ErrorKind::Io(e)));
+                }
+                let start = String::from_utf8_lossy(&self.line).into_owned();
+                return Some(Err(ErrorKind::LineParseError(self.line_number, start)));
+            }
+            let line = match str::from_utf8(&self.line) {
+                Ok(line) => line.trim(),
+                Err(_) => {
+                    let line = String::from_utf8_lossy(&self.line).trim().to_owned();
+                    return Some(Err(ErrorKind::LineParseError(self.line_number, line)));
+                }
+            };
+            if line.is_empty() {
+                continue;
+            }
+            return Some(
+                line.parse()
+                    .map_err(|_| ErrorKind::LineParseError(self.line_number, line.into())),
+            );
+        }
+    }
+}
+
+/// Writes IDs to a stream, one per line
+///
+/// The counterpart to [`B64IdReader`](struct.B64IdReader.html). IDs are
+/// encoded without allocating, and each is written with a single call, so
+/// wrap unbuffered writers like `File` in a `BufWriter`.
+///
+/// ```
+/// # use uuid_b64::{B64IdReader, B64IdWriter, UuidB64};
+/// let ids = vec![UuidB64::new(), UuidB64::new()];
+/// let mut writer = B64IdWriter::new(Vec::new());
+/// writer.write_all(ids.iter().cloned()).unwrap();
+/// let out = writer.into_inner();
+/// assert_eq!(out.len(), 2 * 23);
+///
+/// let read = B64IdReader::new(&out[..]).collect::<Result<Vec<_>, _>>().unwrap();
+/// assert_eq!(read, ids);
+/// ```
+#[derive(Debug)]
+pub struct B64IdWriter<W> {
+    writer: W,
+    count: usize,
+}
+
+impl<W: Write> B64IdWriter<W> {
+    /// Write IDs to `writer`
+    pub fn new(writer: W) -> B64IdWriter<W> {
+        B64IdWriter { writer, count: 0 }
+    }
+
+    /// Write one ID and a newline
+    pub fn write_id(&mut self, id: &UuidB64) -> io::Result<()> {
+        let mut line = [b'\n'; 23];
+        line[..22].copy_from_slice(&id.to_b64_array());
+        self.writer.write_all(&line)?;
+        self.count += 1;
+        Ok(())
+    }
+
+    /// Write every ID from `ids`, stopping at the first error
+    pub fn write_all<I: IntoIterator<Item = UuidB64>>(&mut self, ids: I) -> io::Result<()> {
+        for id in ids {
+            self.write_id(&id)?;
+        }
+        Ok(())
+    }
+
+    /// How many IDs have been written
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// Flush the underlying writer
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+
+    /// Unwrap the underlying writer, without flushing it
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{B64IdReader, B64IdWriter};
+    use errors::ErrorKind;
+    use UuidB64;
+
+    #[test]
+    fn reads_lines() {
+        let ids: Vec<UuidB64> = (0..3).map(|_| UuidB64::new()).collect();
+        let input = format!("{}\r\n\n  {}\n{}", ids[0], ids[1], ids[2]);
+        let read: Vec<UuidB64> = B64IdReader::new(input.as_bytes())
+            .map(|id| id.unwrap())
+            .collect();
+        assert_eq!(read, ids);
+    }
+
+    #[test]
+    fn reports_line_numbers() {
+        let mut input = format!("{}\n\nnope\n", UuidB64::new()).into_bytes();
+        input.extend_from_slice(b"no\xffpe\r\n");
+        input.extend_from_slice(format!("{}\n", UuidB64::new()).as_bytes());
+        let results: Vec<_> = B64IdReader::new(&input[..]).collect();
+        assert_eq!(results.len(), 4);
+        match results[1] {
+            Err(ErrorKind::LineParseError(3, ref line)) if line == "nope" => {}
+            ref other => panic!("unexpected result: {:?}", other),
+        }
+        match results[2] {
+            Err(ErrorKind::LineParseError(4, ref line)) if line == "no\u{fffd}pe" => {}
+            ref other => panic!("unexpected result: {:?}", other),
+        }
+        assert!(results[3].is_ok());
+    }
+
+    #[test]
+    fn rejects_long_lines() {
+        let id = UuidB64::new();
+        let input = format!("{}\n{}\n", "x".repeat(1 << 20), id);
+        let results: Vec<_> = B64IdReader::new(input.as_bytes()).collect();
+        assert_eq!(results.len(), 2);
+        match results[0] {
+            Err(ErrorKind::LineParseError(1, ref line)) => assert_eq!(line.len(), 64),
+            ref other => panic!("unexpected result: {:?}", other),
+        }
+        assert_eq!(results[1].as_ref().unwrap(), &id);
+    }
+
+    #[test]
+    fn writes_lines() {
+        let ids = UuidB64::new_batch(3);
+        let mut writer = B64IdWriter::new(Vec::new());
+        writer.write_id(&ids[0]).unwrap();
+        writer.write_all(ids[1..].iter().cloned()).unwrap();
+        assert_eq!(writer.count(), 3);
+        let out = String::from_utf8(writer.into_inner()).unwrap();
+        assert_eq!(out, format!("{}\n{}\n{}\n", ids[0], ids[1], ids[2]));
+    }
+}
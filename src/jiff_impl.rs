@@ -0,0 +1,68 @@
+extern crate jiff;
+
+use self::jiff::Timestamp;
+
+use versioned::UuidB64V7;
+use UuidB64;
+
+impl UuidB64 {
+    /// The creation time embedded in a v7 UUID, as a `jiff::Timestamp`
+    ///
+    /// Returns `None` for non-v7 UUIDs, and for the (far future) timestamps
+    /// past the year 9999 that jiff can't represent.
+    ///
+    /// ```
+    /// # extern crate uuid;
+    /// # extern crate uuid_b64;
+    /// # use uuid::Uuid;
+    /// # use uuid_b64::UuidB64;
+    /// # fn main() {
+    /// let id = UuidB64::from(Uuid::parse_str("0174876e-807b-7000-8000-000000000000").unwrap());
+    /// assert_eq!(id.jiff_timestamp().unwrap().to_string(), "2020-09-13T12:26:40.123Z");
+    /// assert_eq!(UuidB64::new().jiff_timestamp(), None);
+    /// # }
+    /// ```
+    pub fn jiff_timestamp(&self) -> Option<Timestamp> {
+        self.v7_millis()
+            .and_then(|ms| Timestamp::from_millisecond(ms as i64).ok())
+    }
+}
+
+impl UuidB64V7 {
+    /// The time at which this ID was created, as a `jiff::Timestamp`
+    ///
+    /// Returns `None` only for timestamps past the year 9999.
+    pub fn jiff_timestamp(&self) -> Option<Timestamp> {
+        self.as_uuid_b64().jiff_timestamp()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::TryFrom;
+
+    use super::jiff::Timestamp;
+
+    use uuid::Uuid;
+
+    use {UuidB64, UuidB64V7, V7Generator};
+
+    #[test]
+    fn matches_generation_time() {
+        let before = Timestamp::now();
+        let id = UuidB64V7::try_from(V7Generator::new().generate()).unwrap();
+        let after = Timestamp::now();
+        let created = id.jiff_timestamp().unwrap();
+        assert!(before.as_millisecond() <= created.as_millisecond());
+        assert!(created <= after);
+        assert_eq!(created.as_millisecond() as u64, id.timestamp_millis());
+    }
+
+    #[test]
+    fn out_of_range() {
+        let max = UuidB64::from(Uuid::parse_str("ffffffff-ffff-7000-8000-000000000000").unwrap());
+        assert_eq!(max.jiff_timestamp(), None);
+        let epoch = UuidB64::from(Uuid::parse_str("00000000-0000-7000-8000-000000000000").unwrap());
+        assert_eq!(epoch.jiff_timestamp(), Some(Timestamp::UNIX_EPOCH));
+    }
+}
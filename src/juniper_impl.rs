@@ -0,0 +1,89 @@
+//! A juniper GraphQL scalar
+//!
+//! IDs are a `UuidB64` string scalar in the schema. Arguments and input
+//! fields that don't parse are rejected with the parse error, before the
+//! resolver runs.
+
+extern crate juniper;
+
+use self::juniper::{graphql_scalar, ScalarValue};
+
+use UuidB64;
+
+#[graphql_scalar]
+#[graphql(
+    name = "UuidB64",
+    description = "A UUID, as 22 characters of unpadded base64",
+    with = uuid_b64_scalar,
+    to_output_with = ScalarValue::from_displayable,
+    parse_token(String)
+)]
+type UuidB64Scalar = UuidB64;
+
+mod uuid_b64_scalar {
+    use super::UuidB64Scalar;
+
+    pub(super) fn from_input(s: &str) -> Result<UuidB64Scalar, Box<str>> {
+        s.parse()
+            .map_err(|e: ::errors::ParseUuidB64Error| e.to_string().into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::juniper::{
+        execute_sync, graphql_object, graphql_value, EmptyMutation, EmptySubscription,
+        GraphQLError, InputValue, RootNode, Variables,
+    };
+
+    use UuidB64;
+
+    const ID: &str = "sMHuhm9GTxuNi3hJ51287g";
+
+    struct Query;
+
+    #[graphql_object]
+    impl Query {
+        fn echo(id: UuidB64) -> UuidB64 {
+            id
+        }
+    }
+
+    fn run(query: &str, vars: &Variables) -> Result<super::juniper::Value, GraphQLError> {
+        let schema = RootNode::new(
+            Query,
+            EmptyMutation::<()>::new(),
+            EmptySubscription::<()>::new(),
+        );
+        execute_sync(query, None, &schema, vars, &()).map(|(value, errors)| {
+            assert!(errors.is_empty(), "{:?}", errors);
+            value
+        })
+    }
+
+    #[test]
+    fn roundtrips_as_a_string() {
+        let value = run(&format!("{{ echo(id: \"{}\") }}", ID), &Variables::new()).unwrap();
+        assert_eq!(value, graphql_value!({ "echo": ID }));
+
+        let mut vars = Variables::new();
+        vars.insert("id".to_owned(), InputValue::scalar(ID));
+        let value = run("query($id: UuidB64!) { echo(id: $id) }", &vars).unwrap();
+        assert_eq!(value, graphql_value!({ "echo": ID }));
+    }
+
+    #[test]
+    fn rejects_malformed_ids() {
+        let literal = run("{ echo(id: \"nope\") }", &Variables::new());
+        assert!(literal.is_err());
+
+        let mut vars = Variables::new();
+        vars.insert("id".to_owned(), InputValue::scalar("nope"));
+        match run("query($id: UuidB64!) { echo(id: $id) }", &vars) {
+            Err(GraphQLError::ValidationError(errors)) => {
+                assert!(errors[0].message().contains("nope"), "{:?}", errors);
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+    }
+}
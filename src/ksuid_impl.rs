@@ -0,0 +1,144 @@
+//! Migrating from KSUIDs
+//!
+//! A KSUID is 160 bits: a 32-bit timestamp in seconds since 2014-05-13
+//! (`KSUID_EPOCH`) followed by a 128-bit random payload. That doesn't fit in
+//! a UUID, so there are two mappings, each keeping a different part:
+//!
+//! * `from_ksuid_payload` keeps the payload, bit for bit, as the ID. The
+//!   timestamp is dropped, so IDs no longer sort by creation time, and the
+//!   version and variant bits are whatever the payload had.
+//! * `from_ksuid_v7` makes a v7 ID at the KSUID's time, with the top 74 bits
+//!   of the payload as its random part. IDs sort like the KSUIDs they came
+//!   from.
+//!
+//! Going the other way, `to_ksuid` turns a v7 ID into a KSUID with the ID's
+//! (whole) second as the timestamp and all 128 bits of the ID as the payload,
+//! so `from_ksuid_payload` gets the ID back unchanged.
+
+extern crate svix_ksuid;
+
+use self::svix_ksuid::{Ksuid, KsuidLike, KSUID_EPOCH};
+use uuid::Uuid;
+
+use UuidB64;
+
+impl UuidB64 {
+    /// The ID made of the KSUID's 16-byte payload
+    ///
+    /// Lossless for the payload, but drops the timestamp.
+    pub fn from_ksuid_payload(ksuid: &Ksuid) -> UuidB64 {
+        UuidB64(Uuid::from_bytes(ksuid.payload()).expect("KSUID payloads are 16 bytes"))
+    }
+
+    /// A v7 ID with the KSUID's creation time, and the top 74 bits of its
+    /// payload as the random part
+    ///
+    /// The timestamp only has second resolution, so the milliseconds are
+    /// always zero. IDs sort in the same order as the KSUIDs, though KSUIDs
+    /// differing only in the last 54 bits of their payload come out as the
+    /// same ID.
+    pub fn from_ksuid_v7(ksuid: &Ksuid) -> UuidB64 {
+        let millis = (i64::from(ksuid.timestamp_raw()) + KSUID_EPOCH) as u128 * 1000;
+        let mut payload = [0; 16];
+        payload.copy_from_slice(ksuid.payload());
+        let random = u128::from_be_bytes(payload) >> 54;
+        // the version goes above rand_a's 12 bits and the variant above
+        // rand_b's 62, so the payload's bits stay in order
+        UuidB64::from_u128(
+            millis << 80 | 7 << 76 | (random >> 62) << 64 | 0b10 << 62 | (random & ((1 << 62) - 1)),
+        )
+    }
+
+    /// A KSUID created at this v7 ID's time (truncated to the second), with
+    /// the whole ID as its payload
+    ///
+    /// Returns `None` for non-v7 IDs, and for times outside the years 2014 to
+    /// 2150 that KSUIDs cover. `from_ksuid_payload` reverses this.
+    ///
+    /// ```rust,ignore
+    /// let id = UuidB64::new_v7();
+    /// let ksuid = id.to_ksuid().unwrap();
+    /// assert_eq!(UuidB64::from_ksuid_payload(&ksuid), id);
+    /// ```
+    pub fn to_ksuid(&self) -> Option<Ksuid> {
+        self.v7_millis()
+            .and_then(|millis| self.to_ksuid_at(millis as i64 / 1000))
+    }
+
+    /// A KSUID created at `unix_secs`, with the whole ID as its payload
+    ///
+    /// For IDs without a timestamp of their own. Returns `None` for times
+    /// outside the years 2014 to 2150 that KSUIDs cover.
+    pub fn to_ksuid_at(&self, unix_secs: i64) -> Option<Ksuid> {
+        let timestamp = unix_secs.checked_sub(KSUID_EPOCH)?;
+        if timestamp < 0 || timestamp > i64::from(u32::MAX) {
+            return None;
+        }
+        let mut bytes = [0; 20];
+        bytes[..4].copy_from_slice(&(timestamp as u32).to_be_bytes());
+        bytes[4..].copy_from_slice(self.0.as_bytes());
+        Some(Ksuid::from_bytes(bytes))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::svix_ksuid::{Ksuid, KsuidLike, KSUID_EPOCH};
+
+    use UuidB64;
+
+    /// The example from the KSUID spec, created at 2017-10-10T04:00:47Z
+    fn example() -> Ksuid {
+        Ksuid::from_base62("0ujtsYcgvSTl8PAuAdqWYSMnLOv").unwrap()
+    }
+
+    #[test]
+    fn payload_mapping_is_lossless() {
+        let id = UuidB64::from_ksuid_payload(&example());
+        assert_eq!(
+            id.uuid().simple().to_string(),
+            "b5a1cd34b5f99d1154fb6853345c9735"
+        );
+        assert_eq!(id.to_ksuid_at(1_507_608_047), Some(example()));
+
+        for id in &[UuidB64::new_v7(), UuidB64::new_v7()] {
+            let ksuid = id.to_ksuid().unwrap();
+            assert_eq!(UuidB64::from_ksuid_payload(&ksuid), *id);
+            assert_eq!(
+                ksuid.timestamp_seconds(),
+                id.v7_millis().unwrap() as i64 / 1000
+            );
+        }
+    }
+
+    #[test]
+    fn v7_mapping_keeps_the_time_and_order() {
+        let id = UuidB64::from_ksuid_v7(&example());
+        assert_eq!(id.uuid().get_version_num(), 7);
+        assert_eq!(id.v7_millis(), Some(1_507_608_047_000));
+        assert_eq!(
+            id.uuid().simple().to_string(),
+            "015f04712d987b5a8734d2d7e6744553"
+        );
+
+        let mut ksuids: Vec<_> = (0..100)
+            .map(|i| Ksuid::from_seconds(Some(1_600_000_000 + i % 7), None))
+            .collect();
+        ksuids.sort();
+        let ids: Vec<_> = ksuids.iter().map(UuidB64::from_ksuid_v7).collect();
+        let mut sorted = ids.clone();
+        sorted.sort();
+        assert_eq!(ids, sorted);
+    }
+
+    #[test]
+    fn out_of_range_times() {
+        assert_eq!(UuidB64::new().to_ksuid(), None);
+        let id = UuidB64::new();
+        assert_eq!(id.to_ksuid_at(KSUID_EPOCH - 1), None);
+        assert_eq!(id.to_ksuid_at(KSUID_EPOCH + i64::from(u32::MAX) + 1), None);
+        assert_eq!(id.to_ksuid_at(i64::MIN), None);
+        let last = id.to_ksuid_at(KSUID_EPOCH + i64::from(u32::MAX)).unwrap();
+        assert_eq!(last.timestamp_raw(), u32::MAX);
+    }
+}
@@ -31,6 +31,9 @@
 //! hearing arguments about why this is a ridiculous decision and I should have
 //! made `new` be `new_v4`.
 //!
+//! For IDs that sort by creation time, and so keep database indexes compact,
+//! use `UuidB64::new_v7`.
+//!
 //! # Why?
 //!
 //! UUIDs are great:
@@ -82,27 +85,226 @@
 //! Just use `UuidB64` everywhere you would use `Uuid`, and use `UuidB64::from`
 //! to create one from an existing UUID.
 //!
+//! `use uuid_b64::prelude::*` brings in `UuidB64` and its friends along with
+//! `Uuid` itself. `Uuid` is also re-exported at the crate root, it's always
+//! the version `UuidB64` is built against.
+//!
+//! To keep different kinds of ID apart, use `Id<User>`, `PrefixedId<User>`
+//! (which reads `usr_sMHuhm9GTxuNi3hJ51287g`), or `define_id!` for a newtype
+//! per entity.
+//!
 //! ## Features
 //!
-//! * `serde` enables serialization/deserialization via Serde.
-//! * `diesel-uuid` enables integration with Diesel's UUID support, this is
-//!   only tested on postgres, PRs welcome for other DBs.
+//! * `serde` enables serialization/deserialization via Serde. IDs are Base64
+//!   strings in human-readable formats like JSON, and 16 raw bytes in binary
+//!   formats like bincode, postcard or MessagePack. Self-describing formats
+//!   can also hold the 16 bytes as a byte string or an array, as other
+//!   languages' serializers write them. `serde::as_base64` does
+//!   the same for `Uuid` fields (and `Option`s, `Vec`s and maps of them), with
+//!   `#[serde(with = ...)]`.
+//! * `actix-web` adds `IdPath`, an extractor for an ID in the route path.
+//!   Malformed IDs are rejected with a `400 Bad Request` and a JSON body
+//!   naming the offending segment, rather than the generic `Path` error.
+//! * `aide` documents `UuidB64` path and query parameters correctly in
+//!   aide-generated OpenAPI specs, by way of the `schemars` feature. With
+//!   `axum` too, `IdPath` can be used in `ApiRouter` handlers, and is
+//!   documented as a path parameter named `id`.
+//! * `arbitrary` implements `arbitrary::Arbitrary`, so fuzz targets can take
+//!   IDs (any 16 bytes) as part of their structured input.
+//! * `arrow` adds `UuidB64::to_arrow_array` and `from_arrow_array`, for
+//!   converting columns of IDs to and from Arrow `FixedSizeBinary(16)` arrays
+//!   (Parquet's `UUID` type).
+//! * `avro` converts `UuidB64` into apache-avro `Value`s and implements
+//!   `AvroSchema`, with the `avro` module providing schemas for writing IDs
+//!   as a 16-byte `fixed` or as a `uuid` string.
+//! * `axum` adds the same `IdPath` extractor for axum, implementing
+//!   `FromRequestParts` for it and `IntoResponse` for its `IdRejection`.
+//! * `base58` adds `UuidB64::to_base58` and `from_base58`, for APIs that
+//!   take IDs in Bitcoin's Base58 alphabet.
+//! * `bevy` makes `UuidB64` and the versioned IDs Bevy ECS components, and
+//!   reflects them so inspectors show them in base64.
+//! * `borsh` implements `BorshSerialize` and `BorshDeserialize`, encoding IDs
+//!   as their 16 raw bytes.
+//! * `bson` converts `UuidB64` into BSON UUID binaries (subtype 4), and adds
+//!   `bson_binary` for storing serde-mapped fields as those instead of strings.
+//! * `clap` implements clap's `ValueParserFactory`, so `UuidB64` can be used
+//!   directly as an argument type, with malformed IDs reported like any other
+//!   invalid value.
+//! * `cli` builds the `uuidb64` binary, for generating IDs (`uuidb64 new -n
+//!   100 --v7`), converting between hex and Base64 (`encode` and `decode`,
+//!   reading stdin when given no arguments) and checking them (`validate`).
+//! * `datafusion` adds `b64uuid_udf` and `uuid_b64_udf`, SQL functions for
+//!   converting between IDs and UUIDs that can be registered on a DataFusion
+//!   `SessionContext`.
+//! * `defmt` implements `defmt::Format`, logging IDs as their 22 Base64
+//!   characters without going through `core::fmt`.
+//! * `diesel` maps `UuidB64` to Diesel's `Binary` (16 raw bytes, for SQLite
+//!   blobs and MySQL `BINARY(16)`) and `Text` (Base64, also reading
+//!   hyphenated UUIDs) SQL types on every backend.
+//! * `diesel-uuid` adds Diesel's Postgres `Uuid` type as well.
+//!   `Vec<UuidB64>` maps to `Array<Uuid>`, so array columns and `= ANY(...)`
+//!   filters work too.
+//! * `ffi` adds the `ffi` module, a C ABI (`uuidb64_new`, `uuidb64_parse` and
+//!   `uuidb64_format`, declared in `include/uuid_b64.h`) for linking the same
+//!   encoding into C and C++ through a `cdylib` or `staticlib` crate.
+//! * `futures` exposes the ID generators as async `Stream`s.
+//! * `garde` adds `garde_uuid_b64`, a custom rule for checking string
+//!   fields, and implements `garde::Validate` for `UuidB64`.
+//! * `headers` adds `RequestId`, an `x-request-id` header for use with
+//!   axum's `TypedHeader`.
+//! * `jiff` adds `jiff_timestamp`, the creation time of v7 IDs as a
+//!   `jiff::Timestamp`.
+//! * `juniper` adds a `UuidB64` string scalar to juniper GraphQL schemas.
+//!   Malformed IDs in arguments and variables are rejected with the parse
+//!   error.
+//! * `ksuid` adds conversions from KSUIDs for migrating to `UuidB64`:
+//!   `from_ksuid_payload` keeps the 128-bit payload as the ID, `from_ksuid_v7`
+//!   makes a v7 ID that sorts like the KSUID, and `to_ksuid` goes back from a v7
+//!   ID without losing any bits.
+//! * `mmap` adds `MappedIds`, for memory-mapping files in the packed binary
+//!   format written by `write_packed`.
+//! * `mysql` implements the `mysql`/`mysql_async` crates' value conversions,
+//!   writing IDs as `BINARY(16)` and reading them from either that or a
+//!   `CHAR(36)`/`CHAR(22)` column.
+//! * `napi` registers Node.js bindings (`generate`, `encode`, `decode` and a
+//!   `UuidB64` class), so a `cdylib` crate depending on this one builds a
+//!   `.node` addon that shares this implementation of the format.
+//! * `odbc` implements `odbc_api::IntoParameter` for `UuidB64` and
+//!   `MaybeUuidB64`, binding IDs as hyphenated UUID text, and adds
+//!   `OdbcUuidB64` for output parameters and fetched columns.
+//! * `opentelemetry` adds `UuidB64::to_trace_id` and `from_trace_id`, for
+//!   propagating request IDs as OpenTelemetry trace IDs (the same 16 bytes).
+//! * `postgres` implements postgres-types' `ToSql` and `FromSql`, so the
+//!   `postgres` and `tokio-postgres` crates take `UuidB64` parameters and read
+//!   `uuid` columns (and `Vec<UuidB64>` for `uuid[]`) directly.
+//! * `proptest` adds `testing::any_id`, a proptest strategy weighted toward
+//!   nil, max and each UUID version, and implements proptest's `Arbitrary` with
+//!   it so structs holding IDs can derive their strategies.
+//! * `prost` adds the `proto` module, for putting IDs in the `bytes` and
+//!   `string` fields of prost-generated messages, along with `From<UuidB64>`
+//!   for `Vec<u8>` and `bytes::Bytes`.
+//! * `python` registers a `uuid_b64` Python module (`generate`, `encode`,
+//!   `decode`, and `from_uuid`/`to_uuid` for `uuid.UUID`), so a `cdylib` crate
+//!   depending on this one builds an extension that shares this implementation
+//!   of the format.
+//! * `quickcheck` implements `quickcheck::Arbitrary`, with the same weighting
+//!   toward edge cases as `testing::any_id`.
+//! * `rand` adds `UuidB64::new_with_rng`, for generating v4 IDs from any
+//!   `rand_core::RngCore`, such as a seeded generator in tests, and
+//!   implements rand's `Distribution<UuidB64>` for `StandardUniform`, so
+//!   `rng.random::<UuidB64>()` works. `UuidB64Factory::with_rng` makes a
+//!   factory draw from one too.
+//! * `redis` implements the redis crate's `ToRedisArgs` and `FromRedisValue`,
+//!   writing IDs as base64, or as 16 raw bytes when wrapped in `RedisBytes`,
+//!   and reading either.
+//! * `rkyv` implements rkyv's `Archive`, `Serialize` and `Deserialize`. IDs
+//!   archive as `ArchivedUuidB64`, their 16 bytes, which compares equal to
+//!   and displays like the original, so archived structs can be used in place.
+//! * `regex` adds `UuidB64::regex`, `UuidB64::PATTERN` compiled once.
+//! * `rocket` implements Rocket's `FromParam`, `FromFormField` and
+//!   `UriDisplay`, so routes and forms take `UuidB64` directly and `uri!`
+//!   builds links with it.
+//! * `rusqlite` implements rusqlite's `ToSql` and `FromSql`, storing IDs as
+//!   16-byte `BLOB`s, or as base64 `TEXT` when wrapped in `SqliteText`. Both
+//!   forms, and hyphenated UUIDs, are read back.
+//! * `schemars` implements `JsonSchema` for `UuidB64`, describing IDs as
+//!   22-character base64 strings with a regex `pattern`.
+//! * `scylla` implements the ScyllaDB driver's `SerializeValue` and
+//!   `DeserializeValue`, mapping `UuidB64` to the native CQL `uuid` type (on
+//!   Cassandra too).
+//! * `sea-orm` implements SeaORM's `TryGetable` and `TryFromU64`, so
+//!   `UuidB64` can be used directly as an entity column type. It enables
+//!   `sea-query` for `Value` and `ValueType`.
+//! * `sea-query` converts `UuidB64` to and from `sea_query::Value`, binding
+//!   IDs as UUIDs in statements built without SeaORM.
+//! * `signed-id` adds `SignedId`, an ID with a truncated HMAC-SHA256 tag for
+//!   detecting tampering.
+//! * `slog` implements `slog::Value` for `UuidB64` and `B64String`, so IDs go
+//!   into key-value pairs directly (`"user" => id`), logged as their Base64
+//!   string.
+//! * `specta` implements `specta::Type`, so Tauri command bindings type
+//!   `UuidB64`s as `string` in the generated TypeScript.
+//! * `speedy` implements speedy's `Readable` and `Writable`, encoding IDs as
+//!   their 16 raw bytes.
+//! * `sqlx-any` implements sqlx's `Type`, `Encode` and `Decode` for the `Any`
+//!   driver, binding IDs as hyphenated UUID text.
+//! * `sqlx-mysql` implements sqlx's `Type`, `Encode` and `Decode` for MySQL,
+//!   writing IDs as `BINARY(16)` and reading them from that or from text.
+//! * `sqlx-postgres` implements sqlx's `Type`, `Encode` and `Decode` for
+//!   Postgres `uuid` columns, including `uuid[]` so that `&[UuidB64]` can be
+//!   bound directly in `WHERE id = ANY($1)` queries.
+//! * `sqlx-sqlite` does the same for SQLite, writing IDs as 16-byte `BLOB`s
+//!   and reading them from those or from `TEXT`.
+//! * `subtle` adds `UuidB64::ct_eq` and implements `subtle::ConstantTimeEq`,
+//!   for comparing IDs that are secrets without leaking timing (`==` is not
+//!   constant-time).
+//! * `tantivy` adds helpers for indexing IDs as tantivy bytes fields and
+//!   querying by them.
+//! * `test-util` adds `DeterministicGenerator`, which generates the same IDs
+//!   for the same seed, and can stand in for `UuidB64::new` within a scope so
+//!   snapshot tests see reproducible IDs. It also adds
+//!   `UuidB64Factory::with_seed`, for factories that draw from one.
+//! * `tiberius` implements tiberius' `ToSql`, `IntoSql` and `FromSql`,
+//!   mapping `UuidB64` to SQL Server's `UNIQUEIDENTIFIER` with the same
+//!   hyphenated form on both sides.
+//! * `tonic` adds `RequestIdInterceptor`, which propagates (or generates)
+//!   an `x-request-id` metadata entry on gRPC calls.
+//! * `ts-rs` implements `ts_rs::TS`, exporting `UuidB64` as a branded
+//!   string type so that generated models keep IDs distinct from other
+//!   strings.
+//! * `ulid` converts between `ulid::Ulid` and `UuidB64`, which share the same
+//!   128 bits, with `UuidB64::from_ulid` and `From<UuidB64> for Ulid`, and adds
+//!   `UuidB64::parse_ulid` for the 26-character ULID string.
+//! * `uniffi` exposes `UuidB64` to Kotlin and Swift as a string-backed type,
+//!   along with functions for generating and parsing IDs.
+//! * `utoipa` implements utoipa's `ToSchema`, describing IDs the same way as
+//!   the `schemars` feature (plus an example) in `IntoParams` structs,
+//!   `params(...)` and response bodies.
+//! * `uuid1` converts between `UuidB64` and version 1 of the uuid crate's `Uuid`
+//!   (`from_uuid1`, `to_uuid1` and `From<UuidB64>`), and lets an ID be viewed as
+//!   one with `as_uuid1`, for passing IDs to crates that have moved to uuid 1.
+//! * `validator` adds `validate_uuid_b64`, for checking string fields with
+//!   the `validator` crate.
+//! * `valuable` implements `valuable::Valuable` for `UuidB64` and `B64String`,
+//!   so with tracing's `valuable` support IDs are logged structurally, as their
+//!   Base64 string, with `id = tracing::field::valuable(&id)`.
+//! * `wasm` converts between `UuidB64` and wasm-bindgen's `JsValue`, so IDs
+//!   cross the wasm boundary as plain JS strings.
+//! * `web` enables both `actix-web` and `axum`.
+//! * `zeroize` implements `Zeroize` for `UuidB64` and adds
+//!   `to_zeroizing_string`, for wiping IDs used as secrets from memory. Being
+//!   `Copy`, IDs can't wipe themselves on drop, so keep them in a
+//!   `Zeroizing<UuidB64>`.
+//! * `standard-alphabet` switches Display, parsing and Serde over to the
+//!   standard (`+` and `/`) base64 alphabet, still without padding, for
+//!   deployments that need to stay compatible with legacy systems. This
+//!   changes the format for the whole build, so it should only be enabled by
+//!   final binaries, never by libraries.
 
 extern crate base64;
-#[cfg(feature = "diesel")]
-#[macro_use]
-extern crate diesel_derive_newtype;
-#[macro_use]
-extern crate error_chain;
+// bevy_reflect's and pyo3's macros refer to `::core`, which this edition
+// resolves from the crate root
+#[cfg(any(feature = "bevy", feature = "python"))]
+extern crate core;
 extern crate inlinable_string;
 #[macro_use]
 extern crate lazy_static;
+// pyo3's macros refer to `::pyo3`
+#[cfg(feature = "python")]
+extern crate pyo3;
 extern crate uuid;
 
+#[cfg(all(test, feature = "serde"))]
+extern crate bincode;
 #[cfg(all(test, feature = "diesel-uuid"))]
 #[macro_use]
 extern crate diesel;
-#[cfg(all(test, feature = "diesel-uuid"))]
+// juniper's `graphql_object` refers to `::juniper`
+#[cfg(all(test, feature = "juniper"))]
+extern crate juniper;
+// rkyv's derives refer to `::rkyv`
+#[cfg(all(test, feature = "rkyv"))]
+extern crate rkyv;
 #[cfg(all(test, feature = "serde"))]
 #[macro_use]
 extern crate serde_derive;
@@ -110,24 +312,261 @@ extern crate serde_derive;
 #[macro_use]
 extern crate serde_json;
 
+use std::borrow::Borrow;
 use std::convert::From;
-use std::str::FromStr;
-use std::fmt::{Debug, Display, Formatter, Result as FmtResult};
+use std::mem;
+use std::str::{self, FromStr};
+use std::fmt::{self, Debug, Display, Formatter, Result as FmtResult};
 
-use uuid::Uuid;
 use base64::{CharacterSet, Config, LineWrap};
-use base64::display::Base64Display;
 use inlinable_string::inline_string::InlineString;
 
-use errors::{ErrorKind, ResultExt};
+pub use b64_string::B64String;
+pub use builder::UuidB64Builder;
+pub use cached::CachedUuidB64;
+#[cfg(feature = "clap")]
+pub use clap_impl::UuidB64ValueParser;
+pub use comb::CombPlacement;
+#[cfg(feature = "datafusion")]
+pub use datafusion_impl::{b64uuid_udf, uuid_b64_udf};
+pub use encoder::{Alphabet, EncoderConfig, StdB64};
+pub use encoding::EncodedUuid;
+pub use errors::{BulkParseError, ErrorKind, InvalidEntry, ParseUuidB64Error};
+pub use extsort::{sort_dedup_file, MergeDedup};
+pub use factory::UuidB64Factory;
+pub use formats::{Hyphenated, Simple, Urn};
+#[cfg(feature = "garde")]
+pub use garde_impl::garde_uuid_b64;
+#[cfg(feature = "headers")]
+pub use headers_impl::RequestId;
+pub use io::{B64IdReader, B64IdWriter};
+pub use maybe::MaybeUuidB64;
+#[cfg(feature = "odbc")]
+pub use odbc_impl::OdbcUuidB64;
+#[cfg(feature = "mmap")]
+pub use packed::MappedIds;
+pub use packed::{
+    as_packed_bytes, from_packed_bytes, read_packed, to_packed_bytes, write_packed, PackedIdReader,
+    PACKED_MAGIC,
+};
+pub use pair::UuidB64Pair;
+#[cfg(feature = "serde")]
+#[doc(hidden)]
+pub use serde_impl::serde as __serde;
+pub use prefixed::{IdPrefix, PrefixedId};
+pub use redacted::Redacted;
+#[cfg(feature = "redis")]
+pub use redis_impl::RedisBytes;
+#[cfg(feature = "rusqlite")]
+pub use rusqlite_impl::SqliteText;
+#[cfg(feature = "rkyv")]
+pub use rkyv_impl::ArchivedUuidB64;
+#[cfg(feature = "futures")]
+pub use stream::{BatchedIdStream, IdStream};
+#[cfg(feature = "signed-id")]
+pub use signed::SignedId;
+pub use slug::{ShortId, ShortSlug};
+pub use step::IdRange;
+#[cfg(feature = "test-util")]
+pub use test_util::DeterministicGenerator;
+#[cfg(feature = "tonic")]
+pub use tonic_impl::{request_id, RequestIdInterceptor, REQUEST_ID_METADATA_KEY};
+pub use typed::Id;
+pub use uuid::{Uuid, UuidVariant};
+pub use v1::ClockSequence;
+pub use v7::V7Generator;
+#[cfg(feature = "validator")]
+pub use validator_impl::validate_uuid_b64;
+pub use versioned::{UuidB64V4, UuidB64V7};
+#[cfg(any(feature = "actix-web", feature = "axum"))]
+pub use web::{IdPath, IdRejection};
 
+#[cfg(feature = "avro")]
+pub mod avro;
+#[cfg(feature = "bson")]
+pub mod bson_binary;
+#[cfg(feature = "serde")]
+pub mod comma_separated;
+#[cfg(feature = "serde")]
+pub mod compat;
+pub mod encoding;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+#[cfg(feature = "prost")]
+pub mod proto;
+#[cfg(feature = "serde")]
+pub mod serde;
+#[cfg(feature = "proptest")]
+pub mod testing;
+#[cfg(feature = "actix-web")]
+mod actix_impl;
+#[cfg(all(feature = "aide", feature = "axum"))]
+mod aide_impl;
+#[cfg(feature = "arbitrary")]
+mod arbitrary_impl;
+#[cfg(feature = "arrow")]
+mod arrow_impl;
+#[cfg(feature = "axum")]
+mod axum_impl;
+mod b64_string;
+mod base32;
+#[cfg(feature = "base58")]
+mod base58_impl;
+mod base62;
+mod batch;
+#[cfg(feature = "bevy")]
+mod bevy_impl;
+#[cfg(feature = "borsh")]
+mod borsh_impl;
+mod builder;
+mod cached;
+#[cfg(feature = "clap")]
+mod clap_impl;
+mod comb;
+#[cfg(feature = "datafusion")]
+mod datafusion_impl;
+#[cfg(feature = "defmt")]
+mod defmt_impl;
+mod derive;
+#[cfg(feature = "diesel")]
+mod diesel_impl;
+mod encoder;
 mod errors;
+mod extsort;
+mod factory;
+mod formats;
+#[cfg(feature = "garde")]
+mod garde_impl;
+mod guid;
+#[cfg(feature = "headers")]
+mod headers_impl;
+mod inspect;
+mod io;
+#[cfg(feature = "jiff")]
+mod jiff_impl;
+#[cfg(feature = "juniper")]
+mod juniper_impl;
+#[cfg(feature = "ksuid")]
+mod ksuid_impl;
+#[macro_use]
+mod literal;
+mod maybe;
+mod multi;
+#[macro_use]
+mod newtype;
+mod object_id;
+#[cfg(feature = "mysql")]
+mod mysql_impl;
+#[cfg(feature = "napi")]
+mod napi_impl;
+#[cfg(feature = "odbc")]
+mod odbc_impl;
+#[cfg(feature = "opentelemetry")]
+mod opentelemetry_impl;
+mod packed;
+mod pair;
+mod path;
+#[cfg(feature = "postgres")]
+mod postgres_impl;
+mod prefixed;
+pub mod prelude;
+#[cfg(feature = "python")]
+mod python_impl;
+#[cfg(feature = "quickcheck")]
+mod quickcheck_impl;
+#[cfg(feature = "rand")]
+mod rand_impl;
+mod redacted;
+#[cfg(feature = "redis")]
+mod redis_impl;
+#[cfg(feature = "regex")]
+mod regex_impl;
+mod relay;
+#[cfg(feature = "rkyv")]
+mod rkyv_impl;
+#[cfg(feature = "rocket")]
+mod rocket_impl;
+#[cfg(feature = "rusqlite")]
+mod rusqlite_impl;
+#[cfg(feature = "schemars")]
+mod schemars_impl;
+#[cfg(feature = "scylla")]
+mod scylla_impl;
+#[cfg(feature = "sea-orm")]
+mod sea_orm_impl;
+#[cfg(feature = "sea-query")]
+mod sea_query_impl;
+#[cfg(feature = "specta")]
+mod specta_impl;
+#[cfg(feature = "speedy")]
+mod speedy_impl;
+mod shard;
+#[cfg(feature = "slog")]
+mod slog_impl;
+mod slug;
+mod step;
+#[cfg(feature = "signed-id")]
+mod signed;
+#[cfg(feature = "sqlx-any")]
+mod sqlx_any_impl;
+#[cfg(feature = "sqlx-postgres")]
+mod sqlx_impl;
+#[cfg(feature = "sqlx-mysql")]
+mod sqlx_mysql_impl;
+#[cfg(feature = "sqlx-sqlite")]
+mod sqlx_sqlite_impl;
+#[cfg(feature = "futures")]
+mod stream;
+#[cfg(feature = "subtle")]
+mod subtle_impl;
+#[cfg(feature = "tantivy")]
+mod tantivy_impl;
+#[cfg(feature = "test-util")]
+mod test_util;
+#[cfg(feature = "tiberius")]
+mod tiberius_impl;
+#[cfg(feature = "tonic")]
+mod tonic_impl;
+#[cfg(feature = "ts-rs")]
+mod ts_rs_impl;
+mod typed;
+#[cfg(feature = "ulid")]
+mod ulid_impl;
+#[cfg(feature = "uniffi")]
+mod uniffi_impl;
+#[cfg(feature = "uuid1")]
+mod uuid1_impl;
 #[cfg(feature = "serde")]
 mod serde_impl;
+mod v1;
+mod v7;
+#[cfg(feature = "utoipa")]
+mod utoipa_impl;
+#[cfg(feature = "validator")]
+mod validator_impl;
+#[cfg(feature = "valuable")]
+mod valuable_impl;
+mod versioned;
+#[cfg(feature = "wasm")]
+mod wasm_impl;
+#[cfg(any(feature = "actix-web", feature = "axum"))]
+mod web;
+#[cfg(feature = "zeroize")]
+mod zeroize_impl;
+
+// UniFFI's generated code expects its tag type at the crate root
+#[cfg(feature = "uniffi")]
+uniffi::setup_scaffolding!();
+
+/// The alphabet used everywhere, chosen at build time
+#[cfg(not(feature = "standard-alphabet"))]
+const CHARACTER_SET: CharacterSet = CharacterSet::UrlSafe;
+#[cfg(feature = "standard-alphabet")]
+const CHARACTER_SET: CharacterSet = CharacterSet::Standard;
 
 lazy_static! {
     static ref B64_CONFIG: Config = Config::new(
-        CharacterSet::UrlSafe,
+        CHARACTER_SET,
         false, // pad?
         false, // trim whitespace?
         LineWrap::NoWrap,
@@ -135,21 +574,211 @@ lazy_static! {
 }
 
 /// It's a Uuid that displays as Base 64
+///
+/// `UuidB64` has exactly the same memory layout as `Uuid`: 16 bytes with no
+/// alignment requirements.
+///
+/// IDs are ordered by their 16 bytes, big-endian, the same as `Uuid` and as
+/// a `uuid` column in Postgres, so they can be used as `BTreeMap` keys and
+/// v7 IDs sort by creation time. The Base64 strings do *not* sort the same
+/// way, because neither base64 alphabet is in ASCII order (`-`/`+` and `_`/`/`
+/// encode the largest values but sort before the letters), so sort `UuidB64`s
+/// rather than their strings:
+///
+/// ```
+/// # use uuid_b64::{Uuid, UuidB64};
+/// let low = UuidB64::NIL;
+/// let high = UuidB64::from(Uuid::parse_str("f8000000-0000-0000-0000-000000000000").unwrap());
+/// assert!(low < high);
+/// assert!(low.to_string() > high.to_string()); // "AAAA..." > "-AAA..."
+/// ```
+///
+/// `==` is not constant-time. Compare IDs that are secrets with `ct_eq`
+/// (with the `subtle` feature).
 #[derive(Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
-#[cfg_attr(feature = "diesel", derive(DieselNewType))]
+#[repr(transparent)]
 pub struct UuidB64(uuid::Uuid);
 
 impl UuidB64 {
+    /// The nil UUID, with all 128 bits zero
+    ///
+    /// This is also the `Default`, and sorts before every other ID.
+    ///
+    /// ```
+    /// # use uuid_b64::UuidB64;
+    /// assert_eq!(UuidB64::NIL.to_string(), "AAAAAAAAAAAAAAAAAAAAAA");
+    /// assert_eq!(UuidB64::default(), UuidB64::NIL);
+    /// ```
+    pub const NIL: UuidB64 = UuidB64::from_bytes([0; 16]);
+
+    /// The max UUID, with all 128 bits set, which sorts after every other ID
+    pub const MAX: UuidB64 = UuidB64::from_bytes([0xff; 16]);
+
+    /// How many characters every displayed ID has
+    pub const ENCODED_LEN: usize = 22;
+
+    /// The 64 characters IDs are displayed with, in order of their value
+    ///
+    /// The URL-safe alphabet, or the standard one with the
+    /// `standard-alphabet` feature.
+    ///
+    /// ```
+    /// # use uuid_b64::UuidB64;
+    /// # #[cfg(not(feature = "standard-alphabet"))]
+    /// assert!(UuidB64::ALPHABET.ends_with("0123456789-_"));
+    /// ```
+    #[cfg(not(feature = "standard-alphabet"))]
+    pub const ALPHABET: &'static str =
+        "ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+    /// The 64 characters IDs are displayed with, in order of their value
+    #[cfg(feature = "standard-alphabet")]
+    pub const ALPHABET: &'static str =
+        "ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+    /// A regular expression matching IDs exactly as they're displayed
+    ///
+    /// 21 characters of the alphabet, then one of the four characters that
+    /// leave the 4 unused bits at the end zero. For schemas and validation
+    /// in other languages, which can't share this crate's parser. With the
+    /// `regex` feature, [`regex`](#method.regex) has it compiled.
+    ///
+    /// ```
+    /// # use uuid_b64::UuidB64;
+    /// # #[cfg(not(feature = "standard-alphabet"))]
+    /// assert_eq!(UuidB64::PATTERN, "^[A-Za-z0-9_-]{21}[AQgw]$");
+    /// ```
+    #[cfg(not(feature = "standard-alphabet"))]
+    pub const PATTERN: &'static str = "^[A-Za-z0-9_-]{21}[AQgw]$";
+    /// A regular expression matching IDs exactly as they're displayed
+    #[cfg(feature = "standard-alphabet")]
+    pub const PATTERN: &'static str = "^[A-Za-z0-9+/]{21}[AQgw]$";
+
     /// Generate a new v4 Uuid
+    ///
+    /// With the `test-util` feature, IDs come from the
+    /// [`DeterministicGenerator`](struct.DeterministicGenerator.html) of the
+    /// current scope, if there is one.
     pub fn new() -> UuidB64 {
+        #[cfg(feature = "test-util")]
+        {
+            if let Some(id) = test_util::scoped_id() {
+                return id;
+            }
+        }
         UuidB64(Uuid::new_v4())
     }
 
+    /// Whether this is the nil UUID
+    pub fn is_nil(&self) -> bool {
+        self.0.is_nil()
+    }
+
     /// Copy the raw UUID out
     pub fn uuid(&self) -> Uuid {
         self.0
     }
 
+    /// Unwrap the raw UUID
+    ///
+    /// `From<UuidB64> for Uuid` isn't possible: with the `From` impl for
+    /// everything that converts into a `Uuid`, it would give a second
+    /// `From<UuidB64> for UuidB64`.
+    pub fn into_uuid(self) -> Uuid {
+        self.0
+    }
+
+    /// An ID from its 16 bytes, in big-endian order
+    ///
+    /// Unlike the `Uuid` constructors this is a `const fn`.
+    ///
+    /// ```
+    /// # use uuid_b64::UuidB64;
+    /// const ALL_ONES: UuidB64 = UuidB64::from_bytes([0xff; 16]);
+    /// assert_eq!(ALL_ONES, UuidB64::MAX);
+    ///
+    /// let id = UuidB64::new();
+    /// assert_eq!(UuidB64::from_bytes(*id.as_bytes()), id);
+    /// ```
+    pub const fn from_bytes(bytes: [u8; 16]) -> UuidB64 {
+        // `UuidB64` is `repr(transparent)` over `Uuid`, which in the uuid
+        // versions Cargo.toml allows is a struct with a single `[u8; 16]`
+        // field. If it's also 16 bytes with an alignment of 1, that field
+        // fills it exactly, so there's nothing but the bytes.
+        const _: () = assert!(mem::size_of::<UuidB64>() == 16 && mem::align_of::<UuidB64>() == 1);
+
+        // Safe because of the above, and because any 16 bytes are a valid
+        // UUID.
+        unsafe { mem::transmute::<[u8; 16], UuidB64>(bytes) }
+    }
+
+    /// The 16 bytes of this ID, in big-endian order
+    pub fn as_bytes(&self) -> &[u8; 16] {
+        self.0.as_bytes()
+    }
+
+    /// An ID from its 128 bits as a big-endian number
+    ///
+    /// This stands in for `From<u128>`, which would overlap with the `From`
+    /// impl for everything that converts into a `Uuid`. The other direction
+    /// is a `From` impl on `u128`.
+    ///
+    /// ```
+    /// # use uuid_b64::UuidB64;
+    /// let id = UuidB64::from_u128(0xb0c1_ee86_6f46_4f1b_8d8b_7849_e75d_bcee);
+    /// assert_eq!(id, "sMHuhm9GTxuNi3hJ51287g".parse().unwrap());
+    /// assert_eq!(u128::from(id), 0xb0c1_ee86_6f46_4f1b_8d8b_7849_e75d_bcee);
+    /// ```
+    pub const fn from_u128(n: u128) -> UuidB64 {
+        UuidB64::from_bytes(n.to_be_bytes())
+    }
+
+    /// The 128 bits of this ID as a big-endian number, which orders the same
+    /// way as `UuidB64` does
+    pub fn to_u128(self) -> u128 {
+        u128::from_be_bytes(*self.0.as_bytes())
+    }
+
+    /// An ID from exactly 16 bytes
+    ///
+    /// (This can't be `TryFrom<&[u8]>`, which would overlap with the `From`
+    /// impl for everything that converts into a `Uuid`.)
+    pub fn from_slice(bytes: &[u8]) -> Result<UuidB64, ErrorKind> {
+        if bytes.len() != 16 {
+            return Err(ErrorKind::InvalidBinary(format!(
+                "expected 16 bytes, got {}",
+                bytes.len()
+            )));
+        }
+        Ok(UuidB64(Uuid::from_bytes(bytes).expect("UUIDs are 16 bytes")))
+    }
+
+    /// The 16 raw bytes of this UUID, in big-endian order
+    ///
+    /// This is intended for use as a key in byte-ordered stores (RocksDB,
+    /// LMDB, sled, etc). Keys sort identically to `UuidB64`s themselves, so
+    /// `a.cmp(&b) == a.sort_key().cmp(&b.sort_key())` always holds. Because
+    /// v7 UUIDs keep their millisecond timestamp in the leading 48 bits, keys
+    /// for v7 IDs sort by creation time.
+    ///
+    /// Note that this is *not* true of the Base64 string representation, the
+    /// url-safe alphabet is not in ASCII order.
+    ///
+    /// ```
+    /// # extern crate uuid;
+    /// # extern crate uuid_b64;
+    /// # use uuid::Uuid;
+    /// # use uuid_b64::UuidB64;
+    /// # fn main() {
+    /// let a = UuidB64::from(Uuid::parse_str("00000000-0000-4000-8000-000000000001").unwrap());
+    /// let b = UuidB64::from(Uuid::parse_str("ff000000-0000-4000-8000-000000000000").unwrap());
+    /// assert!(a < b);
+    /// assert!(a.sort_key() < b.sort_key());
+    /// # }
+    /// ```
+    pub fn sort_key(&self) -> [u8; 16] {
+        *self.0.as_bytes()
+    }
+
     /// Convert this to a new [`InlineString`][]
     ///
     /// `InlineString`s are stack-allocated and therefore faster than
@@ -178,6 +807,61 @@ impl UuidB64 {
         buf
     }
 
+    /// The Base64 encoding as an array of ASCII bytes, without allocating
+    ///
+    /// This is what `Display` writes.
+    ///
+    /// ```
+    /// # use uuid_b64::UuidB64;
+    /// let id: UuidB64 = "sMHuhm9GTxuNi3hJ51287g".parse().unwrap();
+    /// assert_eq!(&id.to_b64_array(), b"sMHuhm9GTxuNi3hJ51287g");
+    /// ```
+    pub fn to_b64_array(&self) -> [u8; 22] {
+        let mut buf = [0; 22];
+        self.encode_to(&mut buf);
+        buf
+    }
+
+    /// Encode into a reusable buffer, returning it as a `&str`
+    ///
+    /// ```
+    /// # use uuid_b64::UuidB64;
+    /// let mut buf = [0; 22];
+    /// for id in &[UuidB64::new(), UuidB64::new()] {
+    ///     assert_eq!(id.encode_to(&mut buf), id.to_string());
+    /// }
+    /// ```
+    pub fn encode_to<'a>(&self, buf: &'a mut [u8; 22]) -> &'a str {
+        base64::encode_config_slice(self.0.as_bytes(), *B64_CONFIG, buf);
+        // Base64 is always ASCII
+        unsafe { str::from_utf8_unchecked(buf) }
+    }
+
+    /// Write the Base64 encoding to anything that implements `fmt::Write`,
+    /// without allocating
+    ///
+    /// ```
+    /// # use uuid_b64::UuidB64;
+    /// let id: UuidB64 = "sMHuhm9GTxuNi3hJ51287g".parse().unwrap();
+    /// let mut line = String::with_capacity(64);
+    /// line.push_str("{\"id\":\"");
+    /// id.write_to(&mut line).unwrap();
+    /// line.push_str("\"}");
+    /// assert_eq!(line, r#"{"id":"sMHuhm9GTxuNi3hJ51287g"}"#);
+    /// ```
+    pub fn write_to<W: fmt::Write + ?Sized>(&self, w: &mut W) -> FmtResult {
+        w.write_str(self.encode_to(&mut [0; 22]))
+    }
+
+    /// Convert this to a `String` of exactly 22 bytes
+    ///
+    /// Unlike `to_string`, this allocates once, with no spare capacity.
+    pub fn to_b64_string(&self) -> String {
+        let mut s = String::with_capacity(22);
+        s.push_str(self.encode_to(&mut [0; 22]));
+        s
+    }
+
     /// Write the Base64-encoded UUID into the provided buffer
     ///
     /// ```
@@ -200,20 +884,132 @@ impl UuidB64 {
 
 /// Parse a B64 encoded string into a UuidB64
 ///
+/// Only the exact string that `Display` produces is accepted, so parsing and
+/// displaying always gives back the input. 22 characters hold 132 bits, and
+/// strings whose last 4 bits aren't zero are rejected as having an invalid
+/// last character; see
+/// [`UuidB64::parse_lenient`](struct.UuidB64.html#method.parse_lenient).
+///
 /// ```rust
-/// # use uuid_b64::UuidB64;
+/// # use uuid_b64::{ParseUuidB64Error, UuidB64};
 /// let parsed_b64: UuidB64 = "sMHuhm9GTxuNi3hJ51287g".parse().unwrap();
 /// assert_eq!(format!("{:?}", parsed_b64), "UuidB64(sMHuhm9GTxuNi3hJ51287g)");
+/// assert_eq!(
+///     "sMHuhm9GTxuNi3hJ51287h".parse::<UuidB64>(),
+///     Err(ParseUuidB64Error::InvalidCharacter { char: 'h', index: 21 })
+/// );
+/// assert_eq!(
+///     "sMHuhm9GTxuNi3hJ5128".parse::<UuidB64>(),
+///     Err(ParseUuidB64Error::InvalidLength { found: 20 })
+/// );
 /// ```
 impl FromStr for UuidB64 {
-    type Err = errors::ErrorKind;
+    type Err = ParseUuidB64Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let bytes =
-            base64::decode_config(s, *B64_CONFIG).chain_err(|| ErrorKind::ParseError(s.into()))?;
-        let id = Uuid::from_bytes(&bytes).chain_err(|| ErrorKind::ParseError(s.into()))?;
+        UuidB64::parse_reusing(s, &mut Vec::with_capacity(16))
+    }
+}
+
+impl UuidB64 {
+    /// Parse strictly, like `FromStr`, decoding through `scratch` so that
+    /// parsing many IDs needs only one allocation
+    pub(crate) fn parse_reusing(
+        s: &str,
+        scratch: &mut Vec<u8>,
+    ) -> Result<UuidB64, ParseUuidB64Error> {
+        let id = UuidB64::parse_lenient_reusing(s, scratch)?;
+        // The values of 'A', 'Q', 'g' and 'w' are the multiples of 16, and
+        // they're the same in both alphabets
+        if !s.ends_with(&['A', 'Q', 'g', 'w'][..]) {
+            return Err(ParseUuidB64Error::InvalidCharacter {
+                char: s[21..].chars().next().expect("22 ASCII characters"),
+                index: 21,
+            });
+        }
+        Ok(id)
+    }
+
+    fn parse_lenient_reusing(
+        s: &str,
+        scratch: &mut Vec<u8>,
+    ) -> Result<UuidB64, ParseUuidB64Error> {
+        if s.len() != 22 {
+            return Err(ParseUuidB64Error::InvalidLength { found: s.len() });
+        }
+        scratch.clear();
+        base64::decode_config_buf(s, *B64_CONFIG, scratch).map_err(|e| match e {
+            base64::DecodeError::InvalidByte(index, byte) => ParseUuidB64Error::InvalidCharacter {
+                // everything before the first invalid byte is ASCII
+                char: s[index..].chars().next().unwrap_or(byte as char),
+                index,
+            },
+            base64::DecodeError::InvalidLength => ParseUuidB64Error::InvalidUuidBytes,
+        })?;
+        let id = Uuid::from_bytes(scratch).map_err(|_| ParseUuidB64Error::InvalidUuidBytes)?;
         Ok(UuidB64(id))
     }
+
+    /// Parse an ID, ignoring the 4 unused bits at the end
+    ///
+    /// Several strings decode to each ID this way, differing only in the
+    /// last character. Only for reading IDs from systems that don't zero
+    /// those bits; don't use this where strings are compared, such as for
+    /// deduplication or signatures.
+    ///
+    /// ```rust
+    /// # use uuid_b64::UuidB64;
+    /// let lenient = UuidB64::parse_lenient("sMHuhm9GTxuNi3hJ51287h").unwrap();
+    /// assert_eq!(lenient.to_string(), "sMHuhm9GTxuNi3hJ51287g");
+    /// ```
+    pub fn parse_lenient(s: &str) -> Result<UuidB64, ParseUuidB64Error> {
+        UuidB64::parse_lenient_reusing(s, &mut Vec::with_capacity(16))
+    }
+
+    /// Check that `s` would parse, without decoding it
+    ///
+    /// This accepts and rejects exactly what `FromStr` does, with the same
+    /// errors, but only looks at each character once and never allocates.
+    /// For rejecting malformed IDs at the edge of a service.
+    ///
+    /// ```
+    /// # use uuid_b64::{ParseUuidB64Error, UuidB64};
+    /// assert_eq!(UuidB64::validate("sMHuhm9GTxuNi3hJ51287g"), Ok(()));
+    /// assert_eq!(
+    ///     UuidB64::validate("sMHuhm9GTxuNi3hJ5128.g"),
+    ///     Err(ParseUuidB64Error::InvalidCharacter { char: '.', index: 20 })
+    /// );
+    /// ```
+    pub fn validate(s: &str) -> Result<(), ParseUuidB64Error> {
+        if s.len() != 22 {
+            return Err(ParseUuidB64Error::InvalidLength { found: s.len() });
+        }
+        let invalid = |index: usize| ParseUuidB64Error::InvalidCharacter {
+            // everything before `index` is ASCII, so it's a char boundary
+            char: s[index..].chars().next().expect("index is in bounds"),
+            index,
+        };
+        if let Some(index) = s.bytes().position(|c| literal::decode_char(c).is_none()) {
+            return Err(invalid(index));
+        }
+        // see `parse_reusing`
+        if !s.ends_with(&['A', 'Q', 'g', 'w'][..]) {
+            return Err(invalid(21));
+        }
+        Ok(())
+    }
+
+    /// Whether `s` would parse, see [`validate`](#method.validate)
+    pub fn is_valid(s: &str) -> bool {
+        UuidB64::validate(s).is_ok()
+    }
+}
+
+/// The nil UUID
+impl Default for UuidB64 {
+    fn default() -> UuidB64 {
+        UuidB64::NIL
+    }
 }
 
 /// Right now this is just `Uuid`, but anything Uuid is comfortable with, we are
@@ -226,6 +1022,35 @@ where
     }
 }
 
+impl AsRef<Uuid> for UuidB64 {
+    fn as_ref(&self) -> &Uuid {
+        &self.0
+    }
+}
+
+/// `UuidB64` hashes and compares exactly like the `Uuid` inside it, so maps
+/// and sets keyed by `UuidB64` can be looked up with a `&Uuid`
+///
+/// ```
+/// # use std::collections::HashMap;
+/// # use uuid_b64::UuidB64;
+/// let id = UuidB64::new();
+/// let mut names = HashMap::new();
+/// names.insert(id, "Ada");
+/// assert_eq!(names.get(id.as_ref()), Some(&"Ada"));
+/// ```
+impl Borrow<Uuid> for UuidB64 {
+    fn borrow(&self) -> &Uuid {
+        &self.0
+    }
+}
+
+impl From<UuidB64> for u128 {
+    fn from(id: UuidB64) -> u128 {
+        id.to_u128()
+    }
+}
+
 impl Debug for UuidB64 {
     /// Same as the display formatter, but includes `UuidB64()` around it
     ///
@@ -248,6 +1073,9 @@ impl Debug for UuidB64 {
 impl Display for UuidB64 {
     /// Write Base64 encoding of this UUID
     ///
+    /// The alternate flag (`{:#}`) writes the usual hyphenated form instead,
+    /// for matching IDs up with logs and databases that use it.
+    ///
     /// ```rust
     /// # extern crate uuid;
     /// # extern crate uuid_b64;
@@ -257,12 +1085,42 @@ impl Display for UuidB64 {
     /// let known_id = Uuid::parse_str("b0c1ee86-6f46-4f1b-8d8b-7849e75dbcee").unwrap();
     /// let as_b64 = UuidB64::from(known_id);
     /// assert_eq!(format!("{}", as_b64), "sMHuhm9GTxuNi3hJ51287g");
+    /// assert_eq!(format!("{:#}", as_b64), "b0c1ee86-6f46-4f1b-8d8b-7849e75dbcee");
     /// # }
     /// ```
     fn fmt(&self, f: &mut Formatter) -> FmtResult {
-        // can only hit this error if we use an invalid line length
-        let wrapper = Base64Display::with_config(self.0.as_bytes(), *B64_CONFIG).unwrap();
-        write!(f, "{}", wrapper)
+        if f.alternate() {
+            Display::fmt(&self.0.hyphenated(), f)
+        } else {
+            self.write_to(f)
+        }
+    }
+}
+
+impl fmt::LowerHex for UuidB64 {
+    /// Write the 32 hex digits of the simple UUID form
+    ///
+    /// ```
+    /// # use uuid_b64::UuidB64;
+    /// let id: UuidB64 = "sMHuhm9GTxuNi3hJ51287g".parse().unwrap();
+    /// assert_eq!(format!("{:x}", id), "b0c1ee866f464f1b8d8b7849e75dbcee");
+    /// assert_eq!(format!("{:X}", id), "B0C1EE866F464F1B8D8B7849E75DBCEE");
+    /// ```
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        for byte in self.0.as_bytes() {
+            write!(f, "{:02x}", byte)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::UpperHex for UuidB64 {
+    /// Write the 32 hex digits of the simple UUID form, in upper case
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        for byte in self.0.as_bytes() {
+            write!(f, "{:02X}", byte)?;
+        }
+        Ok(())
     }
 }
 
@@ -276,6 +1134,103 @@ mod tests {
         let fmted = format!("{}", id);
         assert_eq!(fmted.len(), 22);
         assert_eq!(format!("UuidB64({})", fmted), format!("{:?}", id));
+        let mut buf = String::new();
+        id.to_buf(&mut buf);
+        assert_eq!(fmted, buf);
+    }
+
+    #[test]
+    fn other_representations() {
+        let id = UuidB64::new();
+        assert_eq!(format!("{:#}", id), id.uuid().hyphenated().to_string());
+        assert_eq!(format!("{:x}", id), id.uuid().simple().to_string());
+        assert_eq!(
+            format!("{:X}", id),
+            id.uuid().simple().to_string().to_uppercase()
+        );
+        // the flag doesn't leak into Debug
+        assert_eq!(format!("{:#?}", id), format!("{:?}", id));
+    }
+
+    #[test]
+    fn byte_and_integer_conversions() {
+        let id: UuidB64 = "sMHuhm9GTxuNi3hJ51287g".parse().unwrap();
+        assert_eq!(id.as_bytes(), id.uuid().as_bytes());
+        assert_eq!(UuidB64::from_bytes(*id.as_bytes()), id);
+        assert_eq!(UuidB64::from_slice(&id.as_bytes()[..]).unwrap(), id);
+        assert!(UuidB64::from_slice(&id.as_bytes()[1..]).is_err());
+
+        let n: u128 = id.into();
+        assert_eq!(n, 0xb0c1_ee86_6f46_4f1b_8d8b_7849_e75d_bcee);
+        assert_eq!(UuidB64::from_u128(n), id);
+        assert_eq!(u128::from(UuidB64::NIL), 0);
+        assert_eq!(UuidB64::from_u128(u128::MAX), UuidB64::MAX);
+        // the numbers sort like the IDs
+        let (a, b) = (UuidB64::new_v7(), UuidB64::new_v7());
+        assert!(a < b && a.to_u128() < b.to_u128());
+    }
+
+    #[test]
+    fn borrows_as_uuid() {
+        use std::collections::{BTreeSet, HashMap};
+
+        fn takes_uuid<U: AsRef<Uuid>>(id: U) -> Uuid {
+            *id.as_ref()
+        }
+
+        let id = UuidB64::new();
+        assert_eq!(takes_uuid(id), id.uuid());
+        assert_eq!(id.into_uuid(), id.uuid());
+
+        let mut map = HashMap::new();
+        map.insert(id, 1);
+        map.insert(UuidB64::new(), 2);
+        assert_eq!(map.get(&id.uuid()), Some(&1));
+        assert_eq!(map.get(&Uuid::nil()), None);
+        let set: BTreeSet<_> = (0..10).map(|_| UuidB64::new()).collect();
+        let first = *set.iter().next().unwrap();
+        assert!(set.contains(&first.uuid()));
+        assert_eq!(set.range::<Uuid, _>(..first.uuid()).count(), 0);
+    }
+
+    #[test]
+    fn validate_agrees_with_parsing() {
+        let mut inputs = vec![
+            String::new(),
+            "sMHuhm9GTxuNi3hJ51287".to_string(),
+            "sMHuhm9GTxuNi3hJ51287gg".to_string(),
+            "sMHuhm9GTxuNi3hJ51287=".to_string(),
+            "sMHuhm9GTxuNi3hJ5128==".to_string(),
+            "sMHuhm9GTxuNi3hJ5128\u{e9}".to_string(),
+            "\u{1F600}sMHuhm9GTxuNi3hJ5128".to_string(),
+            "sMHuhm9GTxuN i3hJ51287".to_string(),
+        ];
+        for _ in 0..100 {
+            let id = UuidB64::new().to_string();
+            for (i, c) in "-_+/=.AQgwh\\0".chars().enumerate() {
+                let mut bytes = id.clone().into_bytes();
+                bytes[(i * 7) % 22] = c as u8;
+                inputs.push(String::from_utf8(bytes).unwrap());
+            }
+            inputs.push(id);
+        }
+        for s in &inputs {
+            assert_eq!(
+                UuidB64::validate(s),
+                s.parse::<UuidB64>().map(|_| ()),
+                "{:?}",
+                s
+            );
+            assert_eq!(UuidB64::is_valid(s), s.parse::<UuidB64>().is_ok());
+        }
+    }
+
+    #[test]
+    fn to_b64_string_is_exact() {
+        let id = UuidB64::new();
+        let s = id.to_b64_string();
+        assert_eq!(s, id.to_string());
+        assert_eq!(s.capacity(), 22);
     }
 
     #[test]
@@ -286,6 +1241,61 @@ mod tests {
         assert_eq!(parsed, original);
     }
 
+    #[test]
+    fn parse_errors_say_why() {
+        assert_eq!(
+            "".parse::<UuidB64>(),
+            Err(ParseUuidB64Error::InvalidLength { found: 0 })
+        );
+        assert_eq!(
+            "sMHuhm9GTx.Ni3hJ51287g".parse::<UuidB64>(),
+            Err(ParseUuidB64Error::InvalidCharacter {
+                char: '.',
+                index: 10
+            })
+        );
+        assert_eq!(
+            "sMHuhm9GTxéi3hJ51287g".parse::<UuidB64>(),
+            Err(ParseUuidB64Error::InvalidCharacter {
+                char: 'é',
+                index: 10
+            })
+        );
+
+        fn is_std_error<E: ::std::error::Error + Send + Sync + 'static>(_: &E) {}
+        is_std_error(&"nope".parse::<UuidB64>().unwrap_err());
+        is_std_error(&UuidB64::from_slice(&[]).unwrap_err());
+    }
+
+    #[test]
+    fn parse_rejects_non_canonical() {
+        let id = UuidB64::new();
+        let encoded = id.to_string();
+        let mut last_bits_set = encoded[..21].to_owned();
+        last_bits_set.push(match encoded.as_bytes()[21] {
+            b'A' => 'B',
+            b'Q' => 'R',
+            b'g' => 'h',
+            _ => 'x',
+        });
+        match last_bits_set.parse::<UuidB64>() {
+            Err(ParseUuidB64Error::InvalidCharacter { index: 21, .. }) => {}
+            other => panic!("{} was accepted: {:?}", last_bits_set, other),
+        }
+        assert_eq!(UuidB64::parse_lenient(&last_bits_set).unwrap(), id);
+        assert_eq!(UuidB64::parse_lenient(&encoded).unwrap(), id);
+    }
+
+    #[test]
+    fn from_slice_checks_length() {
+        let id = UuidB64::new();
+        assert_eq!(UuidB64::from_slice(id.uuid().as_bytes()).unwrap(), id);
+        assert_eq!(
+            UuidB64::from_slice(&[0; 17]).unwrap_err().to_string(),
+            "Invalid UUID binary: expected 16 bytes, got 17"
+        );
+    }
+
     #[test]
     fn from_uuid_works() {
         let _ = UuidB64::from(Uuid::new_v4());
@@ -301,9 +1311,107 @@ mod tests {
             b64.to_istring();
         }
     }
+
+    #[test]
+    fn alphabet_matches_feature() {
+        let id = UuidB64::from(Uuid::parse_str("fbffbffb-ffbf-fbff-bffb-ffbffbffbfff").unwrap());
+        if cfg!(feature = "standard-alphabet") {
+            assert_eq!(id.to_string(), "+/+/+/+/+/+/+/+/+/+//w");
+        } else {
+            assert_eq!(id.to_string(), "-_-_-_-_-_-_-_-_-_-__w");
+        }
+        assert_eq!(id.to_string().parse::<UuidB64>().unwrap(), id);
+    }
+
+    #[test]
+    fn nil_and_max() {
+        assert_eq!(UuidB64::NIL.uuid(), Uuid::nil());
+        assert!(UuidB64::NIL.is_nil());
+        assert!(!UuidB64::MAX.is_nil());
+        assert_eq!(UuidB64::MAX.sort_key(), [0xff; 16]);
+        assert_eq!(UuidB64::default(), UuidB64::NIL);
+        let id = UuidB64::new();
+        assert!(UuidB64::NIL < id && id < UuidB64::MAX);
+        assert_eq!(UuidB64::MAX.to_string().parse::<UuidB64>().unwrap(), UuidB64::MAX);
+    }
+
+    #[test]
+    fn orders_by_bytes_not_by_string() {
+        let mut ids: Vec<UuidB64> = (0..1000).map(|_| UuidB64::new()).collect();
+        ids.sort();
+        for pair in ids.windows(2) {
+            assert!(pair[0].uuid().as_bytes() < pair[1].uuid().as_bytes());
+        }
+
+        // 0xf8 is 62 in the first character, `-` or `+`, which is before `A`
+        let mut bytes = [0; 16];
+        bytes[0] = 0xf8;
+        let high = UuidB64(Uuid::from_bytes(&bytes).unwrap());
+        assert!(UuidB64::NIL < high);
+        assert!(UuidB64::NIL.to_string() > high.to_string());
+    }
+
+    #[test]
+    fn pattern_matches_displayed_ids() {
+        // `^[A-Za-z0-9_-]{21}[AQgw]$`, or `+/` for the standard alphabet
+        let symbols = &UuidB64::PATTERN[11..13];
+        let in_alphabet = |c: char| c.is_ascii_alphanumeric() || symbols.contains(c);
+        let mut ids: Vec<UuidB64> = (0..1000).map(|_| UuidB64::new()).collect();
+        ids.push(UuidB64::NIL);
+        ids.push(UuidB64::MAX);
+        for id in ids {
+            let s = id.to_string();
+            assert_eq!(s.len(), UuidB64::ENCODED_LEN);
+            assert!(s.chars().all(|c| UuidB64::ALPHABET.contains(c)), "{}", s);
+            assert!(s[..21].chars().all(in_alphabet), "{}", s);
+            assert!("AQgw".contains(&s[21..]), "{}", s);
+        }
+        assert_eq!(UuidB64::ALPHABET.len(), 64);
+        assert!(symbols.chars().all(|c| UuidB64::ALPHABET[62..].contains(c)));
+    }
+
+    /// Build a v7 UUID by hand with the given millisecond timestamp
+    fn v7_at(millis: u64, rand: u8) -> UuidB64 {
+        let mut bytes = [rand; 16];
+        bytes[..6].copy_from_slice(&millis.to_be_bytes()[2..]);
+        bytes[6] = 0x70 | (bytes[6] & 0x0f);
+        bytes[8] = 0x80 | (bytes[8] & 0x3f);
+        UuidB64(Uuid::from_bytes(&bytes).unwrap())
+    }
+
+    #[test]
+    fn sort_key_matches_ord() {
+        let mut by_ord: Vec<UuidB64> = (0..100).map(|_| UuidB64::new()).collect();
+        let mut by_key = by_ord.clone();
+        by_ord.sort();
+        by_key.sort_by_key(|id| id.sort_key());
+        assert_eq!(by_ord, by_key);
+
+        for pair in by_ord.windows(2) {
+            assert_eq!(pair[0].cmp(&pair[1]), pair[0].sort_key().cmp(&pair[1].sort_key()));
+        }
+    }
+
+    #[test]
+    fn sort_key_is_big_endian_bytes() {
+        let id = UuidB64::from(Uuid::parse_str("b0c1ee86-6f46-4f1b-8d8b-7849e75dbcee").unwrap());
+        assert_eq!(&id.sort_key(), id.uuid().as_bytes());
+        assert_eq!(id.sort_key()[0], 0xb0);
+        assert_eq!(id.sort_key()[15], 0xee);
+    }
+
+    #[test]
+    fn sort_key_orders_v7_by_time() {
+        // later timestamps win even when the random bits are smaller
+        let earlier = v7_at(1_600_000_000_000, 0xff);
+        let later = v7_at(1_600_000_000_001, 0x00);
+        assert!(earlier < later);
+        assert!(earlier.sort_key() < later.sort_key());
+    }
 }
 
 #[cfg(all(test, feature = "diesel-uuid"))]
+#[allow(non_local_definitions)] // diesel 1's derives and `table!`
 mod diesel_tests {
     use diesel;
     use diesel::prelude::*;
@@ -328,6 +1436,20 @@ mod diesel_tests {
         }
     }
 
+    #[derive(Debug, Clone, PartialEq, Identifiable, Insertable, Queryable)]
+    #[table_name = "my_groups"]
+    pub struct MyGroup {
+        id: UuidB64,
+        members: Vec<UuidB64>,
+    }
+
+    table! {
+        my_groups {
+            id -> Uuid,
+            members -> Array<Uuid>,
+        }
+    }
+
     #[cfg(test)]
     fn setup() -> PgConnection {
         let db_url = env::var("PG_DATABASE_URL").expect("PG_DB_URL must be in the environment");
@@ -340,6 +1462,14 @@ mod diesel_tests {
          )",
         );
         setup.execute(&conn).expect("Can't create table");
+        #[allow(deprecated)] // not present in diesel 1.0
+        let setup = sql::<diesel::types::Bool>(
+            "CREATE TABLE IF NOT EXISTS my_groups (
+                id UUID PRIMARY KEY,
+                members UUID[] NOT NULL
+         )",
+        );
+        setup.execute(&conn).expect("Can't create table");
         conn
     }
 
@@ -366,4 +1496,45 @@ mod diesel_tests {
             .execute(&conn)
             .expect("Couldn't delete existing object");
     }
+
+    #[test]
+    fn arrays_roundtrip() {
+        use diesel::dsl::any;
+
+        use self::my_groups::dsl::*;
+
+        let conn = setup();
+
+        let mut groups: Vec<MyGroup> = (0..3)
+            .map(|_| MyGroup {
+                id: UuidB64::new(),
+                members: vec![UuidB64::new(), UuidB64::new()],
+            })
+            .collect();
+        groups.sort_by_key(|g| g.id);
+        let all_ids: Vec<UuidB64> = groups.iter().map(|g| g.id).collect();
+
+        diesel::insert_into(my_groups)
+            .values(&groups)
+            .execute(&conn)
+            .expect("Couldn't insert structs into my_groups");
+
+        let wanted = vec![groups[0].id, groups[2].id];
+        let found: Vec<MyGroup> = my_groups
+            .filter(id.eq(any(&wanted)))
+            .order(id)
+            .load(&conn)
+            .unwrap();
+        assert_eq!(found, vec![groups[0].clone(), groups[2].clone()]);
+
+        let found: Vec<MyGroup> = my_groups
+            .filter(members.contains(vec![groups[1].members[1]]))
+            .load(&conn)
+            .unwrap();
+        assert_eq!(found, vec![groups[1].clone()]);
+
+        diesel::delete(my_groups.filter(id.eq(any(all_ids))))
+            .execute(&conn)
+            .expect("Couldn't delete existing objects");
+    }
 }
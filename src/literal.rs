@@ -0,0 +1,153 @@
+//! IDs written as literals, checked at compile time
+
+use UuidB64;
+
+/// The value of a base64 character in the build's alphabet
+pub(crate) const fn decode_char(c: u8) -> Option<u8> {
+    match c {
+        b'A'..=b'Z' => Some(c - b'A'),
+        b'a'..=b'z' => Some(c - b'a' + 26),
+        b'0'..=b'9' => Some(c - b'0' + 52),
+        #[cfg(not(feature = "standard-alphabet"))]
+        b'-' => Some(62),
+        #[cfg(not(feature = "standard-alphabet"))]
+        b'_' => Some(63),
+        #[cfg(feature = "standard-alphabet")]
+        b'+' => Some(62),
+        #[cfg(feature = "standard-alphabet")]
+        b'/' => Some(63),
+        _ => None,
+    }
+}
+
+impl UuidB64 {
+    /// Parse an ID in a `const` context
+    ///
+    /// This accepts exactly the IDs that `Display` produces, in the build's
+    /// alphabet, and panics on anything else. In a `const` or `static` that
+    /// panic is a compile error, so a typo in a well-known ID breaks the
+    /// build instead of a test. [`uuid_b64!`][] does this for expressions.
+    ///
+    /// ```
+    /// # use uuid_b64::UuidB64;
+    /// const SYSTEM_USER: UuidB64 = UuidB64::from_b64_str("sMHuhm9GTxuNi3hJ51287g");
+    /// assert_eq!(SYSTEM_USER, "sMHuhm9GTxuNi3hJ51287g".parse().unwrap());
+    /// ```
+    ///
+    /// ```compile_fail
+    /// # use uuid_b64::UuidB64;
+    /// const TYPO: UuidB64 = UuidB64::from_b64_str("sMHuhm9GTxuNi3hJ5128.g");
+    /// ```
+    ///
+    /// [`uuid_b64!`]: macro.uuid_b64.html
+    pub const fn from_b64_str(s: &str) -> UuidB64 {
+        let s = s.as_bytes();
+        if s.len() != 22 {
+            panic!("an ID is exactly 22 characters");
+        }
+        let mut bytes = [0u8; 16];
+        let mut acc: u32 = 0;
+        let mut bits = 0;
+        let mut out = 0;
+        let mut i = 0;
+        while i < 22 {
+            let value = match decode_char(s[i]) {
+                Some(value) => value,
+                None => panic!("IDs only contain base64 characters"),
+            };
+            acc = (acc << 6) | value as u32;
+            bits += 6;
+            if bits >= 8 {
+                bits -= 8;
+                bytes[out] = (acc >> bits) as u8;
+                out += 1;
+            }
+            i += 1;
+        }
+        // 22 characters hold 132 bits, the last 4 must be zero
+        if acc & ((1 << bits) - 1) != 0 {
+            panic!("not the canonical encoding of an ID");
+        }
+        UuidB64::from_bytes(bytes)
+    }
+}
+
+/// An ID literal, checked at compile time
+///
+/// Expands to a constant `UuidB64`, so an invalid ID is a compile error even
+/// when the macro is used outside of a `const`.
+///
+/// ```
+/// #[macro_use]
+/// extern crate uuid_b64;
+/// use uuid_b64::UuidB64;
+///
+/// const ADMIN_ROLE: UuidB64 = uuid_b64!("sMHuhm9GTxuNi3hJ51287g");
+///
+/// # fn main() {
+/// let lookup = uuid_b64!("AAAAAQACAAMBAgMEBQYHCA");
+/// assert_ne!(lookup, ADMIN_ROLE);
+/// # }
+/// ```
+///
+/// ```compile_fail
+/// #[macro_use]
+/// extern crate uuid_b64;
+///
+/// # fn main() {
+/// let short = uuid_b64!("sMHuhm9GTxuNi3hJ51287");
+/// # }
+/// ```
+#[macro_export]
+macro_rules! uuid_b64 {
+    ($id:expr) => {{
+        const ID: $crate::UuidB64 = $crate::UuidB64::from_b64_str($id);
+        ID
+    }};
+}
+
+#[cfg(test)]
+mod tests {
+    use std::panic;
+
+    use UuidB64;
+
+    #[test]
+    fn matches_runtime_parsing() {
+        for _ in 0..1000 {
+            let id = UuidB64::new();
+            assert_eq!(UuidB64::from_b64_str(&id.to_string()), id);
+        }
+        let edges = ["AAAAAAAAAAAAAAAAAAAAAA", &UuidB64::MAX.to_string()];
+        for id in edges.iter() {
+            assert_eq!(UuidB64::from_b64_str(id), id.parse().unwrap());
+        }
+    }
+
+    #[test]
+    fn macro_is_const() {
+        const ID: UuidB64 = uuid_b64!("sMHuhm9GTxuNi3hJ51287g");
+        assert_eq!(
+            ID.uuid().hyphenated().to_string(),
+            "b0c1ee86-6f46-4f1b-8d8b-7849e75dbcee"
+        );
+    }
+
+    #[test]
+    fn rejects_bad_input() {
+        for bad in &[
+            "",
+            "sMHuhm9GTxuNi3hJ51287",
+            "sMHuhm9GTxuNi3hJ51287gA",
+            "sMHuhm9GTxuNi3hJ5128.g",
+            "sMHuhm9GTxuNi3hJ51287h",
+            "sMHuhm9GTxuNi3hJ5128=g",
+        ] {
+            assert!(
+                panic::catch_unwind(|| UuidB64::from_b64_str(bad)).is_err(),
+                "{}",
+                bad
+            );
+        }
+    }
+}
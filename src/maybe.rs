@@ -0,0 +1,97 @@
+//! An optional ID whose text form for "no ID" is the empty string
+
+use std::fmt::{Display, Formatter, Result as FmtResult};
+use std::str::FromStr;
+
+use errors::ParseUuidB64Error;
+use UuidB64;
+
+/// An `Option<UuidB64>` that parses `""` as `None` and displays `None` as `""`
+///
+/// CSV files and HTML forms represent "no ID" as an empty value, this makes
+/// ingesting them a plain `parse` (or, with the `serde` feature, a plain
+/// `Deserialize`). Surrounding whitespace is *not* trimmed: `" "` is an error.
+///
+/// ```
+/// # use uuid_b64::{MaybeUuidB64, UuidB64};
+/// let none: MaybeUuidB64 = "".parse().unwrap();
+/// assert_eq!(none.into_option(), None);
+/// assert_eq!(none.to_string(), "");
+///
+/// let some: MaybeUuidB64 = "sMHuhm9GTxuNi3hJ51287g".parse().unwrap();
+/// assert_eq!(some.0, Some("sMHuhm9GTxuNi3hJ51287g".parse::<UuidB64>().unwrap()));
+/// ```
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct MaybeUuidB64(pub Option<UuidB64>);
+
+impl MaybeUuidB64 {
+    /// Unwrap into a plain `Option`
+    pub fn into_option(self) -> Option<UuidB64> {
+        self.0
+    }
+}
+
+impl FromStr for MaybeUuidB64 {
+    type Err = ParseUuidB64Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.is_empty() {
+            Ok(MaybeUuidB64(None))
+        } else {
+            s.parse().map(|id| MaybeUuidB64(Some(id)))
+        }
+    }
+}
+
+impl Display for MaybeUuidB64 {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        match self.0 {
+            Some(ref id) => Display::fmt(id, f),
+            None => Ok(()),
+        }
+    }
+}
+
+impl From<Option<UuidB64>> for MaybeUuidB64 {
+    fn from(id: Option<UuidB64>) -> Self {
+        MaybeUuidB64(id)
+    }
+}
+
+impl From<UuidB64> for MaybeUuidB64 {
+    fn from(id: UuidB64) -> Self {
+        MaybeUuidB64(Some(id))
+    }
+}
+
+impl From<MaybeUuidB64> for Option<UuidB64> {
+    fn from(id: MaybeUuidB64) -> Self {
+        id.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MaybeUuidB64;
+    use UuidB64;
+
+    #[test]
+    fn empty_is_none() {
+        let parsed: MaybeUuidB64 = "".parse().unwrap();
+        assert_eq!(parsed, MaybeUuidB64(None));
+        assert_eq!(parsed.to_string(), "");
+    }
+
+    #[test]
+    fn roundtrips_some() {
+        let id = UuidB64::new();
+        let maybe = MaybeUuidB64::from(id);
+        assert_eq!(maybe.to_string(), id.to_string());
+        assert_eq!(maybe.to_string().parse::<MaybeUuidB64>().unwrap(), maybe);
+    }
+
+    #[test]
+    fn whitespace_is_not_empty() {
+        assert!(" ".parse::<MaybeUuidB64>().is_err());
+    }
+}
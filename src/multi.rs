@@ -0,0 +1,297 @@
+//! Encoding several IDs into a single string, and parsing them back out
+
+use errors::{BulkParseError, ErrorKind, InvalidEntry};
+use UuidB64;
+
+/// Length of a single encoded ID
+const CHUNK_LEN: usize = 22;
+
+impl UuidB64 {
+    /// Encode several IDs back-to-back into a single string
+    ///
+    /// Every ID is exactly 22 characters, so no separator is needed. This is
+    /// handy for compact cache keys and opaque cursor tokens. An empty slice
+    /// encodes to an empty string.
+    ///
+    /// ```
+    /// # use uuid_b64::UuidB64;
+    /// let ids = vec![UuidB64::new(), UuidB64::new()];
+    /// let packed = UuidB64::encode_many(&ids);
+    /// assert_eq!(packed.len(), 44);
+    /// assert_eq!(UuidB64::decode_many(&packed).unwrap(), ids);
+    /// ```
+    pub fn encode_many(ids: &[UuidB64]) -> String {
+        let mut packed = String::new();
+        UuidB64::encode_many_into(ids, &mut packed);
+        packed
+    }
+
+    /// Append several IDs back-to-back to `out`, like
+    /// [`encode_many`](#method.encode_many)
+    ///
+    /// `out` grows once for the whole batch, and can be reused between
+    /// batches to avoid allocating at all.
+    pub fn encode_many_into(ids: &[UuidB64], out: &mut String) {
+        out.reserve(ids.len() * CHUNK_LEN);
+        let mut buf = [0; CHUNK_LEN];
+        for id in ids {
+            out.push_str(id.encode_to(&mut buf));
+        }
+    }
+
+    /// Parse a string produced by [`encode_many`](#method.encode_many)
+    ///
+    /// Fails if the length is not a multiple of 22, or if any chunk is not a
+    /// valid ID, in which case the error includes the index of the first bad
+    /// chunk.
+    pub fn decode_many(s: &str) -> Result<Vec<UuidB64>, ErrorKind> {
+        if !s.len().is_multiple_of(CHUNK_LEN) || !s.is_ascii() {
+            return Err(ErrorKind::ParseError(s.into()));
+        }
+        let mut scratch = Vec::with_capacity(16);
+        s.as_bytes()
+            .chunks(CHUNK_LEN)
+            .enumerate()
+            .map(|(i, chunk)| {
+                // chunks of an ascii string are always valid utf8
+                let chunk = ::std::str::from_utf8(chunk).unwrap();
+                UuidB64::parse_reusing(chunk, &mut scratch)
+                    .map_err(|_| ErrorKind::PackedParseError(i, chunk.into()))
+            })
+            .collect()
+    }
+
+    /// Parse every string in `items` as an ID
+    ///
+    /// Decoding shares one buffer, so the only allocation is the result. If
+    /// any item is invalid the error includes its index.
+    ///
+    /// ```
+    /// # use uuid_b64::UuidB64;
+    /// let ids = UuidB64::decode_all(&["sMHuhm9GTxuNi3hJ51287g", "AAAAAQACAAMBAgMEBQYHCA"]).unwrap();
+    /// assert_eq!(ids.len(), 2);
+    /// ```
+    pub fn decode_all<S: AsRef<str>>(items: &[S]) -> Result<Vec<UuidB64>, ErrorKind> {
+        let mut scratch = Vec::with_capacity(16);
+        items
+            .iter()
+            .enumerate()
+            .map(|(i, item)| {
+                let item = item.as_ref();
+                UuidB64::parse_reusing(item, &mut scratch)
+                    .map_err(|_| ErrorKind::ListParseError(i, item.into()))
+            })
+            .collect()
+    }
+
+    /// Parse every string in `items`, reporting all of the invalid ones
+    ///
+    /// Unlike [`decode_all`](#method.decode_all), this doesn't stop at the
+    /// first bad string, so a batch endpoint can tell the client about every
+    /// bad ID at once.
+    ///
+    /// ```
+    /// # use uuid_b64::UuidB64;
+    /// let items = vec!["nope", "sMHuhm9GTxuNi3hJ51287g", ""];
+    /// let err = UuidB64::parse_many(items).unwrap_err();
+    /// let indexes: Vec<usize> = err.invalid.iter().map(|entry| entry.index).collect();
+    /// assert_eq!(indexes, vec![0, 2]);
+    /// ```
+    pub fn parse_many<'a, I>(items: I) -> Result<Vec<UuidB64>, BulkParseError>
+    where
+        I: IntoIterator<Item = &'a str>,
+    {
+        let mut scratch = Vec::with_capacity(16);
+        let mut ids = Vec::new();
+        let mut invalid = Vec::new();
+        for (index, item) in items.into_iter().enumerate() {
+            match UuidB64::parse_reusing(item, &mut scratch) {
+                Ok(id) => ids.push(id),
+                Err(error) => invalid.push(InvalidEntry {
+                    index,
+                    input: item.into(),
+                    error,
+                }),
+            }
+        }
+        if invalid.is_empty() {
+            Ok(ids)
+        } else {
+            Err(BulkParseError { invalid })
+        }
+    }
+
+    /// Parse newline-delimited IDs, one per line
+    ///
+    /// Lines are handled the same way as by
+    /// [`B64IdReader`](struct.B64IdReader.html): surrounding whitespace and
+    /// blank lines are ignored, and errors carry the 1-based line number.
+    /// This is for text that's already in memory; `B64IdReader` streams it.
+    ///
+    /// ```
+    /// # use uuid_b64::UuidB64;
+    /// let ids = UuidB64::decode_lines("sMHuhm9GTxuNi3hJ51287g\r\nAAAAAQACAAMBAgMEBQYHCA\n").unwrap();
+    /// assert_eq!(ids.len(), 2);
+    /// ```
+    pub fn decode_lines(s: &str) -> Result<Vec<UuidB64>, ErrorKind> {
+        let mut scratch = Vec::with_capacity(16);
+        let mut ids = Vec::with_capacity(s.len() / (CHUNK_LEN + 1));
+        for (i, line) in s.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+            let id = UuidB64::parse_reusing(line, &mut scratch)
+                .map_err(|_| ErrorKind::LineParseError(i + 1, line.into()))?;
+            ids.push(id);
+        }
+        Ok(ids)
+    }
+
+    /// Parse a comma-separated list of IDs, like `?ids=a,b,c`
+    ///
+    /// Whitespace around each item is ignored and an empty string is an empty
+    /// list. If any item is invalid the error includes its index in the list.
+    ///
+    /// ```
+    /// # use uuid_b64::UuidB64;
+    /// let ids = UuidB64::parse_list("sMHuhm9GTxuNi3hJ51287g, AAAAAQACAAMBAgMEBQYHCA").unwrap();
+    /// assert_eq!(ids.len(), 2);
+    /// assert!(UuidB64::parse_list("sMHuhm9GTxuNi3hJ51287g,nope").is_err());
+    /// ```
+    pub fn parse_list(s: &str) -> Result<Vec<UuidB64>, ErrorKind> {
+        UuidB64::parse_list_with(s, ',')
+    }
+
+    /// Like [`parse_list`](#method.parse_list), but with a custom separator
+    pub fn parse_list_with(s: &str, separator: char) -> Result<Vec<UuidB64>, ErrorKind> {
+        if s.trim().is_empty() {
+            return Ok(Vec::new());
+        }
+        s.split(separator)
+            .enumerate()
+            .map(|(i, item)| {
+                let item = item.trim();
+                item.parse()
+                    .map_err(|_| ErrorKind::ListParseError(i, item.into()))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use errors::{ErrorKind, InvalidEntry, ParseUuidB64Error};
+    use UuidB64;
+
+    #[test]
+    fn roundtrips() {
+        let ids: Vec<UuidB64> = (0..5).map(|_| UuidB64::new()).collect();
+        let packed = UuidB64::encode_many(&ids);
+        assert_eq!(packed.len(), 5 * 22);
+        assert_eq!(&packed[22..44], ids[1].to_string());
+        assert_eq!(UuidB64::decode_many(&packed).unwrap(), ids);
+    }
+
+    #[test]
+    fn empty() {
+        assert_eq!(UuidB64::encode_many(&[]), "");
+        assert_eq!(UuidB64::decode_many("").unwrap(), vec![]);
+    }
+
+    #[test]
+    fn rejects_partial_chunks() {
+        let packed = UuidB64::encode_many(&[UuidB64::new()]);
+        assert!(UuidB64::decode_many(&packed[..21]).is_err());
+        assert!(UuidB64::decode_many(&format!("{}x", packed)).is_err());
+    }
+
+    #[test]
+    fn reports_bad_chunk_index() {
+        let mut packed = UuidB64::encode_many(&[UuidB64::new(), UuidB64::new()]);
+        packed.replace_range(30..31, "!");
+        match UuidB64::decode_many(&packed) {
+            Err(ErrorKind::PackedParseError(1, _)) => {}
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn encodes_into_existing_strings() {
+        let ids: Vec<UuidB64> = (0..3).map(|_| UuidB64::new()).collect();
+        let mut out = String::from("ids:");
+        UuidB64::encode_many_into(&ids, &mut out);
+        assert_eq!(out, format!("ids:{}", UuidB64::encode_many(&ids)));
+    }
+
+    #[test]
+    fn decodes_slices_and_lines() {
+        let ids: Vec<UuidB64> = (0..3).map(|_| UuidB64::new()).collect();
+        let strings: Vec<String> = ids.iter().map(|id| id.to_string()).collect();
+        assert_eq!(UuidB64::decode_all(&strings).unwrap(), ids);
+        match UuidB64::decode_all(&[strings[0].as_str(), "nope"]) {
+            Err(ErrorKind::ListParseError(1, ref item)) if item == "nope" => {}
+            other => panic!("unexpected result: {:?}", other),
+        }
+
+        let lines = format!("{}\r\n\n  {}\n{}", ids[0], ids[1], ids[2]);
+        assert_eq!(UuidB64::decode_lines(&lines).unwrap(), ids);
+        match UuidB64::decode_lines(&format!("{}\n\nnope\n", ids[0])) {
+            Err(ErrorKind::LineParseError(3, ref line)) if line == "nope" => {}
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn parses_lists() {
+        let ids: Vec<UuidB64> = (0..3).map(|_| UuidB64::new()).collect();
+        let list = format!("{},{} , {}", ids[0], ids[1], ids[2]);
+        assert_eq!(UuidB64::parse_list(&list).unwrap(), ids);
+        assert_eq!(UuidB64::parse_list("").unwrap(), vec![]);
+
+        let list = format!("{};{}", ids[0], ids[1]);
+        assert_eq!(UuidB64::parse_list_with(&list, ';').unwrap(), &ids[..2]);
+    }
+
+    #[test]
+    fn reports_bad_list_index() {
+        let list = format!("{},,{}", UuidB64::new(), UuidB64::new());
+        match UuidB64::parse_list(&list) {
+            Err(ErrorKind::ListParseError(1, ref item)) if item.is_empty() => {}
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn parse_many_reports_every_failure() {
+        let ids: Vec<UuidB64> = (0..2).map(|_| UuidB64::new()).collect();
+        let strings: Vec<String> = ids.iter().map(|id| id.to_string()).collect();
+        let items = strings.iter().map(|s| s.as_str());
+        assert_eq!(UuidB64::parse_many(items).unwrap(), ids);
+        assert_eq!(UuidB64::parse_many(Vec::new()).unwrap(), vec![]);
+
+        let err =
+            UuidB64::parse_many(vec!["short", &strings[0], "sMHuhm9GTxuNi3hJ5128!g"]).unwrap_err();
+        assert_eq!(
+            err.invalid,
+            vec![
+                InvalidEntry {
+                    index: 0,
+                    input: "short".into(),
+                    error: ParseUuidB64Error::InvalidLength { found: 5 },
+                },
+                InvalidEntry {
+                    index: 2,
+                    input: "sMHuhm9GTxuNi3hJ5128!g".into(),
+                    error: ParseUuidB64Error::InvalidCharacter {
+                        char: '!',
+                        index: 20,
+                    },
+                },
+            ]
+        );
+        assert!(err
+            .to_string()
+            .starts_with("2 invalid IDs: 'short' at index 0 ("));
+    }
+}
@@ -0,0 +1,111 @@
+extern crate mysql_common;
+
+use std::convert::TryFrom;
+use std::str;
+
+use uuid::Uuid;
+
+use self::mysql_common::value::convert::{FromValue, FromValueError};
+use self::mysql_common::value::Value;
+
+use UuidB64;
+
+/// IDs are written as 16 raw bytes, for `BINARY(16)` columns
+impl From<UuidB64> for Value {
+    fn from(id: UuidB64) -> Self {
+        Value::Bytes(id.0.as_bytes().to_vec())
+    }
+}
+
+/// Reads IDs from `BINARY(16)` columns, as well as text columns holding
+/// either a hyphenated UUID or the base64 form
+impl FromValue for UuidB64 {
+    type Intermediate = ParsedUuidB64;
+}
+
+/// `FromValue`'s intermediate result, which keeps the original value so
+/// that it can be rolled back
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParsedUuidB64(UuidB64, Value);
+
+fn parse(bytes: &[u8]) -> Option<UuidB64> {
+    if bytes.len() == 16 {
+        return Uuid::from_bytes(bytes).ok().map(UuidB64);
+    }
+    let s = str::from_utf8(bytes).ok()?;
+    if s.len() == 22 {
+        s.parse().ok()
+    } else {
+        Uuid::parse_str(s).ok().map(UuidB64)
+    }
+}
+
+impl TryFrom<Value> for ParsedUuidB64 {
+    type Error = FromValueError;
+
+    fn try_from(v: Value) -> Result<Self, Self::Error> {
+        let id = match v {
+            Value::Bytes(ref bytes) => parse(bytes),
+            _ => None,
+        };
+        match id {
+            Some(id) => Ok(ParsedUuidB64(id, v)),
+            None => Err(FromValueError(v)),
+        }
+    }
+}
+
+impl From<ParsedUuidB64> for UuidB64 {
+    fn from(parsed: ParsedUuidB64) -> Self {
+        parsed.0
+    }
+}
+
+impl From<ParsedUuidB64> for Value {
+    fn from(parsed: ParsedUuidB64) -> Self {
+        parsed.1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::mysql_common::value::convert::{from_value, from_value_opt};
+    use super::mysql_common::value::Value;
+
+    use UuidB64;
+
+    const ID: &str = "sMHuhm9GTxuNi3hJ51287g";
+
+    #[test]
+    fn binary_roundtrip() {
+        let id = UuidB64::new();
+        let value = Value::from(id);
+        match value {
+            Value::Bytes(ref bytes) => assert_eq!(bytes.len(), 16),
+            ref other => panic!("expected bytes, got {:?}", other),
+        }
+        assert_eq!(from_value::<UuidB64>(value), id);
+    }
+
+    #[test]
+    fn reads_text_columns() {
+        let id: UuidB64 = ID.parse().unwrap();
+        let hyphenated = Value::Bytes(b"b0c1ee86-6f46-4f1b-8d8b-7849e75dbcee".to_vec());
+        assert_eq!(from_value::<UuidB64>(hyphenated), id);
+        let b64 = Value::Bytes(ID.as_bytes().to_vec());
+        assert_eq!(from_value::<UuidB64>(b64), id);
+    }
+
+    #[test]
+    fn rejects_other_values() {
+        let bad = Value::Bytes(b"not an id".to_vec());
+        assert_eq!(from_value_opt::<UuidB64>(bad.clone()).unwrap_err().0, bad);
+        assert!(from_value_opt::<UuidB64>(Value::NULL).is_err());
+        assert!(from_value_opt::<UuidB64>(Value::Int(1)).is_err());
+    }
+
+    #[test]
+    fn option_handles_null() {
+        assert_eq!(from_value::<Option<UuidB64>>(Value::NULL), None);
+    }
+}
@@ -0,0 +1,139 @@
+//! Node.js bindings
+//!
+//! These are registered with Node when the addon is loaded, so a `cdylib`
+//! crate that depends on `uuid-b64` with the `napi` feature is all that's
+//! needed to build a `.node` module exposing:
+//!
+//! ```ts
+//! export function generate(): string
+//! export function encode(uuid: string): string
+//! export function decode(id: string): string
+//! export class UuidB64 {
+//!   constructor()
+//!   static parse(id: string): UuidB64
+//!   static fromUuid(uuid: string): UuidB64
+//!   toString(): string
+//!   toUuid(): string
+//!   equals(other: UuidB64): boolean
+//! }
+//! ```
+//!
+//! `encode` and `decode` convert between the hyphenated UUID format and
+//! ours, so Node services produce exactly the same strings as Rust ones.
+
+extern crate napi;
+extern crate napi_derive;
+
+use uuid::Uuid;
+
+use self::napi::{Error, Result};
+use self::napi_derive::napi;
+
+use UuidB64;
+
+fn to_js_error<E: ::std::fmt::Display>(e: E) -> Error {
+    Error::from_reason(e.to_string())
+}
+
+fn parse_uuid(uuid: &str) -> Result<UuidB64> {
+    Uuid::parse_str(uuid).map(UuidB64::from).map_err(to_js_error)
+}
+
+/// Generate a new random ID
+#[napi]
+pub fn generate() -> String {
+    UuidB64::new().to_string()
+}
+
+/// Convert a hyphenated UUID into an ID
+#[napi]
+pub fn encode(uuid: String) -> Result<String> {
+    parse_uuid(&uuid).map(|id| id.to_string())
+}
+
+/// Convert an ID into a hyphenated UUID
+#[napi]
+pub fn decode(id: String) -> Result<String> {
+    id.parse::<UuidB64>()
+        .map(|id| id.uuid().hyphenated().to_string())
+        .map_err(to_js_error)
+}
+
+/// A parsed ID, for code that wants to validate once and pass it around
+#[napi(js_name = "UuidB64")]
+pub struct JsUuidB64 {
+    inner: UuidB64,
+}
+
+#[napi]
+impl JsUuidB64 {
+    /// Generate a new random ID
+    #[napi(constructor)]
+    pub fn new() -> Self {
+        JsUuidB64 {
+            inner: UuidB64::new(),
+        }
+    }
+
+    /// Parse an ID from its base64 form
+    #[napi(factory)]
+    pub fn parse(id: String) -> Result<Self> {
+        id.parse()
+            .map(|inner| JsUuidB64 { inner })
+            .map_err(to_js_error)
+    }
+
+    /// Parse an ID from a hyphenated UUID
+    #[napi(factory)]
+    pub fn from_uuid(uuid: String) -> Result<Self> {
+        parse_uuid(&uuid).map(|inner| JsUuidB64 { inner })
+    }
+
+    #[napi]
+    #[allow(clippy::inherent_to_string)]
+    pub fn to_string(&self) -> String {
+        self.inner.to_string()
+    }
+
+    #[napi]
+    pub fn to_uuid(&self) -> String {
+        self.inner.uuid().hyphenated().to_string()
+    }
+
+    #[napi]
+    pub fn equals(&self, other: &JsUuidB64) -> bool {
+        self.inner == other.inner
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const UUID: &str = "b0c1ee86-6f46-4f1b-8d8b-7849e75dbcee";
+    const ID: &str = "sMHuhm9GTxuNi3hJ51287g";
+
+    #[test]
+    fn encode_decode() {
+        assert_eq!(encode(UUID.to_owned()).unwrap(), ID);
+        assert_eq!(decode(ID.to_owned()).unwrap(), UUID);
+        let generated = generate();
+        assert_eq!(encode(decode(generated.clone()).unwrap()).unwrap(), generated);
+    }
+
+    #[test]
+    fn errors_are_reported() {
+        let err = decode("nope".to_owned()).unwrap_err();
+        assert!(err.reason.contains("expected 22 characters"), "{}", err.reason);
+        assert!(encode("nope".to_owned()).is_err());
+    }
+
+    #[test]
+    fn class() {
+        let id = JsUuidB64::parse(ID.to_owned()).unwrap();
+        assert_eq!(id.to_string(), ID);
+        assert_eq!(id.to_uuid(), UUID);
+        assert!(id.equals(&JsUuidB64::from_uuid(UUID.to_owned()).unwrap()));
+        assert!(!id.equals(&JsUuidB64::new()));
+    }
+}
@@ -0,0 +1,264 @@
+//! Defining ID newtypes for each kind of entity
+
+/// Define a newtype around `UuidB64` for one kind of entity
+///
+/// The newtype gets everything an ID usually needs: `Display`, `FromStr`,
+/// `Debug`, the comparison traits, conversions to and from `UuidB64` and
+/// `Uuid`, and, when this crate's `serde` feature is enabled, `Serialize` and
+/// `Deserialize`.
+///
+/// ```
+/// #[macro_use]
+/// extern crate uuid_b64;
+///
+/// define_id! {
+///     /// Identifies an order
+///     pub OrderId
+/// }
+///
+/// # fn main() {
+/// let id: OrderId = "sMHuhm9GTxuNi3hJ51287g".parse().unwrap();
+/// assert_eq!(id.to_string(), "sMHuhm9GTxuNi3hJ51287g");
+/// assert_eq!(format!("{:?}", id), "OrderId(sMHuhm9GTxuNi3hJ51287g)");
+/// # }
+/// ```
+///
+/// With a `prefix`, the ID displays, parses and serializes like a
+/// [`PrefixedId`][]:
+///
+/// ```
+/// #[macro_use]
+/// extern crate uuid_b64;
+///
+/// define_id!(pub UserId, prefix = "usr");
+///
+/// # fn main() {
+/// let id: UserId = "usr_sMHuhm9GTxuNi3hJ51287g".parse().unwrap();
+/// assert_eq!(id.to_string(), "usr_sMHuhm9GTxuNi3hJ51287g");
+/// assert!("sMHuhm9GTxuNi3hJ51287g".parse::<UserId>().is_err());
+/// # }
+/// ```
+///
+/// If the types don't need to be newtypes, [`Id`][] and `PrefixedId` do the
+/// same with a marker type and no macro.
+///
+/// [`Id`]: struct.Id.html
+/// [`PrefixedId`]: struct.PrefixedId.html
+#[macro_export]
+macro_rules! define_id {
+    ($(#[$attr:meta])* $vis:vis $name:ident) => {
+        $crate::__define_id_common!($(#[$attr])* $vis $name);
+
+        impl ::std::str::FromStr for $name {
+            type Err = <$crate::UuidB64 as ::std::str::FromStr>::Err;
+
+            fn from_str(s: &str) -> ::std::result::Result<Self, Self::Err> {
+                s.parse().map($name)
+            }
+        }
+
+        impl ::std::fmt::Display for $name {
+            fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+                ::std::fmt::Display::fmt(&self.0, f)
+            }
+        }
+
+        $crate::__define_id_serde!($name);
+    };
+    ($(#[$attr:meta])* $vis:vis $name:ident, prefix = $prefix:expr) => {
+        $crate::__define_id_common!($(#[$attr])* $vis $name);
+
+        impl $crate::IdPrefix for $name {
+            const PREFIX: &'static str = $prefix;
+        }
+
+        impl ::std::str::FromStr for $name {
+            type Err = <$crate::PrefixedId<$name> as ::std::str::FromStr>::Err;
+
+            fn from_str(s: &str) -> ::std::result::Result<Self, Self::Err> {
+                $crate::UuidB64::parse_prefixed($prefix, s).map($name)
+            }
+        }
+
+        impl ::std::fmt::Display for $name {
+            fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+                write!(f, "{}_{}", $prefix, self.0)
+            }
+        }
+
+        $crate::__define_id_serde!($name, prefixed);
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __define_id_common {
+    ($(#[$attr:meta])* $vis:vis $name:ident) => {
+        $(#[$attr])*
+        #[derive(Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+        $vis struct $name($crate::UuidB64);
+
+        #[allow(dead_code)]
+        impl $name {
+            /// Generate a new v4 ID
+            #[allow(clippy::new_without_default)]
+            pub fn new() -> $name {
+                $name($crate::UuidB64::new())
+            }
+
+            /// The underlying `UuidB64`
+            pub fn as_uuid_b64(&self) -> $crate::UuidB64 {
+                self.0
+            }
+
+            /// Copy the raw UUID out
+            pub fn uuid(&self) -> $crate::Uuid {
+                self.0.uuid()
+            }
+        }
+
+        impl ::std::convert::From<$crate::UuidB64> for $name {
+            fn from(id: $crate::UuidB64) -> Self {
+                $name(id)
+            }
+        }
+
+        impl ::std::convert::From<$crate::Uuid> for $name {
+            fn from(id: $crate::Uuid) -> Self {
+                $name($crate::UuidB64::from(id))
+            }
+        }
+
+        // UuidB64's blanket `From<T: Into<Uuid>>` turns this into a
+        // conversion to UuidB64 as well
+        impl ::std::convert::From<$name> for $crate::Uuid {
+            fn from(id: $name) -> Self {
+                id.0.uuid()
+            }
+        }
+
+        impl ::std::fmt::Debug for $name {
+            fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+                write!(f, "{}({})", stringify!($name), self)
+            }
+        }
+    };
+}
+
+// The serde impls are chosen by this crate's features rather than the
+// caller's, so there are two versions of this macro
+
+#[cfg(feature = "serde")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __define_id_serde {
+    ($name:ident) => {
+        impl $crate::__serde::Serialize for $name {
+            fn serialize<S>(&self, serializer: S) -> ::std::result::Result<S::Ok, S::Error>
+            where
+                S: $crate::__serde::Serializer,
+            {
+                $crate::__serde::Serialize::serialize(&self.0, serializer)
+            }
+        }
+
+        impl<'de> $crate::__serde::Deserialize<'de> for $name {
+            fn deserialize<D>(deserializer: D) -> ::std::result::Result<Self, D::Error>
+            where
+                D: $crate::__serde::Deserializer<'de>,
+            {
+                <$crate::UuidB64 as $crate::__serde::Deserialize>::deserialize(deserializer)
+                    .map($name)
+            }
+        }
+    };
+    ($name:ident, prefixed) => {
+        impl $crate::__serde::Serialize for $name {
+            fn serialize<S>(&self, serializer: S) -> ::std::result::Result<S::Ok, S::Error>
+            where
+                S: $crate::__serde::Serializer,
+            {
+                let id = $crate::PrefixedId::<$name>::from(self.0);
+                $crate::__serde::Serialize::serialize(&id, serializer)
+            }
+        }
+
+        impl<'de> $crate::__serde::Deserialize<'de> for $name {
+            fn deserialize<D>(deserializer: D) -> ::std::result::Result<Self, D::Error>
+            where
+                D: $crate::__serde::Deserializer<'de>,
+            {
+                <$crate::PrefixedId<$name> as $crate::__serde::Deserialize>::deserialize(
+                    deserializer,
+                )
+                .map(|id| $name(id.as_uuid_b64()))
+            }
+        }
+    };
+}
+
+#[cfg(not(feature = "serde"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __define_id_serde {
+    ($name:ident $(, $prefixed:ident)*) => {};
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use uuid::Uuid;
+
+    use UuidB64;
+
+    define_id! {
+        /// Identifies an order
+        OrderId
+    }
+
+    define_id!(pub(crate) UserId, prefix = "usr");
+
+    #[test]
+    fn plain() {
+        let id: OrderId = "sMHuhm9GTxuNi3hJ51287g".parse().unwrap();
+        assert_eq!(id.to_string(), "sMHuhm9GTxuNi3hJ51287g");
+        assert_eq!(format!("{:?}", id), "OrderId(sMHuhm9GTxuNi3hJ51287g)");
+        assert!("usr_sMHuhm9GTxuNi3hJ51287g".parse::<OrderId>().is_err());
+    }
+
+    #[test]
+    fn prefixed() {
+        let id: UserId = "usr_sMHuhm9GTxuNi3hJ51287g".parse().unwrap();
+        assert_eq!(id.to_string(), "usr_sMHuhm9GTxuNi3hJ51287g");
+        assert_eq!(format!("{:?}", id), "UserId(usr_sMHuhm9GTxuNi3hJ51287g)");
+        assert!("sMHuhm9GTxuNi3hJ51287g".parse::<UserId>().is_err());
+        assert!("ord_sMHuhm9GTxuNi3hJ51287g".parse::<UserId>().is_err());
+    }
+
+    #[test]
+    fn conversions() {
+        let uuid = Uuid::new_v4();
+        let id = UserId::from(uuid);
+        assert_eq!(id.uuid(), uuid);
+        assert_eq!(Uuid::from(id), uuid);
+        assert_eq!(UuidB64::from(id), id.as_uuid_b64());
+        assert_eq!(OrderId::from(UuidB64::from(uuid)).uuid(), uuid);
+        let set: HashSet<_> = vec![id, id, UserId::new()].into_iter().collect();
+        assert_eq!(set.len(), 2);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn ser_de() {
+        let order = OrderId::new();
+        let json = ::serde_json::to_string(&order).unwrap();
+        assert_eq!(json, format!(r#""{}""#, order));
+        assert_eq!(::serde_json::from_str::<OrderId>(&json).unwrap(), order);
+
+        let user = UserId::from(order.as_uuid_b64());
+        let json = ::serde_json::to_string(&user).unwrap();
+        assert_eq!(json, format!(r#""usr_{}""#, order));
+        assert_eq!(::serde_json::from_str::<UserId>(&json).unwrap(), user);
+    }
+}
@@ -0,0 +1,103 @@
+//! Carrying MongoDB ObjectIds in UUIDs
+
+use UuidB64;
+
+impl UuidB64 {
+    /// Embed a 12-byte MongoDB ObjectId in a v8 UUID
+    ///
+    /// For moving a collection onto UUID-keyed storage without renumbering
+    /// it. The ObjectId's bytes are kept in order, around the version and
+    /// variant bits, and the 26 bits left over are zero:
+    ///
+    /// ```text
+    /// oid[0..6] | 8 0 | oid[6] | 8 0 | oid[7..12] | 00 00
+    /// ```
+    ///
+    /// So IDs made this way sort in the same order as their ObjectIds (by
+    /// creation time, first), and [`object_id`][] gets the ObjectId back.
+    ///
+    /// ```
+    /// # use uuid_b64::UuidB64;
+    /// let oid = [0x65, 0x2f, 0x1e, 0x01, 0xa1, 0xb2, 0xc3, 0xd4, 0xe5, 0xf6, 0x07, 0x18];
+    /// let id = UuidB64::from_object_id(oid);
+    /// assert_eq!(
+    ///     id.uuid().hyphenated().to_string(),
+    ///     "652f1e01-a1b2-80c3-80d4-e5f607180000"
+    /// );
+    /// assert_eq!(id.object_id(), Some(oid));
+    /// ```
+    ///
+    /// [`object_id`]: #method.object_id
+    pub fn from_object_id(oid: [u8; 12]) -> UuidB64 {
+        let mut bytes = [0; 16];
+        bytes[..6].copy_from_slice(&oid[..6]);
+        bytes[6] = 0x80;
+        bytes[7] = oid[6];
+        bytes[8] = 0x80;
+        bytes[9..14].copy_from_slice(&oid[7..]);
+        UuidB64::from_bytes(bytes)
+    }
+
+    /// The ObjectId embedded by [`from_object_id`][], if this ID is one
+    ///
+    /// Any other ID gives `None`, unless it happens to be a v8 UUID with the
+    /// same 26 bits zeroed: a 1 in 67 million chance for a random v8 UUID,
+    /// and impossible for any other version.
+    ///
+    /// [`from_object_id`]: #method.from_object_id
+    pub fn object_id(&self) -> Option<[u8; 12]> {
+        let bytes = self.0.as_bytes();
+        if bytes[6] != 0x80 || bytes[8] != 0x80 || bytes[14..] != [0, 0] {
+            return None;
+        }
+        let mut oid = [0; 12];
+        oid[..6].copy_from_slice(&bytes[..6]);
+        oid[6] = bytes[7];
+        oid[7..].copy_from_slice(&bytes[9..14]);
+        Some(oid)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use UuidB64;
+
+    fn oid(seed: u8) -> [u8; 12] {
+        let mut oid = [0; 12];
+        for (i, b) in oid.iter_mut().enumerate() {
+            *b = seed.wrapping_mul(31).wrapping_add(i as u8 * 17);
+        }
+        oid
+    }
+
+    #[test]
+    fn roundtrips() {
+        for seed in 0..=255 {
+            let oid = oid(seed);
+            let id = UuidB64::from_object_id(oid);
+            assert_eq!(id.uuid().get_version_num(), 8);
+            assert_eq!(id.object_id(), Some(oid));
+        }
+        assert_eq!(
+            UuidB64::from_object_id([0xff; 12]).object_id(),
+            Some([0xff; 12])
+        );
+    }
+
+    #[test]
+    fn keeps_object_id_order() {
+        let mut oids: Vec<_> = (0..=255).map(oid).collect();
+        oids.sort();
+        let ids: Vec<_> = oids
+            .iter()
+            .map(|&oid| UuidB64::from_object_id(oid))
+            .collect();
+        assert!(ids.windows(2).all(|w| w[0] < w[1]));
+    }
+
+    #[test]
+    fn other_ids_have_no_object_id() {
+        assert_eq!(UuidB64::new().object_id(), None);
+        assert_eq!(::V7Generator::new().generate().object_id(), None);
+    }
+}
@@ -0,0 +1,171 @@
+extern crate odbc_api;
+
+use std::ffi::c_void;
+use std::fmt::{Debug, Formatter, Result as FmtResult};
+use std::str;
+
+use uuid::Uuid;
+
+use self::odbc_api::handles::{CData, CDataMut, HasDataType};
+use self::odbc_api::parameter::{CElement, OutputParameter, VarCharArray};
+use self::odbc_api::sys::CDataType;
+use self::odbc_api::{DataType, IntoParameter};
+
+use errors::ErrorKind;
+use {MaybeUuidB64, UuidB64};
+
+/// Hyphenated UUIDs are the one text format that every ODBC source
+/// understands, including MSSQL's `uniqueidentifier` columns
+const HYPHENATED_LEN: usize = 36;
+
+/// Binds as hyphenated UUID text
+impl IntoParameter for UuidB64 {
+    type Parameter = VarCharArray<HYPHENATED_LEN>;
+
+    fn into_parameter(self) -> Self::Parameter {
+        VarCharArray::new(self.0.hyphenated().to_string().as_bytes())
+    }
+}
+
+/// Binds as hyphenated UUID text, or `NULL`
+impl IntoParameter for MaybeUuidB64 {
+    type Parameter = VarCharArray<HYPHENATED_LEN>;
+
+    fn into_parameter(self) -> Self::Parameter {
+        match self.0 {
+            Some(id) => id.into_parameter(),
+            None => VarCharArray::NULL,
+        }
+    }
+}
+
+/// A buffer for reading an ID out of ODBC
+///
+/// Bind it with `Out` or `InOut` to receive an output parameter, or pass it
+/// to `CursorRow::get_data` to fetch a column. Either hyphenated UUIDs or
+/// the base64 form are accepted.
+///
+/// ```rust,ignore
+/// let mut id = OdbcUuidB64::default();
+/// conn.execute("{? = call new_order()}", (Out(&mut id),), None)?;
+/// let id: Option<UuidB64> = id.get().transpose()?;
+/// ```
+pub struct OdbcUuidB64(VarCharArray<{ HYPHENATED_LEN + 1 }>);
+
+impl OdbcUuidB64 {
+    /// The ID that was read, `None` if it was `NULL`
+    pub fn get(&self) -> Option<Result<UuidB64, ErrorKind>> {
+        self.0.as_bytes().map(|bytes| {
+            let s = str::from_utf8(bytes)
+                .map_err(|_| ErrorKind::ParseError(String::from_utf8_lossy(bytes).into_owned()))?;
+            if !self.0.is_complete() {
+                return Err(ErrorKind::ParseError(s.to_owned()));
+            }
+            if s.len() == 22 {
+                s.parse().map_err(ErrorKind::from)
+            } else {
+                Uuid::parse_str(s)
+                    .map(UuidB64)
+                    .map_err(|_| ErrorKind::ParseError(s.to_owned()))
+            }
+        })
+    }
+}
+
+impl Debug for OdbcUuidB64 {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        f.debug_tuple("OdbcUuidB64").field(&self.get()).finish()
+    }
+}
+
+impl Default for OdbcUuidB64 {
+    fn default() -> Self {
+        OdbcUuidB64(VarCharArray::NULL)
+    }
+}
+
+impl From<UuidB64> for OdbcUuidB64 {
+    fn from(id: UuidB64) -> Self {
+        OdbcUuidB64(VarCharArray::new(id.0.hyphenated().to_string().as_bytes()))
+    }
+}
+
+// Everything below just forwards to the wrapped buffer
+
+unsafe impl CData for OdbcUuidB64 {
+    fn cdata_type(&self) -> CDataType {
+        self.0.cdata_type()
+    }
+
+    fn indicator_ptr(&self) -> *const isize {
+        self.0.indicator_ptr()
+    }
+
+    fn value_ptr(&self) -> *const c_void {
+        self.0.value_ptr()
+    }
+
+    fn buffer_length(&self) -> isize {
+        self.0.buffer_length()
+    }
+}
+
+unsafe impl CDataMut for OdbcUuidB64 {
+    fn mut_indicator_ptr(&mut self) -> *mut isize {
+        self.0.mut_indicator_ptr()
+    }
+
+    fn mut_value_ptr(&mut self) -> *mut c_void {
+        self.0.mut_value_ptr()
+    }
+}
+
+unsafe impl CElement for OdbcUuidB64 {
+    fn assert_completness(&self) {
+        self.0.assert_completness()
+    }
+}
+
+impl HasDataType for OdbcUuidB64 {
+    fn data_type(&self) -> DataType {
+        self.0.data_type()
+    }
+}
+
+unsafe impl OutputParameter for OdbcUuidB64 {}
+
+#[cfg(test)]
+mod tests {
+    use super::odbc_api::parameter::VarCharArray;
+    use super::odbc_api::IntoParameter;
+    use super::OdbcUuidB64;
+
+    use {MaybeUuidB64, UuidB64};
+
+    const UUID: &str = "b0c1ee86-6f46-4f1b-8d8b-7849e75dbcee";
+    const ID: &str = "sMHuhm9GTxuNi3hJ51287g";
+
+    #[test]
+    fn binds_as_hyphenated_text() {
+        let id: UuidB64 = ID.parse().unwrap();
+        assert_eq!(id.into_parameter().as_bytes(), Some(UUID.as_bytes()));
+        let maybe = MaybeUuidB64(Some(id));
+        assert_eq!(maybe.into_parameter().as_bytes(), Some(UUID.as_bytes()));
+        assert_eq!(MaybeUuidB64(None).into_parameter().as_bytes(), None);
+    }
+
+    #[test]
+    fn reads_either_format() {
+        let id: UuidB64 = ID.parse().unwrap();
+        assert_eq!(OdbcUuidB64::from(id).get().unwrap().unwrap(), id);
+        let b64 = OdbcUuidB64(VarCharArray::new(ID.as_bytes()));
+        assert_eq!(b64.get().unwrap().unwrap(), id);
+    }
+
+    #[test]
+    fn null_and_garbage() {
+        assert!(OdbcUuidB64::default().get().is_none());
+        let bad = OdbcUuidB64(VarCharArray::new(b"not an id"));
+        assert!(bad.get().unwrap().is_err());
+    }
+}
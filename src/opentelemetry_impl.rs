@@ -0,0 +1,61 @@
+//! Using IDs as OpenTelemetry trace IDs
+//!
+//! A `TraceId` is 16 bytes like a UUID, so a request ID can be propagated
+//! as the trace ID, and the trace ID in a `traceparent` header (32 hex
+//! digits) is then the ID's simple hex form.
+//!
+//! Both v4 and v7 IDs make good trace IDs: W3C Trace Context asks for at
+//! least the rightmost 7 bytes to be random, and they are in both. The nil
+//! ID becomes `TraceId::INVALID`, which isn't propagated.
+
+extern crate opentelemetry;
+
+use self::opentelemetry::TraceId;
+
+use UuidB64;
+
+impl UuidB64 {
+    /// The ID with the same 16 bytes as `trace_id`
+    ///
+    /// This stands in for `From<TraceId>`, which would overlap with the `From`
+    /// impl for everything that converts into a `Uuid`. Trace IDs from other
+    /// sources are usually random bytes rather than UUIDs, so the result
+    /// won't necessarily have a UUID version.
+    pub const fn from_trace_id(trace_id: TraceId) -> UuidB64 {
+        UuidB64::from_bytes(trace_id.to_bytes())
+    }
+
+    /// The trace ID with the same 16 bytes as this ID
+    pub fn to_trace_id(&self) -> TraceId {
+        TraceId::from_bytes(*self.as_bytes())
+    }
+}
+
+impl From<UuidB64> for TraceId {
+    fn from(id: UuidB64) -> TraceId {
+        id.to_trace_id()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::opentelemetry::TraceId;
+
+    use UuidB64;
+
+    #[test]
+    fn same_bytes() {
+        let id: UuidB64 = "sMHuhm9GTxuNi3hJ51287g".parse().unwrap();
+        let trace_id = TraceId::from_hex("b0c1ee866f464f1b8d8b7849e75dbcee").unwrap();
+        assert_eq!(id.to_trace_id(), trace_id);
+        assert_eq!(TraceId::from(id), trace_id);
+        assert_eq!(UuidB64::from_trace_id(trace_id), id);
+        // the form used in `traceparent` headers
+        assert_eq!(trace_id.to_string(), format!("{:x}", id));
+
+        for id in &[UuidB64::new(), UuidB64::new_v7(), UuidB64::MAX] {
+            assert_eq!(UuidB64::from_trace_id(id.to_trace_id()), *id);
+        }
+        assert_eq!(UuidB64::NIL.to_trace_id(), TraceId::INVALID);
+    }
+}
@@ -0,0 +1,326 @@
+//! A binary file format for large sets of IDs
+//!
+//! Packed files are just IDs back-to-back as 16 raw bytes each, optionally
+//! preceded by a 16-byte header: the 8 bytes of [`PACKED_MAGIC`][] and then
+//! the number of IDs as a little-endian `u64`. That's 16 bytes per ID,
+//! rather than 23 for newline-delimited text, and no parsing is needed to
+//! read it back: a packed buffer can be viewed as a `&[UuidB64]` in place.
+//!
+//! [`PACKED_MAGIC`]: constant.PACKED_MAGIC.html
+
+use std::io::{self, Read, Write};
+use std::slice;
+
+use errors::ErrorKind;
+use UuidB64;
+
+/// The first 8 bytes of a packed file with a header
+///
+/// A headerless file is recognized by *not* starting with these bytes. Real
+/// data would need a v3 UUID whose first bytes spell out `UUIDB64` to be
+/// mistaken for a header, which is vanishingly unlikely.
+pub const PACKED_MAGIC: [u8; 8] = *b"UUIDB64\x01";
+
+const HEADER_LEN: usize = 16;
+
+/// View packed bytes as IDs, without copying
+///
+/// `bytes` must be a whole number of IDs.
+fn ids_from_bytes(bytes: &[u8]) -> &[UuidB64] {
+    assert!(bytes.len().is_multiple_of(16));
+    // Safe because UuidB64 is 16 bytes with an alignment of 1 (asserted in
+    // `UuidB64::from_bytes`), and any 16 bytes are a valid UUID.
+    unsafe { slice::from_raw_parts(bytes.as_ptr() as *const UuidB64, bytes.len() / 16) }
+}
+
+/// View IDs as packed bytes, without copying
+fn bytes_from_ids(ids: &[UuidB64]) -> &[u8] {
+    // Safe for the same reasons as `ids_from_bytes`, plus UuidB64 has no
+    // padding.
+    unsafe { slice::from_raw_parts(ids.as_ptr() as *const u8, ids.len() * 16) }
+}
+
+/// Pack IDs into a headerless blob, 16 bytes each
+///
+/// For cache values, message payloads and anywhere else that already knows
+/// how long the data is. Use [`as_packed_bytes`][] to avoid the copy.
+///
+/// [`as_packed_bytes`]: fn.as_packed_bytes.html
+pub fn to_packed_bytes(ids: &[UuidB64]) -> Vec<u8> {
+    bytes_from_ids(ids).to_vec()
+}
+
+/// View IDs as a headerless packed blob, without copying
+pub fn as_packed_bytes(ids: &[UuidB64]) -> &[u8] {
+    bytes_from_ids(ids)
+}
+
+/// View a headerless packed blob as IDs, without copying
+///
+/// Unlike [`read_packed`][] this never looks for a header, so any whole
+/// number of IDs is accepted as-is. `UuidB64` has an alignment of 1, so
+/// there's no need for the data to be aligned.
+///
+/// ```
+/// # use uuid_b64::{from_packed_bytes, to_packed_bytes, UuidB64};
+/// let ids = vec![UuidB64::new(), UuidB64::new()];
+/// let blob = to_packed_bytes(&ids);
+/// assert_eq!(blob.len(), 32);
+/// assert_eq!(from_packed_bytes(&blob).unwrap(), &ids[..]);
+/// assert!(from_packed_bytes(&blob[1..]).is_err());
+/// ```
+///
+/// [`read_packed`]: fn.read_packed.html
+pub fn from_packed_bytes(bytes: &[u8]) -> Result<&[UuidB64], ErrorKind> {
+    if !bytes.len().is_multiple_of(16) {
+        return Err(ErrorKind::InvalidPackedIds(format!(
+            "{} bytes is not a whole number of IDs",
+            bytes.len()
+        )));
+    }
+    Ok(ids_from_bytes(bytes))
+}
+
+/// Write IDs in the packed format, with or without a header
+pub fn write_packed<W: Write>(mut writer: W, ids: &[UuidB64], header: bool) -> io::Result<()> {
+    if header {
+        writer.write_all(&PACKED_MAGIC)?;
+        writer.write_all(&(ids.len() as u64).to_le_bytes())?;
+    }
+    writer.write_all(bytes_from_ids(ids))
+}
+
+/// View a packed buffer, with or without a header, as IDs without copying
+///
+/// Fails if the data isn't a whole number of IDs, or if a header is present
+/// and its count doesn't match the data.
+///
+/// ```
+/// # use uuid_b64::{read_packed, write_packed, UuidB64};
+/// let ids = vec![UuidB64::new(), UuidB64::new()];
+/// let mut buf = Vec::new();
+/// write_packed(&mut buf, &ids, true).unwrap();
+/// assert_eq!(buf.len(), 16 + 2 * 16);
+/// assert_eq!(read_packed(&buf).unwrap(), &ids[..]);
+/// ```
+pub fn read_packed(bytes: &[u8]) -> Result<&[UuidB64], ErrorKind> {
+    let (count, data) = if bytes.starts_with(&PACKED_MAGIC) {
+        if bytes.len() < HEADER_LEN {
+            return Err(ErrorKind::InvalidPackedIds("truncated header".into()));
+        }
+        let mut count = [0; 8];
+        count.copy_from_slice(&bytes[8..HEADER_LEN]);
+        (Some(u64::from_le_bytes(count)), &bytes[HEADER_LEN..])
+    } else {
+        (None, bytes)
+    };
+    if !data.len().is_multiple_of(16) {
+        return Err(ErrorKind::InvalidPackedIds(format!(
+            "{} bytes is not a whole number of IDs",
+            data.len()
+        )));
+    }
+    let ids = ids_from_bytes(data);
+    match count {
+        Some(count) if count != ids.len() as u64 => Err(ErrorKind::InvalidPackedIds(format!(
+            "header says {} IDs but found {}",
+            count,
+            ids.len()
+        ))),
+        _ => Ok(ids),
+    }
+}
+
+/// Stream IDs out of a headerless packed reader, one at a time
+///
+/// This is for packed data that is too big to hold in memory and can't be
+/// memory mapped, like a network stream. A trailing partial ID is an error.
+#[derive(Debug)]
+pub struct PackedIdReader<R> {
+    reader: R,
+}
+
+impl<R: Read> PackedIdReader<R> {
+    /// Read packed IDs from `reader`
+    pub fn new(reader: R) -> PackedIdReader<R> {
+        PackedIdReader { reader }
+    }
+}
+
+impl<R: Read> Iterator for PackedIdReader<R> {
+    type Item = Result<UuidB64, ErrorKind>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut buf = [0; 16];
+        let mut filled = 0;
+        while filled < 16 {
+            match self.reader.read(&mut buf[filled..]) {
+                Ok(0) if filled == 0 => return None,
+                Ok(0) => {
+                    return Some(Err(ErrorKind::InvalidPackedIds(
+                        "trailing partial ID".into(),
+                    )))
+                }
+                Ok(n) => filled += n,
+                Err(ref e) if e.kind() == io::ErrorKind::Interrupted => {}
+                Err(e) => return Some(Err(ErrorKind::Io(e))),
+            }
+        }
+        Some(Ok(ids_from_bytes(&buf)[0]))
+    }
+}
+
+#[cfg(feature = "mmap")]
+pub use self::mapped::MappedIds;
+
+#[cfg(feature = "mmap")]
+mod mapped {
+    extern crate memmap2;
+
+    use std::fs::File;
+    use std::ops::Deref;
+    use std::path::Path;
+
+    use self::memmap2::Mmap;
+
+    use super::read_packed;
+    use errors::ErrorKind;
+    use UuidB64;
+
+    /// A memory-mapped packed file, usable as a `&[UuidB64]`
+    ///
+    /// The file is validated when it is opened, after which reading IDs is
+    /// free: the OS pages them in as they are touched.
+    #[derive(Debug)]
+    pub struct MappedIds {
+        mmap: Mmap,
+        start: usize,
+        len: usize,
+    }
+
+    impl MappedIds {
+        /// Map a packed file, with or without a header
+        ///
+        /// As with any memory map, the file must not be modified while it
+        /// is mapped.
+        pub fn open<P: AsRef<Path>>(path: P) -> Result<MappedIds, ErrorKind> {
+            let file = File::open(path).map_err(ErrorKind::Io)?;
+            // Safe as long as nobody modifies the file while it is mapped,
+            // which is the documented contract of this method.
+            let mmap = unsafe { Mmap::map(&file).map_err(ErrorKind::Io)? };
+            let (start, len) = {
+                let ids = read_packed(&mmap)?;
+                (mmap.len() - ids.len() * 16, ids.len())
+            };
+            Ok(MappedIds { mmap, start, len })
+        }
+    }
+
+    impl Deref for MappedIds {
+        type Target = [UuidB64];
+
+        fn deref(&self) -> &[UuidB64] {
+            let ids = super::ids_from_bytes(&self.mmap[self.start..]);
+            debug_assert_eq!(ids.len(), self.len);
+            ids
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        as_packed_bytes, from_packed_bytes, read_packed, to_packed_bytes, write_packed,
+        PackedIdReader, PACKED_MAGIC,
+    };
+    use UuidB64;
+
+    fn some_ids() -> Vec<UuidB64> {
+        (0..10).map(|_| UuidB64::new()).collect()
+    }
+
+    #[test]
+    fn roundtrips_with_and_without_header() {
+        let ids = some_ids();
+        for &header in &[true, false] {
+            let mut buf = Vec::new();
+            write_packed(&mut buf, &ids, header).unwrap();
+            assert_eq!(buf.starts_with(&PACKED_MAGIC), header);
+            assert_eq!(read_packed(&buf).unwrap(), &ids[..]);
+        }
+    }
+
+    #[test]
+    fn ids_are_raw_bytes() {
+        let ids = some_ids();
+        let mut buf = Vec::new();
+        write_packed(&mut buf, &ids, false).unwrap();
+        assert_eq!(&buf[16..32], ids[1].uuid().as_bytes());
+    }
+
+    #[test]
+    fn rejects_bad_data() {
+        let ids = some_ids();
+        let mut buf = Vec::new();
+        write_packed(&mut buf, &ids, true).unwrap();
+        assert!(read_packed(&buf[..buf.len() - 1]).is_err());
+        assert!(read_packed(&buf[..buf.len() - 16]).is_err());
+        assert!(read_packed(&buf[..12]).is_err());
+    }
+
+    #[test]
+    fn blobs() {
+        let ids = some_ids();
+        let blob = to_packed_bytes(&ids);
+        assert_eq!(&blob[..], as_packed_bytes(&ids));
+        assert_eq!(from_packed_bytes(&blob).unwrap(), &ids[..]);
+        assert_eq!(from_packed_bytes(&[]).unwrap(), &[][..]);
+        assert!(from_packed_bytes(&blob[..17]).is_err());
+
+        // Unaligned views are fine
+        let mut offset = vec![0];
+        offset.extend_from_slice(&blob);
+        assert_eq!(from_packed_bytes(&offset[1..]).unwrap(), &ids[..]);
+    }
+
+    #[test]
+    fn headerless_blobs_are_never_sniffed() {
+        let mut id = [0; 16];
+        id[..8].copy_from_slice(&PACKED_MAGIC);
+        let ids = from_packed_bytes(&id).unwrap();
+        assert_eq!(ids[0].uuid().as_bytes(), &id);
+    }
+
+    #[test]
+    fn streams() {
+        let ids = some_ids();
+        let mut buf = Vec::new();
+        write_packed(&mut buf, &ids, false).unwrap();
+        let read: Vec<UuidB64> = PackedIdReader::new(&buf[..])
+            .map(|id| id.unwrap())
+            .collect();
+        assert_eq!(read, ids);
+
+        let results: Vec<_> = PackedIdReader::new(&buf[..20]).collect();
+        assert!(results[0].is_ok());
+        assert!(results[1].is_err());
+    }
+
+    #[cfg(feature = "mmap")]
+    #[test]
+    fn maps_files() {
+        use std::env;
+        use std::fs::{self, File};
+
+        use super::MappedIds;
+
+        let ids = some_ids();
+        let path = env::temp_dir().join(format!("uuid-b64-{}.ids", UuidB64::new().uuid().simple()));
+        write_packed(File::create(&path).unwrap(), &ids, true).unwrap();
+        {
+            let mapped = MappedIds::open(&path).unwrap();
+            assert_eq!(&mapped[..], &ids[..]);
+        }
+        fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,113 @@
+//! Composite keys made of two IDs
+
+use std::fmt::{Display, Formatter, Result as FmtResult};
+use std::str::FromStr;
+
+use errors::ErrorKind;
+use UuidB64;
+
+/// Two IDs that form a single key, such as a tenant ID plus an entity ID
+///
+/// Displays as the two 22-character encodings back to back (44 characters,
+/// no separator) and parses back from the same. Pairs order by the first ID
+/// and then the second, which matches the ordering of
+/// [`sort_key`](#method.sort_key).
+///
+/// ```
+/// # use uuid_b64::{UuidB64, UuidB64Pair};
+/// let tenant = UuidB64::new();
+/// let entity = UuidB64::new();
+/// let key = UuidB64Pair(tenant, entity);
+///
+/// let token = key.to_string();
+/// assert_eq!(token.len(), 44);
+/// assert_eq!(token.parse::<UuidB64Pair>().unwrap(), key);
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct UuidB64Pair(pub UuidB64, pub UuidB64);
+
+impl UuidB64Pair {
+    /// The 32 raw bytes of both IDs, first ID first
+    ///
+    /// Like [`UuidB64::sort_key`][] these sort identically to the pairs
+    /// themselves, so they can be used as keys in byte-ordered stores where
+    /// all of a tenant's entities will be adjacent.
+    ///
+    /// [`UuidB64::sort_key`]: struct.UuidB64.html#method.sort_key
+    pub fn sort_key(&self) -> [u8; 32] {
+        let mut key = [0; 32];
+        key[..16].copy_from_slice(&self.0.sort_key());
+        key[16..].copy_from_slice(&self.1.sort_key());
+        key
+    }
+}
+
+impl FromStr for UuidB64Pair {
+    type Err = ErrorKind;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.len() != 44 || !s.is_char_boundary(22) {
+            return Err(ErrorKind::ParseError(s.into()));
+        }
+        let (first, second) = s.split_at(22);
+        match (first.parse(), second.parse()) {
+            (Ok(first), Ok(second)) => Ok(UuidB64Pair(first, second)),
+            _ => Err(ErrorKind::ParseError(s.into())),
+        }
+    }
+}
+
+impl Display for UuidB64Pair {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        write!(f, "{}{}", self.0, self.1)
+    }
+}
+
+impl From<(UuidB64, UuidB64)> for UuidB64Pair {
+    fn from((first, second): (UuidB64, UuidB64)) -> Self {
+        UuidB64Pair(first, second)
+    }
+}
+
+impl From<UuidB64Pair> for (UuidB64, UuidB64) {
+    fn from(pair: UuidB64Pair) -> Self {
+        (pair.0, pair.1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::UuidB64Pair;
+    use UuidB64;
+
+    #[test]
+    fn roundtrips() {
+        let pair = UuidB64Pair(UuidB64::new(), UuidB64::new());
+        let token = pair.to_string();
+        assert_eq!(token, format!("{}{}", pair.0, pair.1));
+        assert_eq!(token.parse::<UuidB64Pair>().unwrap(), pair);
+    }
+
+    #[test]
+    fn rejects_bad_lengths() {
+        let token = UuidB64Pair(UuidB64::new(), UuidB64::new()).to_string();
+        assert!(token[..43].parse::<UuidB64Pair>().is_err());
+        assert!(format!("{}A", token).parse::<UuidB64Pair>().is_err());
+        assert!(token[..22].parse::<UuidB64Pair>().is_err());
+    }
+
+    #[test]
+    fn sort_key_matches_ord() {
+        let tenant = UuidB64::new();
+        let mut pairs: Vec<UuidB64Pair> = (0..50)
+            .map(|i| {
+                let first = if i % 2 == 0 { tenant } else { UuidB64::new() };
+                UuidB64Pair(first, UuidB64::new())
+            })
+            .collect();
+        let mut by_key = pairs.clone();
+        pairs.sort();
+        by_key.sort_by_key(|p| p.sort_key());
+        assert_eq!(pairs, by_key);
+    }
+}
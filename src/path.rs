@@ -0,0 +1,127 @@
+//! IDs as file and directory names
+
+use std::ffi::OsStr;
+use std::path::Path;
+
+use encoder::{Alphabet, EncoderConfig, URL_SAFE_ALPHABET};
+use errors::ErrorKind;
+use UuidB64;
+
+/// Whether `c` can appear anywhere in a file name on every common platform
+const fn filename_safe(c: u8) -> bool {
+    c.is_ascii_alphanumeric() || c == b'-' || c == b'_'
+}
+
+// The file name helpers rely on this: no path separators, dots, reserved
+// Windows characters or anything needing quoting in a shell
+const _: () = {
+    let mut i = 0;
+    while i < URL_SAFE_ALPHABET.len() {
+        assert!(filename_safe(URL_SAFE_ALPHABET[i]));
+        i += 1;
+    }
+};
+
+fn file_name_config() -> EncoderConfig {
+    EncoderConfig::new().alphabet(Alphabet::UrlSafe)
+}
+
+impl UuidB64 {
+    /// This ID as a name for a file or directory
+    ///
+    /// Always uses the URL-safe alphabet, which only contains `A-Z`, `a-z`,
+    /// `0-9`, `-` and `_`, even in `standard-alphabet` builds (where `/`
+    /// would be a problem). Use an extension if the name might be passed on
+    /// a command line, since about 1 in 64 IDs start with `-`.
+    ///
+    /// Base64 is case-sensitive, so on case-insensitive file systems (the
+    /// default on macOS and Windows) two IDs that differ only in case share
+    /// a name. That's very unlikely for random IDs (around 1 in 2^66 per pair)
+    /// but not for IDs that are chosen by users.
+    ///
+    /// ```
+    /// # use std::path::Path;
+    /// # use uuid_b64::UuidB64;
+    /// let id = UuidB64::new();
+    /// let path = Path::new("/var/lib/orders").join(id.file_name()).with_extension("json");
+    /// assert_eq!(UuidB64::from_path_stem(&path).unwrap(), id);
+    /// ```
+    pub fn file_name(&self) -> String {
+        self.encode_with(&file_name_config())
+    }
+
+    /// Parse a name made by [`file_name`][]
+    ///
+    /// Fails on names that aren't valid Unicode, rather than guessing at
+    /// them. (This can't be `TryFrom<&OsStr>`, which would overlap with the
+    /// `From` impl for everything that converts into a `Uuid`.)
+    ///
+    /// [`file_name`]: #method.file_name
+    pub fn from_os_str(name: &OsStr) -> Result<UuidB64, ErrorKind> {
+        match name.to_str() {
+            Some(name) => UuidB64::parse_with(name, &file_name_config()),
+            None => Err(ErrorKind::ParseError(name.to_string_lossy().into_owned())),
+        }
+    }
+
+    /// Parse the ID in a path's last component, ignoring any extension
+    pub fn from_path_stem(path: &Path) -> Result<UuidB64, ErrorKind> {
+        match path.file_stem() {
+            Some(stem) => UuidB64::from_os_str(stem),
+            None => Err(ErrorKind::ParseError(path.display().to_string())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ffi::OsStr;
+    use std::path::Path;
+
+    use uuid::Uuid;
+
+    use UuidB64;
+
+    #[test]
+    fn roundtrips() {
+        let id = UuidB64::new();
+        let name = id.file_name();
+        assert_eq!(UuidB64::from_os_str(OsStr::new(&name)).unwrap(), id);
+        assert_eq!(UuidB64::from_path_stem(Path::new(&name)).unwrap(), id);
+        let nested = Path::new("a").join(&name).with_extension("json");
+        assert_eq!(UuidB64::from_path_stem(&nested).unwrap(), id);
+        // Only the last extension is stripped
+        assert!(UuidB64::from_path_stem(&nested.with_extension("json.gz")).is_err());
+    }
+
+    #[test]
+    fn always_url_safe() {
+        let id = UuidB64::from(Uuid::parse_str("fbffbffb-ffbf-fbff-bffb-ffbffbffbfff").unwrap());
+        assert_eq!(id.file_name(), "-_-_-_-_-_-_-_-_-_-__w");
+        assert!(UuidB64::from_os_str(OsStr::new("+/+/+/+/+/+/+/+/+/+//w")).is_err());
+    }
+
+    #[test]
+    fn rejects_bad_names() {
+        assert!(UuidB64::from_path_stem(Path::new("/")).is_err());
+        assert!(UuidB64::from_path_stem(Path::new("notes.txt")).is_err());
+    }
+
+    #[test]
+    fn one_name_per_id() {
+        let name = OsStr::new("sMHuhm9GTxuNi3hJ51287g");
+        let id = UuidB64::from_os_str(name).unwrap();
+        assert_eq!(id.file_name(), "sMHuhm9GTxuNi3hJ51287g");
+        // Same bytes, but not a name `file_name` would produce
+        assert!(UuidB64::from_os_str(OsStr::new("sMHuhm9GTxuNi3hJ51287h")).is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn rejects_non_unicode() {
+        use std::os::unix::ffi::OsStrExt;
+
+        let name = OsStr::from_bytes(b"sMHuhm9GTxuNi3hJ51287\xff");
+        assert!(UuidB64::from_os_str(name).is_err());
+    }
+}
@@ -0,0 +1,74 @@
+//! Support for the `postgres` and `tokio-postgres` crates
+//!
+//! IDs bind to, and are read from, Postgres `uuid` columns. `Vec<UuidB64>`
+//! and `&[UuidB64]` get `uuid[]` from postgres-types' blanket array impls.
+
+extern crate bytes;
+extern crate postgres_types;
+
+use std::error::Error;
+
+use self::bytes::BytesMut;
+use self::postgres_types::{FromSql, IsNull, ToSql, Type};
+use uuid::Uuid;
+
+use UuidB64;
+
+impl ToSql for UuidB64 {
+    fn to_sql(&self, _: &Type, out: &mut BytesMut) -> Result<IsNull, Box<dyn Error + Sync + Send>> {
+        out.extend_from_slice(self.0.as_bytes());
+        Ok(IsNull::No)
+    }
+
+    postgres_types::accepts!(UUID);
+
+    postgres_types::to_sql_checked!();
+}
+
+impl<'a> FromSql<'a> for UuidB64 {
+    fn from_sql(_: &Type, raw: &'a [u8]) -> Result<UuidB64, Box<dyn Error + Sync + Send>> {
+        if raw.len() != 16 {
+            return Err(format!("expected 16 bytes for a UUID, got {}", raw.len()).into());
+        }
+        Ok(UuidB64(Uuid::from_bytes(raw)?))
+    }
+
+    postgres_types::accepts!(UUID);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::bytes::BytesMut;
+    use super::postgres_types::{FromSql, IsNull, ToSql, Type};
+
+    use UuidB64;
+
+    const ID: &str = "sMHuhm9GTxuNi3hJ51287g";
+
+    #[test]
+    fn roundtrips_through_uuid_columns() {
+        let id: UuidB64 = ID.parse().unwrap();
+        let mut buf = BytesMut::new();
+        let is_null = id.to_sql_checked(&Type::UUID, &mut buf).unwrap();
+        assert!(matches!(is_null, IsNull::No));
+        assert_eq!(&buf[..], id.uuid().as_bytes());
+        assert_eq!(UuidB64::from_sql(&Type::UUID, &buf).unwrap(), id);
+    }
+
+    #[test]
+    fn only_accepts_uuids() {
+        let id: UuidB64 = ID.parse().unwrap();
+        assert!(<UuidB64 as ToSql>::accepts(&Type::UUID));
+        assert!(<Vec<UuidB64> as ToSql>::accepts(&Type::UUID_ARRAY));
+        assert!(<UuidB64 as FromSql>::accepts(&Type::UUID));
+        assert!(!<UuidB64 as FromSql>::accepts(&Type::TEXT));
+        assert!(id
+            .to_sql_checked(&Type::TEXT, &mut BytesMut::new())
+            .is_err());
+    }
+
+    #[test]
+    fn rejects_wrong_lengths() {
+        assert!(UuidB64::from_sql(&Type::UUID, &[1, 2]).is_err());
+    }
+}
@@ -0,0 +1,264 @@
+//! Stripe-style IDs that say what they identify, like `usr_sMHuhm9GTxuNi3hJ51287g`
+
+use std::cmp::Ordering;
+use std::fmt::{Debug, Display, Formatter, Result as FmtResult};
+use std::hash::{Hash, Hasher};
+use std::str::FromStr;
+
+use uuid::Uuid;
+
+use errors::ErrorKind;
+use typed::Id;
+use UuidB64;
+
+/// The prefix that a kind of thing's IDs are written with
+///
+/// Implemented on the marker types used with [`Id`][], which makes
+/// [`PrefixedId`][] available for them.
+///
+/// [`Id`]: struct.Id.html
+/// [`PrefixedId`]: struct.PrefixedId.html
+pub trait IdPrefix {
+    /// Written before the ID, separated from it by an underscore
+    const PREFIX: &'static str;
+}
+
+/// An [`Id`][] that displays, parses and serializes with its type's prefix
+///
+/// ```
+/// # use uuid_b64::{IdPrefix, PrefixedId};
+/// struct User;
+///
+/// impl IdPrefix for User {
+///     const PREFIX: &'static str = "usr";
+/// }
+///
+/// let id: PrefixedId<User> = "usr_sMHuhm9GTxuNi3hJ51287g".parse().unwrap();
+/// assert_eq!(id.to_string(), "usr_sMHuhm9GTxuNi3hJ51287g");
+/// assert_eq!(id.id().to_string(), "sMHuhm9GTxuNi3hJ51287g");
+///
+/// assert!("acct_sMHuhm9GTxuNi3hJ51287g".parse::<PrefixedId<User>>().is_err());
+/// assert!("sMHuhm9GTxuNi3hJ51287g".parse::<PrefixedId<User>>().is_err());
+/// ```
+///
+/// Parsing finds the ID by its length rather than by the separator, so it
+/// doesn't matter that the URL-safe alphabet contains `_` too.
+pub struct PrefixedId<T>(Id<T>);
+
+impl<T: IdPrefix> PrefixedId<T> {
+    /// Generate a new v4 ID
+    #[allow(clippy::new_without_default)]
+    pub fn new() -> PrefixedId<T> {
+        PrefixedId(Id::new())
+    }
+
+    /// The ID without its prefix
+    pub fn id(&self) -> Id<T> {
+        self.0
+    }
+
+    /// The untyped ID
+    pub fn as_uuid_b64(&self) -> UuidB64 {
+        self.0.as_uuid_b64()
+    }
+
+    /// Copy the raw UUID out
+    pub fn uuid(&self) -> Uuid {
+        self.0.uuid()
+    }
+}
+
+impl UuidB64 {
+    /// Write this ID after `prefix` and an underscore
+    ///
+    /// For prefixes that are only known at runtime; [`PrefixedId`][] is
+    /// usually more convenient.
+    ///
+    /// ```
+    /// # use uuid_b64::UuidB64;
+    /// let id: UuidB64 = "sMHuhm9GTxuNi3hJ51287g".parse().unwrap();
+    /// assert_eq!(id.to_prefixed("usr"), "usr_sMHuhm9GTxuNi3hJ51287g");
+    /// assert_eq!(UuidB64::parse_prefixed("usr", "usr_sMHuhm9GTxuNi3hJ51287g").unwrap(), id);
+    /// ```
+    ///
+    /// [`PrefixedId`]: struct.PrefixedId.html
+    pub fn to_prefixed(&self, prefix: &str) -> String {
+        format!("{}_{}", prefix, self)
+    }
+
+    /// Parse an ID written by [`to_prefixed`][], requiring that its prefix is
+    /// `prefix`
+    ///
+    /// [`to_prefixed`]: #method.to_prefixed
+    pub fn parse_prefixed(prefix: &str, s: &str) -> Result<UuidB64, ErrorKind> {
+        // The ID is always the last 22 characters, and can contain `_` itself
+        let split = match s.len().checked_sub(23) {
+            Some(split) if s.is_char_boundary(split) && s[split..].starts_with('_') => split,
+            _ => return Err(ErrorKind::ParseError(s.into())),
+        };
+        let id = s[split + 1..].parse()?;
+        if &s[..split] != prefix {
+            return Err(ErrorKind::PrefixMismatch(prefix.into(), s[..split].into()));
+        }
+        Ok(id)
+    }
+}
+
+// These are written out by hand because deriving them would require `T` to
+// implement them too
+
+impl<T> Clone for PrefixedId<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for PrefixedId<T> {}
+
+impl<T> PartialEq for PrefixedId<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl<T> Eq for PrefixedId<T> {}
+
+impl<T> PartialOrd for PrefixedId<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T> Ord for PrefixedId<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.cmp(&other.0)
+    }
+}
+
+impl<T> Hash for PrefixedId<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.hash(state)
+    }
+}
+
+impl<T> From<Id<T>> for PrefixedId<T> {
+    fn from(id: Id<T>) -> Self {
+        PrefixedId(id)
+    }
+}
+
+impl<T> From<PrefixedId<T>> for Id<T> {
+    fn from(id: PrefixedId<T>) -> Self {
+        id.0
+    }
+}
+
+impl<T> From<UuidB64> for PrefixedId<T> {
+    fn from(id: UuidB64) -> Self {
+        PrefixedId(Id::from_uuid_b64(id))
+    }
+}
+
+// UuidB64's blanket `From<T: Into<Uuid>>` turns this into a conversion to
+// UuidB64 as well
+impl<T> From<PrefixedId<T>> for Uuid {
+    fn from(id: PrefixedId<T>) -> Self {
+        id.0.uuid()
+    }
+}
+
+impl<T: IdPrefix> FromStr for PrefixedId<T> {
+    type Err = ErrorKind;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        UuidB64::parse_prefixed(T::PREFIX, s).map(PrefixedId::from)
+    }
+}
+
+impl<T: IdPrefix> Display for PrefixedId<T> {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        write!(f, "{}_{}", T::PREFIX, self.0)
+    }
+}
+
+impl<T: IdPrefix> Debug for PrefixedId<T> {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        write!(f, "PrefixedId({})", self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use errors::ErrorKind;
+    use {IdPrefix, PrefixedId, UuidB64};
+
+    struct User;
+
+    impl IdPrefix for User {
+        const PREFIX: &'static str = "usr";
+    }
+
+    struct Empty;
+
+    impl IdPrefix for Empty {
+        const PREFIX: &'static str = "";
+    }
+
+    #[test]
+    fn roundtrips() {
+        for _ in 0..1000 {
+            let id = PrefixedId::<User>::new();
+            let s = id.to_string();
+            assert!(s.starts_with("usr_"));
+            assert_eq!(s.len(), 26);
+            assert_eq!(s.parse::<PrefixedId<User>>().unwrap(), id);
+        }
+        let id = PrefixedId::<Empty>::new();
+        assert_eq!(id.to_string().parse::<PrefixedId<Empty>>().unwrap(), id);
+    }
+
+    #[test]
+    fn formats() {
+        let id: PrefixedId<User> = "usr_sMHuhm9GTxuNi3hJ51287g".parse().unwrap();
+        assert_eq!(
+            id.uuid().hyphenated().to_string(),
+            "b0c1ee86-6f46-4f1b-8d8b-7849e75dbcee"
+        );
+        assert_eq!(
+            format!("{:?}", id),
+            "PrefixedId(usr_sMHuhm9GTxuNi3hJ51287g)"
+        );
+    }
+
+    #[test]
+    fn rejects_wrong_prefix() {
+        match "acct_sMHuhm9GTxuNi3hJ51287g".parse::<PrefixedId<User>>() {
+            Err(ErrorKind::PrefixMismatch(ref expected, ref found))
+                if expected == "usr" && found == "acct" => {}
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn rejects_garbage() {
+        for bad in &[
+            "",
+            "usr_",
+            "sMHuhm9GTxuNi3hJ51287g",
+            "usrsMHuhm9GTxuNi3hJ51287g",
+            "usr_sMHuhm9GTxuNi3hJ51287",
+            "usr_sMHuhm9GTxuNi3hJ5128.g",
+            "usé_sMHuhm9GTxuNi3hJ5128g",
+        ] {
+            assert!(bad.parse::<PrefixedId<User>>().is_err(), "{} parsed", bad);
+        }
+    }
+
+    #[test]
+    fn untyped() {
+        let id = UuidB64::new();
+        let s = id.to_prefixed("ns_usr");
+        assert_eq!(UuidB64::parse_prefixed("ns_usr", &s).unwrap(), id);
+        assert!(UuidB64::parse_prefixed("usr", &s).is_err());
+    }
+}
@@ -0,0 +1,19 @@
+//! Everything you need for day-to-day use, in one import
+//!
+//! ```
+//! use uuid_b64::prelude::*;
+//!
+//! let uuid = Uuid::new_v4();
+//! let id = UuidB64::from(uuid);
+//! assert_eq!(id.uuid(), uuid);
+//! ```
+//!
+//! `Uuid` is the same type that `UuidB64` wraps, so there's no need to keep
+//! a separate `uuid` dependency's version in step with this crate's.
+
+pub use maybe::MaybeUuidB64;
+pub use prefixed::{IdPrefix, PrefixedId};
+pub use typed::Id;
+pub use uuid::Uuid;
+pub use versioned::{UuidB64V4, UuidB64V7};
+pub use UuidB64;
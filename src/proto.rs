@@ -0,0 +1,142 @@
+//! Conversions for protobuf fields, as generated by prost
+//!
+//! Protobuf has no UUID type, so IDs travel in either a `bytes` field, as
+//! their 16 raw bytes, or a `string` field, in Base64. `bytes_field` and
+//! `string_field` convert both ways, treating the empty value (what proto3
+//! sends for an unset field) as missing:
+//!
+//! ```
+//! # extern crate uuid_b64;
+//! # use uuid_b64::{proto, UuidB64};
+//! // message User { bytes id = 1; string parent_id = 2; }
+//! struct User {
+//!     id: Vec<u8>,
+//!     parent_id: String,
+//! }
+//!
+//! # fn main() {
+//! let id = UuidB64::new();
+//! let user = User {
+//!     id: proto::bytes_field::encode(&id),
+//!     parent_id: proto::string_field::encode_optional(None),
+//! };
+//! assert_eq!(proto::bytes_field::decode(&user.id).unwrap(), id);
+//! assert_eq!(proto::string_field::decode_optional(&user.parent_id).unwrap(), None);
+//! # }
+//! ```
+
+extern crate bytes;
+
+use self::bytes::Bytes;
+use UuidB64;
+
+impl From<UuidB64> for Vec<u8> {
+    fn from(id: UuidB64) -> Vec<u8> {
+        id.0.as_bytes().to_vec()
+    }
+}
+
+impl From<UuidB64> for Bytes {
+    fn from(id: UuidB64) -> Bytes {
+        Bytes::copy_from_slice(id.0.as_bytes())
+    }
+}
+
+/// IDs in `bytes` fields
+///
+/// Works with both `Vec<u8>` and, with prost's `bytes` option, `Bytes`
+/// fields.
+pub mod bytes_field {
+    use errors::ErrorKind;
+    use UuidB64;
+
+    /// The field value for `id`
+    pub fn encode<T: From<UuidB64>>(id: &UuidB64) -> T {
+        T::from(*id)
+    }
+
+    /// The field value for `id`, empty for `None`
+    pub fn encode_optional<T: From<UuidB64> + Default>(id: Option<UuidB64>) -> T {
+        id.map(T::from).unwrap_or_default()
+    }
+
+    /// The ID in a required field, which must hold exactly 16 bytes
+    pub fn decode(field: &[u8]) -> Result<UuidB64, ErrorKind> {
+        UuidB64::from_slice(field)
+    }
+
+    /// The ID in an optional field, `None` if it's empty
+    pub fn decode_optional(field: &[u8]) -> Result<Option<UuidB64>, ErrorKind> {
+        if field.is_empty() {
+            Ok(None)
+        } else {
+            decode(field).map(Some)
+        }
+    }
+}
+
+/// IDs in `string` fields
+pub mod string_field {
+    use errors::ErrorKind;
+    use UuidB64;
+
+    /// The field value for `id`
+    pub fn encode(id: &UuidB64) -> String {
+        id.to_string()
+    }
+
+    /// The field value for `id`, empty for `None`
+    pub fn encode_optional(id: Option<UuidB64>) -> String {
+        id.as_ref().map(encode).unwrap_or_default()
+    }
+
+    /// The ID in a required field
+    pub fn decode(field: &str) -> Result<UuidB64, ErrorKind> {
+        Ok(field.parse()?)
+    }
+
+    /// The ID in an optional field, `None` if it's empty
+    pub fn decode_optional(field: &str) -> Result<Option<UuidB64>, ErrorKind> {
+        if field.is_empty() {
+            Ok(None)
+        } else {
+            decode(field).map(Some)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::bytes::Bytes;
+    use super::{bytes_field, string_field};
+
+    use UuidB64;
+
+    #[test]
+    fn bytes_fields() {
+        let id = UuidB64::new();
+        let field: Vec<u8> = bytes_field::encode(&id);
+        assert_eq!(&field[..], id.uuid().as_bytes());
+        assert_eq!(bytes_field::decode(&field).unwrap(), id);
+        let field: Bytes = bytes_field::encode(&id);
+        assert_eq!(bytes_field::decode(&field).unwrap(), id);
+
+        let empty: Vec<u8> = bytes_field::encode_optional(None);
+        assert!(empty.is_empty());
+        assert_eq!(bytes_field::decode_optional(&empty).unwrap(), None);
+        assert!(bytes_field::decode(&empty).is_err());
+        assert!(bytes_field::decode(&field[..15]).is_err());
+        assert_eq!(bytes_field::decode_optional(&field).unwrap(), Some(id));
+    }
+
+    #[test]
+    fn string_fields() {
+        let id: UuidB64 = "sMHuhm9GTxuNi3hJ51287g".parse().unwrap();
+        assert_eq!(string_field::encode(&id), "sMHuhm9GTxuNi3hJ51287g");
+        assert_eq!(string_field::decode("sMHuhm9GTxuNi3hJ51287g").unwrap(), id);
+        assert_eq!(string_field::encode_optional(None), "");
+        assert_eq!(string_field::decode_optional("").unwrap(), None);
+        assert!(string_field::decode("").is_err());
+        assert!(string_field::decode_optional("nope").is_err());
+    }
+}
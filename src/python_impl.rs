@@ -0,0 +1,139 @@
+//! Python bindings
+//!
+//! The `uuid_b64` module is exported for Python to load, so a `cdylib` crate
+//! that depends on `uuid-b64` with the `python` feature (and pyo3's
+//! `extension-module`) is all that's needed to build an extension exposing:
+//!
+//! ```python
+//! def generate() -> str: ...
+//! def encode(uuid: str) -> str: ...
+//! def decode(id: str) -> str: ...
+//! def from_uuid(uuid: uuid.UUID) -> str: ...
+//! def to_uuid(id: str) -> uuid.UUID: ...
+//! ```
+//!
+//! `encode` and `decode` convert between the hyphenated UUID format and
+//! ours, so Python code produces exactly the same unpadded, url-safe strings
+//! as Rust.
+
+extern crate pyo3;
+
+use std::fmt::Display;
+
+use uuid::Uuid;
+
+use self::pyo3::exceptions::PyValueError;
+use self::pyo3::prelude::*;
+use self::pyo3::types::{PyBytes, PyDict};
+
+use UuidB64;
+
+fn to_py_error<E: Display>(e: E) -> PyErr {
+    PyValueError::new_err(e.to_string())
+}
+
+/// Generate a new random ID
+#[pyfunction]
+fn generate() -> String {
+    UuidB64::new().to_string()
+}
+
+/// Convert a hyphenated UUID into an ID
+#[pyfunction]
+fn encode(uuid: &str) -> PyResult<String> {
+    Uuid::parse_str(uuid)
+        .map(|uuid| UuidB64::from(uuid).to_string())
+        .map_err(to_py_error)
+}
+
+/// Convert an ID into a hyphenated UUID
+#[pyfunction]
+fn decode(id: &str) -> PyResult<String> {
+    id.parse::<UuidB64>()
+        .map(|id| id.uuid().hyphenated().to_string())
+        .map_err(to_py_error)
+}
+
+/// Convert a `uuid.UUID` into an ID
+#[pyfunction]
+fn from_uuid(uuid: &Bound<PyAny>) -> PyResult<String> {
+    let bytes: Bound<PyBytes> = uuid.getattr("bytes")?.extract()?;
+    UuidB64::from_slice(bytes.as_bytes())
+        .map(|id| id.to_string())
+        .map_err(to_py_error)
+}
+
+/// Convert an ID into a `uuid.UUID`
+#[pyfunction]
+fn to_uuid<'py>(py: Python<'py>, id: &str) -> PyResult<Bound<'py, PyAny>> {
+    let id: UuidB64 = id.parse().map_err(to_py_error)?;
+    let kwargs = PyDict::new(py);
+    kwargs.set_item("bytes", PyBytes::new(py, id.uuid().as_bytes()))?;
+    py.import("uuid")?.getattr("UUID")?.call((), Some(&kwargs))
+}
+
+#[pymodule]
+fn uuid_b64(m: &Bound<PyModule>) -> PyResult<()> {
+    m.add_function(wrap_pyfunction!(self::generate, m)?)?;
+    m.add_function(wrap_pyfunction!(self::encode, m)?)?;
+    m.add_function(wrap_pyfunction!(self::decode, m)?)?;
+    m.add_function(wrap_pyfunction!(self::from_uuid, m)?)?;
+    m.add_function(wrap_pyfunction!(self::to_uuid, m)?)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::pyo3::prelude::*;
+    use super::pyo3::types::PyDict;
+
+    use super::*;
+
+    const UUID: &str = "b0c1ee86-6f46-4f1b-8d8b-7849e75dbcee";
+    const ID: &str = "sMHuhm9GTxuNi3hJ51287g";
+
+    /// Run `code` with the module imported as `uuid_b64`
+    fn run(code: &str) {
+        Python::initialize();
+        Python::attach(|py| {
+            let module = PyModule::new(py, "uuid_b64").unwrap();
+            uuid_b64(&module).unwrap();
+            let globals = PyDict::new(py);
+            globals.set_item("uuid_b64", module).unwrap();
+            let code = ::std::ffi::CString::new(code).unwrap();
+            py.run(&code, Some(&globals), None).unwrap();
+        });
+    }
+
+    #[test]
+    fn encode_decode() {
+        assert_eq!(encode(UUID).unwrap(), ID);
+        assert_eq!(decode(ID).unwrap(), UUID);
+        let generated = generate();
+        assert_eq!(encode(&decode(&generated).unwrap()).unwrap(), generated);
+    }
+
+    #[test]
+    fn from_python() {
+        run(&format!(
+            r#"
+import uuid
+u = uuid.UUID("{uuid}")
+assert uuid_b64.from_uuid(u) == "{id}"
+assert uuid_b64.to_uuid("{id}") == u
+assert uuid_b64.encode("{uuid}") == "{id}"
+assert uuid_b64.decode("{id}") == "{uuid}"
+new = uuid_b64.generate()
+assert len(new) == 22 and "=" not in new
+assert uuid_b64.to_uuid(new).version == 4
+try:
+    uuid_b64.decode("nope")
+    assert False
+except ValueError as e:
+    assert "expected 22 characters" in str(e)
+"#,
+            uuid = UUID,
+            id = ID
+        ));
+    }
+}
@@ -0,0 +1,70 @@
+//! quickcheck generation
+
+extern crate quickcheck;
+
+use std::iter;
+
+use self::quickcheck::{Arbitrary, Gen};
+use uuid::UuidVariant;
+
+use {UuidB64, UuidB64Builder};
+
+/// Weighted toward the edge cases, as with `testing::any_id`: nil, max, and
+/// an RFC 4122 ID of each version from 1 to 8, as well as any 16 bytes
+impl Arbitrary for UuidB64 {
+    fn arbitrary(g: &mut Gen) -> UuidB64 {
+        let bytes = <[u8; 16]>::arbitrary(g);
+        let choices: Vec<u8> = (0..12).collect();
+        match *g.choose(&choices).expect("not empty") {
+            0 => UuidB64::NIL,
+            1 => UuidB64::MAX,
+            2 | 3 => UuidB64::from_bytes(bytes),
+            n => UuidB64Builder::from_bytes(bytes)
+                .with_version(n - 3)
+                .with_variant(UuidVariant::RFC4122)
+                .into_uuid_b64(),
+        }
+    }
+
+    fn shrink(&self) -> Box<dyn Iterator<Item = UuidB64>> {
+        if self.is_nil() {
+            Box::new(iter::empty())
+        } else {
+            Box::new(iter::once(UuidB64::NIL))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::quickcheck::{quickcheck, Arbitrary, Gen};
+
+    use UuidB64;
+
+    #[test]
+    fn covers_the_edge_cases() {
+        let mut g = Gen::new(16);
+        let ids: Vec<UuidB64> = (0..1000).map(|_| UuidB64::arbitrary(&mut g)).collect();
+        assert!(ids.contains(&UuidB64::NIL));
+        assert!(ids.contains(&UuidB64::MAX));
+        let versions: HashSet<_> = ids.iter().map(|id| id.uuid().get_version_num()).collect();
+        for version in 1..=8 {
+            assert!(versions.contains(&version), "no v{}", version);
+        }
+        assert_eq!(
+            UuidB64::MAX.shrink().collect::<Vec<_>>(),
+            vec![UuidB64::NIL]
+        );
+        assert_eq!(UuidB64::NIL.shrink().count(), 0);
+    }
+
+    #[test]
+    fn roundtrips() {
+        fn prop(id: UuidB64) -> bool {
+            id.to_string().parse::<UuidB64>() == Ok(id)
+        }
+        quickcheck(prop as fn(UuidB64) -> bool);
+    }
+}
@@ -0,0 +1,106 @@
+//! Generating IDs from a caller-supplied random number generator
+
+extern crate rand09;
+extern crate rand_core;
+
+use self::rand09::distr::{Distribution, StandardUniform};
+use self::rand09::Rng;
+use self::rand_core::RngCore;
+use uuid::Uuid;
+
+use UuidB64;
+
+impl UuidB64 {
+    /// Generate a new v4 UUID from `rng`, instead of the generator
+    /// [`new`](#method.new) uses
+    ///
+    /// For a per-thread generator, or a deterministic one in tests. The
+    /// version and variant bits are set as usual, so the other 122 bits come
+    /// from `rng`.
+    ///
+    /// ```rust,ignore
+    /// let mut rng = ChaCha20Rng::seed_from_u64(7);
+    /// let id = UuidB64::new_with_rng(&mut rng);
+    /// assert_eq!(id, UuidB64::new_with_rng(&mut ChaCha20Rng::seed_from_u64(7)));
+    /// ```
+    pub fn new_with_rng<R: RngCore + ?Sized>(rng: &mut R) -> UuidB64 {
+        let mut bytes = [0; 16];
+        rng.fill_bytes(&mut bytes);
+        UuidB64(Uuid::from_random_bytes(bytes))
+    }
+}
+
+/// `rng.random::<UuidB64>()` makes a v4 ID, the same as
+/// [`UuidB64::new_with_rng`](../struct.UuidB64.html#method.new_with_rng)
+///
+/// So `UuidB64` works in code that's generic over `T` where
+/// `StandardUniform: Distribution<T>`.
+impl Distribution<UuidB64> for StandardUniform {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> UuidB64 {
+        UuidB64::new_with_rng(rng)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::rand09::distr::{Distribution, StandardUniform};
+    use super::rand09::Rng;
+    use super::rand_core::RngCore;
+    use uuid::{UuidVariant, UuidVersion};
+
+    use UuidB64;
+
+    /// Counts up from the byte it starts at
+    struct Counter(u8);
+
+    impl RngCore for Counter {
+        fn next_u32(&mut self) -> u32 {
+            let mut bytes = [0; 4];
+            self.fill_bytes(&mut bytes);
+            u32::from_le_bytes(bytes)
+        }
+
+        fn next_u64(&mut self) -> u64 {
+            let mut bytes = [0; 8];
+            self.fill_bytes(&mut bytes);
+            u64::from_le_bytes(bytes)
+        }
+
+        fn fill_bytes(&mut self, dest: &mut [u8]) {
+            for byte in dest {
+                *byte = self.0;
+                self.0 = self.0.wrapping_add(1);
+            }
+        }
+    }
+
+    #[test]
+    fn uses_the_rng() {
+        let id = UuidB64::new_with_rng(&mut Counter(0));
+        assert_eq!(
+            id.uuid().hyphenated().to_string(),
+            "00010203-0405-4607-8809-0a0b0c0d0e0f"
+        );
+        assert_eq!(id.uuid().get_version(), Some(UuidVersion::Random));
+        assert_eq!(id.uuid().get_variant(), Some(UuidVariant::RFC4122));
+
+        let mut rng = Counter(0);
+        assert_ne!(
+            UuidB64::new_with_rng(&mut rng),
+            UuidB64::new_with_rng(&mut rng)
+        );
+        let dyn_rng: &mut dyn RngCore = &mut Counter(0);
+        assert_eq!(UuidB64::new_with_rng(dyn_rng), id);
+    }
+
+    #[test]
+    fn samples_from_the_standard_distribution() {
+        let id: UuidB64 = Counter(0).random();
+        assert_eq!(id, UuidB64::new_with_rng(&mut Counter(0)));
+        assert_eq!(id.uuid().get_version(), Some(UuidVersion::Random));
+
+        let ids: Vec<UuidB64> = StandardUniform.sample_iter(Counter(0)).take(2).collect();
+        assert_eq!(ids[0], id);
+        assert_ne!(ids[0], ids[1]);
+    }
+}
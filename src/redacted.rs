@@ -0,0 +1,114 @@
+//! An ID whose `Debug` output is cut short, for structs that get logged
+
+use std::fmt::{Debug, Display, Formatter, Result as FmtResult};
+use std::ops::Deref;
+use std::str::FromStr;
+
+use errors::ParseUuidB64Error;
+use UuidB64;
+
+/// How many characters of the ID `Debug` shows
+const SHOWN: usize = 4;
+
+/// A `UuidB64` that only shows its first 4 characters in `Debug` output
+///
+/// For IDs that act as capability tokens, like invite or reset links: put
+/// this in a struct instead of a `UuidB64`, and a derived `Debug` on the
+/// struct no longer leaks the whole token into logs. `Display`, `FromStr`
+/// and, with the `serde` feature, (de)serialization all use the full ID, since
+/// those are how it gets passed on.
+///
+/// ```
+/// # use uuid_b64::{Redacted, UuidB64};
+/// let token: Redacted = "sMHuhm9GTxuNi3hJ51287g".parse().unwrap();
+/// assert_eq!(format!("{:?}", token), "Redacted(sMHu…)");
+/// assert_eq!(token.to_string(), "sMHuhm9GTxuNi3hJ51287g");
+/// assert_eq!(token.into_inner(), UuidB64::from(token));
+/// ```
+#[derive(Copy, Clone, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Redacted(pub UuidB64);
+
+impl Redacted {
+    /// Unwrap into the full ID
+    pub fn into_inner(self) -> UuidB64 {
+        self.0
+    }
+}
+
+impl UuidB64 {
+    /// Wrap this ID so that its `Debug` output is cut short, see
+    /// [`Redacted`](struct.Redacted.html)
+    pub fn redacted(self) -> Redacted {
+        Redacted(self)
+    }
+}
+
+impl Debug for Redacted {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        let mut buf = [0; 22];
+        write!(f, "Redacted({}…)", &self.0.encode_to(&mut buf)[..SHOWN])
+    }
+}
+
+impl Display for Redacted {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        Display::fmt(&self.0, f)
+    }
+}
+
+impl FromStr for Redacted {
+    type Err = ParseUuidB64Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.parse().map(Redacted)
+    }
+}
+
+impl Deref for Redacted {
+    type Target = UuidB64;
+
+    fn deref(&self) -> &UuidB64 {
+        &self.0
+    }
+}
+
+impl From<UuidB64> for Redacted {
+    fn from(id: UuidB64) -> Self {
+        Redacted(id)
+    }
+}
+
+impl From<Redacted> for UuidB64 {
+    fn from(id: Redacted) -> Self {
+        id.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Redacted;
+    use UuidB64;
+
+    #[derive(Debug)]
+    struct Invite {
+        #[allow(dead_code)]
+        token: Redacted,
+    }
+
+    #[test]
+    fn derived_debug_hides_the_id() {
+        let id = UuidB64::new();
+        let full = id.to_string();
+        let invite = Invite {
+            token: id.redacted(),
+        };
+        let debug = format!("{:?}", invite);
+        assert_eq!(
+            debug,
+            format!("Invite {{ token: Redacted({}…) }}", &full[..4])
+        );
+        assert!(!debug.contains(&full[4..]));
+        assert_eq!(invite.token.to_string(), full);
+        assert_eq!(*invite.token, id);
+    }
+}
@@ -0,0 +1,130 @@
+//! Support for the redis crate
+//!
+//! IDs are written as their 22-character base64 form, so keys built from
+//! them stay readable in `redis-cli`. Wrap them in `RedisBytes` to write the
+//! 16 raw bytes instead. Either form is read back.
+
+extern crate redis;
+
+use std::str;
+
+use self::redis::{FromRedisValue, ParsingError, RedisWrite, ToRedisArgs, ToSingleRedisArg, Value};
+use uuid::Uuid;
+
+use UuidB64;
+
+/// Writes an ID to Redis as 16 raw bytes instead of base64
+///
+/// Smaller, for sets or hashes holding many IDs, but unreadable in
+/// `redis-cli`.
+///
+/// ```rust,ignore
+/// con.sadd("seen", RedisBytes(id))?;
+/// let RedisBytes(id) = con.spop("seen")?;
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct RedisBytes(pub UuidB64);
+
+impl ToRedisArgs for UuidB64 {
+    fn write_redis_args<W>(&self, out: &mut W)
+    where
+        W: ?Sized + RedisWrite,
+    {
+        out.write_arg(self.to_istring().as_bytes())
+    }
+}
+
+impl ToSingleRedisArg for UuidB64 {}
+
+/// Reads the base64 form or 16 raw bytes
+impl FromRedisValue for UuidB64 {
+    fn from_redis_value(v: Value) -> Result<Self, ParsingError> {
+        let bytes = match v {
+            Value::BulkString(ref bytes) => &bytes[..],
+            Value::SimpleString(ref s) => s.as_bytes(),
+            _ => return Err(format!("expected a UUID, found {:?}", v).into()),
+        };
+        if bytes.len() == 16 {
+            return Ok(UuidB64(
+                Uuid::from_bytes(bytes).expect("UUIDs are 16 bytes"),
+            ));
+        }
+        str::from_utf8(bytes)?
+            .parse()
+            .map_err(|e: ::errors::ParseUuidB64Error| e.to_string().into())
+    }
+}
+
+impl ToRedisArgs for RedisBytes {
+    fn write_redis_args<W>(&self, out: &mut W)
+    where
+        W: ?Sized + RedisWrite,
+    {
+        out.write_arg(self.0 .0.as_bytes())
+    }
+}
+
+impl ToSingleRedisArg for RedisBytes {}
+
+impl FromRedisValue for RedisBytes {
+    fn from_redis_value(v: Value) -> Result<Self, ParsingError> {
+        UuidB64::from_redis_value(v).map(RedisBytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::redis::{FromRedisValue, ToRedisArgs, Value};
+    use super::RedisBytes;
+
+    use UuidB64;
+
+    const ID: &str = "sMHuhm9GTxuNi3hJ51287g";
+
+    #[test]
+    fn writes_base64() {
+        let id: UuidB64 = ID.parse().unwrap();
+        assert_eq!(id.to_redis_args(), vec![ID.as_bytes().to_vec()]);
+        // and in lists of arguments, one per ID
+        assert_eq!(vec![id, id].to_redis_args().len(), 2);
+    }
+
+    #[test]
+    fn writes_raw_bytes() {
+        let id: UuidB64 = ID.parse().unwrap();
+        assert_eq!(
+            RedisBytes(id).to_redis_args(),
+            vec![id.uuid().as_bytes().to_vec()]
+        );
+    }
+
+    #[test]
+    fn reads_either_form() {
+        let id: UuidB64 = ID.parse().unwrap();
+        let values = vec![
+            Value::BulkString(ID.as_bytes().to_vec()),
+            Value::SimpleString(ID.to_owned()),
+            Value::BulkString(id.uuid().as_bytes().to_vec()),
+        ];
+        for value in values {
+            assert_eq!(UuidB64::from_redis_value(value.clone()).unwrap(), id);
+            assert_eq!(RedisBytes::from_redis_value(value).unwrap(), RedisBytes(id));
+        }
+    }
+
+    #[test]
+    fn rejects_other_values() {
+        for value in [
+            Value::Nil,
+            Value::Int(4),
+            Value::BulkString(b"nope".to_vec()),
+            Value::BulkString(vec![0xff; 22]),
+        ] {
+            assert!(UuidB64::from_redis_value(value).is_err());
+        }
+        assert_eq!(
+            Option::<UuidB64>::from_redis_value(Value::Nil).unwrap(),
+            None
+        );
+    }
+}
@@ -0,0 +1,62 @@
+//! `UuidB64::PATTERN` as a compiled regex
+
+extern crate regex;
+
+use self::regex::Regex;
+
+use UuidB64;
+
+lazy_static! {
+    static ref PATTERN: Regex = Regex::new(UuidB64::PATTERN).expect("PATTERN is a valid regex");
+}
+
+impl UuidB64 {
+    /// [`PATTERN`](#associatedconstant.PATTERN), compiled the first time
+    /// it's used
+    ///
+    /// For checking IDs in code that's already written in terms of regexes,
+    /// such as routing or form validation. It accepts exactly what
+    /// [`is_valid`](#method.is_valid) does, which is faster.
+    ///
+    /// ```
+    /// # use uuid_b64::UuidB64;
+    /// assert!(UuidB64::regex().is_match("sMHuhm9GTxuNi3hJ51287g"));
+    /// assert!(!UuidB64::regex().is_match("sMHuhm9GTxuNi3hJ51287h"));
+    /// ```
+    pub fn regex() -> &'static Regex {
+        &PATTERN
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use UuidB64;
+
+    #[test]
+    fn agrees_with_is_valid() {
+        let mut inputs = vec![
+            String::new(),
+            "sMHuhm9GTxuNi3hJ51287".to_string(),
+            "sMHuhm9GTxuNi3hJ51287gg".to_string(),
+            "xsMHuhm9GTxuNi3hJ51287g".to_string(),
+            "sMHuhm9GTxuNi3hJ51287g\n".to_string(),
+        ];
+        for _ in 0..100 {
+            let id = UuidB64::new().to_string();
+            for (i, c) in "-_+/=.AQgwh".chars().enumerate() {
+                let mut bytes = id.clone().into_bytes();
+                bytes[(i * 7) % 22] = c as u8;
+                inputs.push(String::from_utf8(bytes).unwrap());
+            }
+            inputs.push(id);
+        }
+        for s in &inputs {
+            assert_eq!(
+                UuidB64::regex().is_match(s),
+                UuidB64::is_valid(s),
+                "{:?}",
+                s
+            );
+        }
+    }
+}
@@ -0,0 +1,97 @@
+//! GraphQL Relay global object identifiers
+
+use base64;
+
+use errors::ErrorKind;
+use UuidB64;
+
+impl UuidB64 {
+    /// Encode a Relay global ID for an object of type `type_name`
+    ///
+    /// This follows the `graphql-relay` convention of standard, padded
+    /// base64 of `"Type:id"`, where `id` is this ID's usual 22-character form.
+    ///
+    /// ```
+    /// # use uuid_b64::UuidB64;
+    /// let id: UuidB64 = "sMHuhm9GTxuNi3hJ51287g".parse().unwrap();
+    /// let global = id.to_global_id("User");
+    /// assert_eq!(global, "VXNlcjpzTUh1aG05R1R4dU5pM2hKNTEyODdn");
+    /// assert_eq!(UuidB64::parse_global_id(&global).unwrap(), ("User".to_string(), id));
+    /// ```
+    pub fn to_global_id(&self, type_name: &str) -> String {
+        base64::encode(&format!("{}:{}", type_name, self))
+    }
+
+    /// Decode a Relay global ID into its type name and ID
+    pub fn parse_global_id(global_id: &str) -> Result<(String, UuidB64), ErrorKind> {
+        let invalid = || ErrorKind::InvalidGlobalId(global_id.into());
+        let decoded = base64::decode(global_id).map_err(|_| invalid())?;
+        let decoded = String::from_utf8(decoded).map_err(|_| invalid())?;
+        let split = decoded.rfind(':').ok_or_else(invalid)?;
+        let id = decoded[split + 1..].parse().map_err(|_| invalid())?;
+        let mut type_name = decoded;
+        type_name.truncate(split);
+        if type_name.is_empty() {
+            return Err(invalid());
+        }
+        Ok((type_name, id))
+    }
+
+    /// Decode a Relay global ID, requiring that it is for type `type_name`
+    pub fn parse_global_id_of(type_name: &str, global_id: &str) -> Result<UuidB64, ErrorKind> {
+        match UuidB64::parse_global_id(global_id)? {
+            (ref found, id) if found == type_name => Ok(id),
+            (found, _) => Err(ErrorKind::GlobalIdTypeMismatch(type_name.into(), found)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use base64;
+
+    use errors::ErrorKind;
+    use UuidB64;
+
+    #[test]
+    fn roundtrips() {
+        let id = UuidB64::new();
+        let global = id.to_global_id("Order");
+        assert_eq!(
+            UuidB64::parse_global_id(&global).unwrap(),
+            ("Order".to_string(), id)
+        );
+        assert_eq!(UuidB64::parse_global_id_of("Order", &global).unwrap(), id);
+    }
+
+    #[test]
+    fn is_relay_compatible() {
+        let id = UuidB64::new();
+        let decoded = base64::decode(&id.to_global_id("Order")).unwrap();
+        assert_eq!(String::from_utf8(decoded).unwrap(), format!("Order:{}", id));
+    }
+
+    #[test]
+    fn type_names_may_contain_colons() {
+        let id = UuidB64::new();
+        let global = id.to_global_id("ns:Order");
+        assert_eq!(UuidB64::parse_global_id_of("ns:Order", &global).unwrap(), id);
+    }
+
+    #[test]
+    fn rejects_wrong_type() {
+        let global = UuidB64::new().to_global_id("Order");
+        match UuidB64::parse_global_id_of("User", &global) {
+            Err(ErrorKind::GlobalIdTypeMismatch(ref expected, ref found))
+                if expected == "User" && found == "Order" => {}
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn rejects_garbage() {
+        for bad in &["", "!!!", "T3JkZXI=", "T3JkZXI6", "Om5vcGU="] {
+            assert!(UuidB64::parse_global_id(bad).is_err(), "{} parsed", bad);
+        }
+    }
+}
@@ -0,0 +1,148 @@
+//! rkyv zero-copy archiving
+//!
+//! IDs archive as their 16 bytes, so they can be read straight out of an
+//! archived (or memory-mapped) buffer:
+//!
+//! ```rust,ignore
+//! #[derive(Archive, Serialize, Deserialize)]
+//! struct Session {
+//!     id: UuidB64,
+//!     user: UuidB64,
+//! }
+//!
+//! let archived = rkyv::access::<ArchivedSession, rkyv::rancor::Error>(&bytes)?;
+//! if archived.user == user_id {
+//!     println!("{}", archived.id);
+//! }
+//! ```
+
+extern crate rkyv;
+
+use std::cmp::Ordering;
+use std::fmt;
+
+use self::rkyv::bytecheck::CheckBytes;
+use self::rkyv::rancor::Fallible;
+use self::rkyv::{Archive, Deserialize, Place, Portable, Serialize};
+
+use UuidB64;
+
+/// The archived form of a `UuidB64`: its 16 bytes
+///
+/// Compares equal to, and displays the same as, the `UuidB64` it came from.
+/// The comparison only works with the archived ID on the left, so that
+/// comparisons between `UuidB64`s don't become ambiguous.
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(transparent)]
+pub struct ArchivedUuidB64([u8; 16]);
+
+impl ArchivedUuidB64 {
+    /// The ID, copied out of the archive
+    pub fn uuid_b64(&self) -> UuidB64 {
+        UuidB64::from_bytes(self.0)
+    }
+
+    /// The raw bytes, in the same order as `Uuid::as_bytes`
+    pub fn as_bytes(&self) -> &[u8; 16] {
+        &self.0
+    }
+}
+
+impl PartialEq<UuidB64> for ArchivedUuidB64 {
+    fn eq(&self, other: &UuidB64) -> bool {
+        self.0 == *other.0.as_bytes()
+    }
+}
+
+impl PartialOrd<UuidB64> for ArchivedUuidB64 {
+    fn partial_cmp(&self, other: &UuidB64) -> Option<Ordering> {
+        self.0.partial_cmp(other.0.as_bytes())
+    }
+}
+
+impl fmt::Display for ArchivedUuidB64 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.uuid_b64().fmt(f)
+    }
+}
+
+impl fmt::Debug for ArchivedUuidB64 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "ArchivedUuidB64({})", self)
+    }
+}
+
+// SAFETY: `ArchivedUuidB64` is a transparent `[u8; 16]`, which has the same
+// layout on every platform
+unsafe impl Portable for ArchivedUuidB64 {}
+
+// SAFETY: every 16 bytes are a valid ID
+unsafe impl<C: Fallible + ?Sized> CheckBytes<C> for ArchivedUuidB64 {
+    unsafe fn check_bytes(_: *const Self, _: &mut C) -> Result<(), C::Error> {
+        Ok(())
+    }
+}
+
+impl Archive for UuidB64 {
+    type Archived = ArchivedUuidB64;
+    type Resolver = ();
+
+    fn resolve(&self, _: (), out: Place<ArchivedUuidB64>) {
+        // SAFETY: `ArchivedUuidB64` is a transparent `[u8; 16]`, which has
+        // no padding
+        unsafe {
+            out.write_unchecked(ArchivedUuidB64(*self.0.as_bytes()));
+        }
+    }
+}
+
+impl<S: Fallible + ?Sized> Serialize<S> for UuidB64 {
+    fn serialize(&self, _: &mut S) -> Result<(), S::Error> {
+        Ok(())
+    }
+}
+
+impl<D: Fallible + ?Sized> Deserialize<UuidB64, D> for ArchivedUuidB64 {
+    fn deserialize(&self, _: &mut D) -> Result<UuidB64, D::Error> {
+        Ok(self.uuid_b64())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::rkyv::rancor::Error;
+    use super::rkyv::{self, Archive, Deserialize, Serialize};
+    use super::ArchivedUuidB64;
+
+    use UuidB64;
+
+    #[derive(Debug, PartialEq, Archive, Serialize, Deserialize)]
+    struct Session {
+        id: UuidB64,
+        users: Vec<UuidB64>,
+    }
+
+    #[test]
+    fn archives_16_bytes() {
+        let id: UuidB64 = "sMHuhm9GTxuNi3hJ51287g".parse().unwrap();
+        let bytes = rkyv::to_bytes::<Error>(&id).unwrap();
+        assert_eq!(&bytes[..], id.uuid().as_bytes());
+        let archived = rkyv::access::<ArchivedUuidB64, Error>(&bytes).unwrap();
+        assert_eq!(*archived, id);
+        assert_eq!(archived.to_string(), "sMHuhm9GTxuNi3hJ51287g");
+    }
+
+    #[test]
+    fn roundtrips_in_structs() {
+        let session = Session {
+            id: UuidB64::new(),
+            users: vec![UuidB64::new(), UuidB64::new()],
+        };
+        let bytes = rkyv::to_bytes::<Error>(&session).unwrap();
+        let archived = rkyv::access::<ArchivedSession, Error>(&bytes).unwrap();
+        assert_eq!(archived.id, session.id);
+        assert_eq!(archived.users[1], session.users[1]);
+        let back: Session = rkyv::deserialize::<Session, Error>(archived).unwrap();
+        assert_eq!(back, session);
+    }
+}
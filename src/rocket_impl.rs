@@ -0,0 +1,90 @@
+//! Rocket route parameters, form fields and `uri!`
+//!
+//! ```rust,ignore
+//! #[get("/users/<id>")]
+//! fn user(id: UuidB64) -> String {
+//!     id.to_string()
+//! }
+//!
+//! let link = uri!(user(UuidB64::new()));
+//! ```
+//!
+//! A malformed ID forwards the request, like any other failed parameter
+//! guard, so it can fall through to another route. If none match, Rocket
+//! answers 422 Unprocessable Entity; take an `Option<UuidB64>` to answer
+//! 404 (or anything else) from the route instead.
+
+extern crate rocket;
+
+use std::fmt;
+
+use self::rocket::form::{self, FromFormField, ValueField};
+use self::rocket::http::uri::fmt::{Formatter, Part, UriDisplay};
+use self::rocket::request::FromParam;
+
+use errors::ParseUuidB64Error;
+use UuidB64;
+
+impl<'a> FromParam<'a> for UuidB64 {
+    type Error = ParseUuidB64Error;
+
+    fn from_param(param: &'a str) -> Result<Self, ParseUuidB64Error> {
+        param.parse()
+    }
+}
+
+impl<'v> FromFormField<'v> for UuidB64 {
+    fn from_value(field: ValueField<'v>) -> form::Result<'v, Self> {
+        field
+            .value
+            .parse()
+            .map_err(|e: ParseUuidB64Error| form::Error::validation(e.to_string()).into())
+    }
+}
+
+impl<P: Part> UriDisplay<P> for UuidB64 {
+    fn fmt(&self, f: &mut Formatter<P>) -> fmt::Result {
+        f.write_value(&*self.to_istring())
+    }
+}
+
+rocket::http::impl_from_uri_param_identity!(UuidB64);
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::rocket::form::{Form, FromFormField, ValueField};
+    use super::rocket::http::uri::fmt::{Path, Query, UriDisplay};
+    use super::rocket::request::FromParam;
+
+    use UuidB64;
+
+    const ID: &str = "sMHuhm9GTxuNi3hJ51287g";
+
+    #[test]
+    fn path_parameters() {
+        assert_eq!(UuidB64::from_param(ID).unwrap().to_string(), ID);
+        assert!(UuidB64::from_param("nope").is_err());
+    }
+
+    #[test]
+    fn form_fields() {
+        let id = UuidB64::from_value(ValueField::parse(&format!("owner={}", ID))).unwrap();
+        assert_eq!(id.to_string(), ID);
+        let errors = UuidB64::from_value(ValueField::parse("owner=nope")).unwrap_err();
+        assert!(errors.to_string().contains("expected 22 characters"), "{}", errors);
+
+        let form: HashMap<String, UuidB64> = Form::parse(&format!("a={}&b={}", ID, ID)).unwrap();
+        assert_eq!(form["b"].to_string(), ID);
+    }
+
+    #[test]
+    fn displays_in_uris() {
+        let id: UuidB64 = ID.parse().unwrap();
+        let path = &id as &dyn UriDisplay<Path>;
+        assert_eq!(path.to_string(), ID);
+        let query = &id as &dyn UriDisplay<Query>;
+        assert_eq!(query.to_string(), ID);
+    }
+}
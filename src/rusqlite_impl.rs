@@ -0,0 +1,145 @@
+//! Support for rusqlite
+//!
+//! IDs are stored as 16-byte `BLOB`s, or as their 22-character base64 form
+//! when wrapped in `SqliteText`. Either way they're read back from both,
+//! and from `TEXT` columns holding hyphenated UUIDs.
+
+extern crate rusqlite;
+
+use self::rusqlite::types::{FromSql, FromSqlError, FromSqlResult, ToSqlOutput, ValueRef};
+use self::rusqlite::ToSql;
+use uuid::Uuid;
+
+use UuidB64;
+
+/// Stores an ID as its base64 text instead of a `BLOB`
+///
+/// Text takes more space, but stays readable from the `sqlite3` shell.
+///
+/// ```rust,ignore
+/// conn.execute("INSERT INTO users (id) VALUES (?1)", [SqliteText(id)])?;
+/// let SqliteText(id) = conn.query_row("SELECT id FROM users", [], |row| row.get(0))?;
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct SqliteText(pub UuidB64);
+
+impl ToSql for UuidB64 {
+    fn to_sql(&self) -> rusqlite::Result<ToSqlOutput<'_>> {
+        Ok(ToSqlOutput::Borrowed(ValueRef::Blob(self.0.as_bytes())))
+    }
+}
+
+impl FromSql for UuidB64 {
+    fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+        match value {
+            ValueRef::Blob(bytes) if bytes.len() == 16 => Ok(UuidB64(
+                Uuid::from_bytes(bytes).map_err(|e| FromSqlError::Other(Box::new(e)))?,
+            )),
+            ValueRef::Blob(bytes) => Err(FromSqlError::InvalidBlobSize {
+                expected_size: 16,
+                blob_size: bytes.len(),
+            }),
+            ValueRef::Text(_) => {
+                let s = value.as_str()?;
+                if s.len() == 22 {
+                    s.parse()
+                        .map_err(|e| FromSqlError::Other(Box::new(e)))
+                } else {
+                    Uuid::parse_str(s)
+                        .map(UuidB64)
+                        .map_err(|e| FromSqlError::Other(e.to_string().into()))
+                }
+            }
+            _ => Err(FromSqlError::InvalidType),
+        }
+    }
+}
+
+impl ToSql for SqliteText {
+    fn to_sql(&self) -> rusqlite::Result<ToSqlOutput<'_>> {
+        Ok(ToSqlOutput::from(self.0.to_string()))
+    }
+}
+
+impl FromSql for SqliteText {
+    fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+        UuidB64::column_result(value).map(SqliteText)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::rusqlite::types::Value;
+    use super::rusqlite::Connection;
+    use super::SqliteText;
+
+    use UuidB64;
+
+    const ID: &str = "sMHuhm9GTxuNi3hJ51287g";
+
+    fn connection() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute("CREATE TABLE ids (id)", []).unwrap();
+        conn
+    }
+
+    #[test]
+    fn stores_blobs() {
+        let conn = connection();
+        let id: UuidB64 = ID.parse().unwrap();
+        conn.execute("INSERT INTO ids VALUES (?1)", [id]).unwrap();
+        let raw: Value = conn
+            .query_row("SELECT id FROM ids", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(raw, Value::Blob(id.uuid().as_bytes().to_vec()));
+        let found: UuidB64 = conn
+            .query_row("SELECT id FROM ids", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(found, id);
+    }
+
+    #[test]
+    fn stores_text() {
+        let conn = connection();
+        let id: UuidB64 = ID.parse().unwrap();
+        conn.execute("INSERT INTO ids VALUES (?1)", [SqliteText(id)])
+            .unwrap();
+        let raw: String = conn
+            .query_row("SELECT id FROM ids", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(raw, ID);
+        let found: UuidB64 = conn
+            .query_row("SELECT id FROM ids", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(found, id);
+        let SqliteText(found) = conn
+            .query_row("SELECT id FROM ids", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(found, id);
+    }
+
+    #[test]
+    fn reads_hyphenated_text() {
+        let conn = connection();
+        let found: UuidB64 = conn
+            .query_row("SELECT 'b0c1ee86-6f46-4f1b-8d8b-7849e75dbcee'", [], |row| {
+                row.get(0)
+            })
+            .unwrap();
+        assert_eq!(found.to_string(), ID);
+    }
+
+    #[test]
+    fn rejects_other_values() {
+        let conn = connection();
+        for sql in &[
+            "SELECT x'0102'",
+            "SELECT 'nope'",
+            "SELECT 42",
+            "SELECT NULL",
+        ] {
+            let found = conn.query_row(sql, [], |row| row.get::<_, UuidB64>(0));
+            assert!(found.is_err(), "{}", sql);
+        }
+    }
+}
@@ -0,0 +1,118 @@
+extern crate schemars;
+
+use std::borrow::Cow;
+// `json_schema!` expects `TryFrom` in scope
+use std::convert::TryFrom;
+
+use self::schemars::{JsonSchema, Schema, SchemaGenerator};
+
+use UuidB64;
+
+/// `UuidB64`s are described as 22-character base64 strings, with a
+/// `pattern` that validators can check, so they show up correctly wherever
+/// they're used in schemars-derived types, including the path and query
+/// parameter structs that aide documents for axum handlers.
+impl JsonSchema for UuidB64 {
+    fn inline_schema() -> bool {
+        true
+    }
+
+    fn schema_name() -> Cow<'static, str> {
+        "UuidB64".into()
+    }
+
+    fn schema_id() -> Cow<'static, str> {
+        "uuid_b64::UuidB64".into()
+    }
+
+    fn json_schema(_: &mut SchemaGenerator) -> Schema {
+        self::schemars::json_schema!({
+            "type": "string",
+            "format": "uuid-b64",
+            "minLength": 22,
+            "maxLength": 22,
+            "pattern": UuidB64::PATTERN,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "aide")]
+    extern crate aide;
+
+    use std::convert::TryFrom;
+
+    use super::schemars::{JsonSchema, Schema, SchemaGenerator};
+
+    use UuidB64;
+
+    /// A hand-rolled stand-in for `#[derive(JsonSchema)]` on
+    /// `struct OrderPath { id: UuidB64 }`
+    struct OrderPath;
+
+    impl JsonSchema for OrderPath {
+        fn schema_name() -> ::std::borrow::Cow<'static, str> {
+            "OrderPath".into()
+        }
+
+        fn json_schema(gen: &mut SchemaGenerator) -> Schema {
+            let id = gen.subschema_for::<UuidB64>();
+            super::schemars::json_schema!({
+                "type": "object",
+                "properties": { "id": id },
+                "required": ["id"],
+            })
+        }
+    }
+
+    #[test]
+    fn schema_is_an_inline_string() {
+        let mut gen = SchemaGenerator::default();
+        let schema = gen.subschema_for::<UuidB64>();
+        assert_eq!(schema.get("type").unwrap(), "string");
+        assert_eq!(schema.get("format").unwrap(), "uuid-b64");
+        assert_eq!(schema.get("minLength").unwrap(), 22);
+        assert_eq!(schema.get("maxLength").unwrap(), 22);
+        assert_eq!(schema.get("pattern").unwrap(), UuidB64::PATTERN);
+        assert!(gen.definitions().is_empty());
+    }
+
+    #[test]
+    fn field_schema_is_not_a_bare_string() {
+        let mut gen = SchemaGenerator::default();
+        gen.subschema_for::<OrderPath>();
+        let schema = &gen.definitions()["OrderPath"];
+        assert_eq!(schema["properties"]["id"]["format"], "uuid-b64");
+    }
+
+    #[cfg(feature = "aide")]
+    #[test]
+    fn aide_path_parameters() {
+        use self::aide::generate::in_context;
+        use self::aide::openapi::{Parameter, ParameterSchemaOrContent};
+        use self::aide::operation::{parameters_from_schema, ParamLocation};
+
+        let params = in_context(|ctx| {
+            let schema = ctx.schema.subschema_for::<OrderPath>();
+            parameters_from_schema(ctx, schema, ParamLocation::Path)
+        });
+        assert_eq!(params.len(), 1);
+        match params[0] {
+            Parameter::Path {
+                ref parameter_data,
+                ..
+            } => {
+                assert_eq!(parameter_data.name, "id");
+                assert!(parameter_data.required);
+                match parameter_data.format {
+                    ParameterSchemaOrContent::Schema(ref s) => {
+                        assert_eq!(s.json_schema.get("format").unwrap(), "uuid-b64");
+                    }
+                    ref other => panic!("expected a schema, got {:?}", other),
+                }
+            }
+            ref other => panic!("expected a path parameter, got {:?}", other),
+        }
+    }
+}
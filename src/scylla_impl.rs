@@ -0,0 +1,80 @@
+//! Support for the ScyllaDB (and Cassandra) driver
+//!
+//! IDs map to the native CQL `uuid` type, in both bound values and rows.
+//! The driver works with version 1 of the uuid crate, which this converts
+//! through (the `scylla` feature turns on `uuid1`).
+
+extern crate scylla;
+extern crate uuid1;
+
+use self::scylla::cluster::metadata::ColumnType;
+use self::scylla::deserialize::value::DeserializeValue;
+use self::scylla::deserialize::{DeserializationError, FrameSlice, TypeCheckError};
+use self::scylla::serialize::value::SerializeValue;
+use self::scylla::serialize::writers::{CellWriter, WrittenCellProof};
+use self::scylla::serialize::SerializationError;
+
+use UuidB64;
+
+impl SerializeValue for UuidB64 {
+    fn serialize<'b>(
+        &self,
+        typ: &ColumnType,
+        writer: CellWriter<'b>,
+    ) -> Result<WrittenCellProof<'b>, SerializationError> {
+        self.to_uuid1().serialize(typ, writer)
+    }
+}
+
+impl<'frame, 'metadata> DeserializeValue<'frame, 'metadata> for UuidB64 {
+    fn type_check(typ: &ColumnType) -> Result<(), TypeCheckError> {
+        <uuid1::Uuid as DeserializeValue>::type_check(typ)
+    }
+
+    fn deserialize(
+        typ: &'metadata ColumnType<'metadata>,
+        v: Option<FrameSlice<'frame>>,
+    ) -> Result<Self, DeserializationError> {
+        uuid1::Uuid::deserialize(typ, v).map(UuidB64::from_uuid1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::scylla::cluster::metadata::{ColumnType, NativeType};
+    use super::scylla::deserialize::value::DeserializeValue;
+    use super::scylla::deserialize::FrameSlice;
+    use super::scylla::serialize::value::SerializeValue;
+    use super::scylla::serialize::writers::CellWriter;
+
+    use UuidB64;
+
+    const UUID: ColumnType = ColumnType::Native(NativeType::Uuid);
+    const TEXT: ColumnType = ColumnType::Native(NativeType::Text);
+
+    #[test]
+    fn roundtrips_as_cql_uuids() {
+        let id: UuidB64 = "sMHuhm9GTxuNi3hJ51287g".parse().unwrap();
+        let mut buf = Vec::new();
+        id.serialize(&UUID, CellWriter::new(&mut buf)).unwrap();
+        // length-prefixed
+        assert_eq!(&buf[..4], &[0, 0, 0, 16]);
+        assert_eq!(&buf[4..], id.uuid().as_bytes());
+
+        assert!(<UuidB64 as DeserializeValue>::type_check(&UUID).is_ok());
+        let slice = FrameSlice::new_borrowed(&buf[4..]);
+        assert_eq!(UuidB64::deserialize(&UUID, Some(slice)).unwrap(), id);
+    }
+
+    #[test]
+    fn rejects_other_types() {
+        let id = UuidB64::new();
+        assert!(id
+            .serialize(&TEXT, CellWriter::new(&mut Vec::new()))
+            .is_err());
+        assert!(<UuidB64 as DeserializeValue>::type_check(&TEXT).is_err());
+        assert!(UuidB64::deserialize(&UUID, None).is_err());
+        let short = FrameSlice::new_borrowed(&[1, 2]);
+        assert!(UuidB64::deserialize(&UUID, Some(short)).is_err());
+    }
+}
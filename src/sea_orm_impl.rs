@@ -0,0 +1,60 @@
+//! Support for SeaORM entities
+//!
+//! `Value` and `ValueType` come from the `sea-query` feature, this adds
+//! what SeaORM needs on top to read `UuidB64` columns out of query results:
+//!
+//! ```rust,ignore
+//! #[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+//! #[sea_orm(table_name = "users")]
+//! pub struct Model {
+//!     #[sea_orm(primary_key, auto_increment = false)]
+//!     pub id: UuidB64,
+//!     pub parent: Option<UuidB64>,
+//! }
+//! ```
+
+extern crate sea_orm;
+
+use self::sea_orm::prelude::Uuid as SeaUuid;
+use self::sea_orm::{ColIdx, DbErr, QueryResult, TryFromU64, TryGetError, TryGetable};
+
+use UuidB64;
+
+/// Reads `uuid` columns, or whatever the backend stores UUIDs as
+impl TryGetable for UuidB64 {
+    fn try_get_by<I: ColIdx>(res: &QueryResult, index: I) -> Result<Self, TryGetError> {
+        let uuid = SeaUuid::try_get_by(res, index)?;
+        Ok(UuidB64::from_bytes(*uuid.as_bytes()))
+    }
+}
+
+/// IDs are never auto-incremented, so primary keys need
+/// `auto_increment = false`
+impl TryFromU64 for UuidB64 {
+    fn try_from_u64(_: u64) -> Result<Self, DbErr> {
+        Err(DbErr::ConvertFromU64("UuidB64"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::sea_orm::{DbErr, TryFromU64, Value};
+
+    use UuidB64;
+
+    #[test]
+    fn not_auto_incremented() {
+        match UuidB64::try_from_u64(1) {
+            Err(DbErr::ConvertFromU64(ty)) => assert_eq!(ty, "UuidB64"),
+            other => panic!("unexpected {:?}", other),
+        }
+    }
+
+    #[test]
+    fn shares_sea_querys_values() {
+        // sea_orm::Value is sea_query::Value, so the sea-query impls apply
+        let id = UuidB64::new();
+        let value: Value = id.into();
+        assert_eq!(value.unwrap::<UuidB64>(), id);
+    }
+}
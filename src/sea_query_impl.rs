@@ -0,0 +1,93 @@
+extern crate sea_query;
+
+use self::sea_query::value::prelude::Uuid as SeaUuid;
+use self::sea_query::{ArrayType, ColumnType, Nullable, Value, ValueType, ValueTypeErr};
+
+use {MaybeUuidB64, UuidB64};
+
+/// Binds as a UUID, so IDs compare against `uuid` columns without casts
+///
+/// ```rust,ignore
+/// let query = Query::select()
+///     .column(Order::Total)
+///     .from(Order::Table)
+///     .and_where(Expr::col(Order::Id).eq(id))
+///     .build(PostgresQueryBuilder);
+/// ```
+impl From<UuidB64> for Value {
+    fn from(id: UuidB64) -> Value {
+        Value::Uuid(Some(SeaUuid::from_bytes(*id.0.as_bytes())))
+    }
+}
+
+impl From<MaybeUuidB64> for Value {
+    fn from(id: MaybeUuidB64) -> Value {
+        id.0.into()
+    }
+}
+
+impl Nullable for UuidB64 {
+    fn null() -> Value {
+        Value::Uuid(None)
+    }
+}
+
+impl ValueType for UuidB64 {
+    fn try_from(v: Value) -> Result<Self, ValueTypeErr> {
+        match v {
+            Value::Uuid(Some(uuid)) => Ok(UuidB64::from_bytes(*uuid.as_bytes())),
+            _ => Err(ValueTypeErr),
+        }
+    }
+
+    fn type_name() -> String {
+        "UuidB64".to_owned()
+    }
+
+    fn array_type() -> ArrayType {
+        ArrayType::Uuid
+    }
+
+    fn column_type() -> ColumnType {
+        ColumnType::Uuid
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::sea_query::{Nullable, Value, ValueType};
+
+    use {MaybeUuidB64, UuidB64};
+
+    const UUID: &str = "b0c1ee86-6f46-4f1b-8d8b-7849e75dbcee";
+    const ID: &str = "sMHuhm9GTxuNi3hJ51287g";
+
+    #[test]
+    fn binds_as_a_uuid() {
+        let id: UuidB64 = ID.parse().unwrap();
+        let value = Value::from(id);
+        assert_eq!(value.as_ref_uuid().unwrap().to_string(), UUID);
+        assert_eq!(<UuidB64 as ValueType>::try_from(value).unwrap(), id);
+    }
+
+    #[test]
+    fn nulls() {
+        assert_eq!(Value::from(None::<UuidB64>), UuidB64::null());
+        assert_eq!(Value::from(MaybeUuidB64(None)), UuidB64::null());
+        assert_eq!(
+            <Option<UuidB64> as ValueType>::try_from(UuidB64::null()).unwrap(),
+            None
+        );
+        let id = UuidB64::new();
+        assert_eq!(
+            <Option<UuidB64> as ValueType>::try_from(Value::from(MaybeUuidB64(Some(id)))).unwrap(),
+            Some(id)
+        );
+    }
+
+    #[test]
+    fn rejects_other_values() {
+        assert!(<UuidB64 as ValueType>::try_from(Value::from(ID)).is_err());
+        assert!(<UuidB64 as ValueType>::try_from(UuidB64::null()).is_err());
+    }
+}
@@ -0,0 +1,272 @@
+//! `#[serde(with = ...)]` helpers for fields that stay `Uuid`
+//!
+//! For structs that can't switch their fields to `UuidB64`, because they're
+//! shared with crates that expect `uuid::Uuid`, but should still be written
+//! as Base64. The fields have to be the `Uuid` this crate re-exports.
+
+/// Serialize and deserialize a `Uuid` field exactly like a `UuidB64`
+///
+/// Base64 in human-readable formats, and the 16 raw bytes in binary ones.
+///
+/// ```
+/// # extern crate serde_json;
+/// # #[macro_use] extern crate serde_derive;
+/// # extern crate uuid_b64;
+/// # use uuid_b64::Uuid;
+/// #[derive(Deserialize, Serialize)]
+/// struct Order {
+///     #[serde(with = "uuid_b64::serde::as_base64")]
+///     id: Uuid,
+/// }
+///
+/// # fn main() {
+/// let order: Order = serde_json::from_str(r#"{"id": "sMHuhm9GTxuNi3hJ51287g"}"#).unwrap();
+/// assert_eq!(order.id.hyphenated().to_string(), "b0c1ee86-6f46-4f1b-8d8b-7849e75dbcee");
+/// assert_eq!(serde_json::to_string(&order).unwrap(), r#"{"id":"sMHuhm9GTxuNi3hJ51287g"}"#);
+/// # }
+/// ```
+///
+/// The submodules do the same for `Option<Uuid>`, `Vec<Uuid>`, and maps
+/// keyed by `Uuid`, so those don't need wrapping element by element.
+pub mod as_base64 {
+    extern crate serde;
+
+    use self::serde::de::{Deserialize, Deserializer};
+    use self::serde::ser::{Serialize, Serializer};
+    use uuid::Uuid;
+
+    use UuidB64;
+
+    pub fn serialize<S>(uuid: &Uuid, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        UuidB64::from(*uuid).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Uuid, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        UuidB64::deserialize(deserializer).map(|id| id.uuid())
+    }
+
+    /// `Option<Uuid>`, with `None` as the format's usual null
+    pub mod option {
+        use super::serde::de::{Deserialize, Deserializer};
+        use super::serde::ser::{Serialize, Serializer};
+        use uuid::Uuid;
+
+        use UuidB64;
+
+        pub fn serialize<S>(uuid: &Option<Uuid>, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            uuid.map(UuidB64::from).serialize(serializer)
+        }
+
+        pub fn deserialize<'de, D>(deserializer: D) -> Result<Option<Uuid>, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            Option::<UuidB64>::deserialize(deserializer).map(|id| id.map(|id| id.uuid()))
+        }
+    }
+
+    /// `Vec<Uuid>`, as a sequence of IDs
+    pub mod vec {
+        use super::serde::de::{Deserialize, Deserializer};
+        use super::serde::ser::Serializer;
+        use uuid::Uuid;
+
+        use UuidB64;
+
+        pub fn serialize<S>(uuids: &[Uuid], serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            serializer.collect_seq(uuids.iter().map(|&uuid| UuidB64::from(uuid)))
+        }
+
+        pub fn deserialize<'de, D>(deserializer: D) -> Result<Vec<Uuid>, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            Vec::<UuidB64>::deserialize(deserializer)
+                .map(|ids| ids.into_iter().map(|id| id.uuid()).collect())
+        }
+    }
+
+    /// Maps keyed by `Uuid`, such as `HashMap<Uuid, T>` and
+    /// `BTreeMap<Uuid, T>`, with the keys as IDs
+    ///
+    /// The values are (de)serialized as usual. Any `FromIterator` map can be
+    /// deserialized, serializing takes a [`UuidMap`](trait.UuidMap.html).
+    pub mod map {
+        use std::collections::{BTreeMap, HashMap};
+        use std::fmt::{Formatter, Result as FmtResult};
+        use std::hash::BuildHasher;
+        use std::iter::{self, FromIterator};
+        use std::marker::PhantomData;
+
+        use super::serde::de::{Deserialize, Deserializer, MapAccess, Visitor};
+        use super::serde::ser::{Serialize, Serializer};
+        use uuid::Uuid;
+
+        use UuidB64;
+
+        /// A map keyed by `Uuid` that `serialize` accepts
+        ///
+        /// A trait rather than an `IntoIterator` bound, which can send type
+        /// inference into a loop when other crates in the build implement
+        /// `Iterator` recursively.
+        pub trait UuidMap {
+            fn serialize_ids<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error>;
+        }
+
+        impl<V: Serialize, H: BuildHasher> UuidMap for HashMap<Uuid, V, H> {
+            fn serialize_ids<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.collect_map(
+                    self.iter()
+                        .map(|(&uuid, value)| (UuidB64::from(uuid), value)),
+                )
+            }
+        }
+
+        impl<V: Serialize> UuidMap for BTreeMap<Uuid, V> {
+            fn serialize_ids<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.collect_map(
+                    self.iter()
+                        .map(|(&uuid, value)| (UuidB64::from(uuid), value)),
+                )
+            }
+        }
+
+        pub fn serialize<M, S>(map: &M, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            M: UuidMap,
+            S: Serializer,
+        {
+            map.serialize_ids(serializer)
+        }
+
+        pub fn deserialize<'de, M, V, D>(deserializer: D) -> Result<M, D::Error>
+        where
+            M: FromIterator<(Uuid, V)>,
+            V: Deserialize<'de>,
+            D: Deserializer<'de>,
+        {
+            deserializer.deserialize_map(MapVisitor(PhantomData))
+        }
+
+        struct MapVisitor<M, V>(PhantomData<(M, V)>);
+
+        impl<'de, M, V> Visitor<'de> for MapVisitor<M, V>
+        where
+            M: FromIterator<(Uuid, V)>,
+            V: Deserialize<'de>,
+        {
+            type Value = M;
+
+            fn expecting(&self, f: &mut Formatter) -> FmtResult {
+                write!(f, "a map keyed by Base64-encoded strings")
+            }
+
+            fn visit_map<A>(self, mut map: A) -> Result<M, A::Error>
+            where
+                A: MapAccess<'de>,
+            {
+                iter::from_fn(|| map.next_entry::<UuidB64, V>().transpose())
+                    .map(|entry| entry.map(|(id, value)| (id.uuid(), value)))
+                    .collect()
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::{BTreeMap, HashMap};
+
+    use uuid::Uuid;
+
+    use UuidB64;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Order {
+        #[serde(with = "::serde::as_base64")]
+        id: Uuid,
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Batch {
+        #[serde(with = "::serde::as_base64::option")]
+        parent: Option<Uuid>,
+        #[serde(with = "::serde::as_base64::vec")]
+        items: Vec<Uuid>,
+        #[serde(with = "::serde::as_base64::map")]
+        counts: HashMap<Uuid, u32>,
+        #[serde(with = "::serde::as_base64::map")]
+        names: BTreeMap<Uuid, String>,
+    }
+
+    #[test]
+    fn same_as_uuid_b64() {
+        let order = Order { id: Uuid::new_v4() };
+        let json = ::serde_json::to_string(&order).unwrap();
+        assert_eq!(json, format!(r#"{{"id":"{}"}}"#, UuidB64::from(order.id)));
+        assert_eq!(::serde_json::from_str::<Order>(&json).unwrap(), order);
+        assert!(::serde_json::from_str::<Order>(
+            r#"{"id":"b0c1ee86-6f46-4f1b-8d8b-7849e75dbcee"}"#
+        )
+        .is_err());
+
+        let encoded = ::bincode::serialize(&order).unwrap();
+        assert_eq!(
+            encoded,
+            ::bincode::serialize(&UuidB64::from(order.id)).unwrap()
+        );
+        assert_eq!(::bincode::deserialize::<Order>(&encoded).unwrap(), order);
+    }
+
+    #[test]
+    fn composites() {
+        let (a, b) = (Uuid::new_v4(), Uuid::new_v4());
+        let batch = Batch {
+            parent: Some(a),
+            items: vec![a, b],
+            counts: vec![(a, 1)].into_iter().collect(),
+            names: vec![(b, "b".to_owned())].into_iter().collect(),
+        };
+        let (a64, b64) = (UuidB64::from(a), UuidB64::from(b));
+        let json = ::serde_json::to_string(&batch).unwrap();
+        assert_eq!(
+            json,
+            format!(
+                r#"{{"parent":"{a}","items":["{a}","{b}"],"counts":{{"{a}":1}},"names":{{"{b}":"b"}}}}"#,
+                a = a64,
+                b = b64
+            )
+        );
+        assert_eq!(::serde_json::from_str::<Batch>(&json).unwrap(), batch);
+
+        let empty = Batch {
+            parent: None,
+            items: vec![],
+            counts: HashMap::new(),
+            names: BTreeMap::new(),
+        };
+        let json = ::serde_json::to_string(&empty).unwrap();
+        assert_eq!(json, r#"{"parent":null,"items":[],"counts":{},"names":{}}"#);
+        assert_eq!(::serde_json::from_str::<Batch>(&json).unwrap(), empty);
+
+        let encoded = ::bincode::serialize(&batch).unwrap();
+        assert_eq!(::bincode::deserialize::<Batch>(&encoded).unwrap(), batch);
+
+        assert!(::serde_json::from_str::<Batch>(
+            r#"{"parent":null,"items":[],"counts":{"nope":1},"names":{}}"#
+        )
+        .is_err());
+    }
+}
@@ -1,27 +1,49 @@
-extern crate serde;
+// `pub` for `define_id!`, which refers to serde through this crate
+pub extern crate serde;
 
+use std::convert::TryFrom;
 use std::fmt::{Formatter, Result as FmtResult};
+use std::marker::PhantomData;
+use std::str;
 
+use self::serde::de::{self, Deserialize, Deserializer, SeqAccess, Unexpected, Visitor};
 use self::serde::ser::{Serialize, Serializer};
-use self::serde::de::{self, Deserialize, Deserializer, Visitor};
+use uuid::Uuid;
 
-use super::UuidB64;
+use super::{
+    B64String, CachedUuidB64, EncodedUuid, Id, IdPrefix, MaybeUuidB64, PrefixedId, Redacted,
+    UuidB64, UuidB64Pair, UuidB64V4, UuidB64V7,
+};
+use encoding::Encoding;
 
+// Human-readable formats get the Base64 string, binary ones the 16 bytes
 impl Serialize for UuidB64 {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: Serializer,
     {
-        serializer.serialize_str(&self.to_istring())
+        if serializer.is_human_readable() {
+            serializer.serialize_str(&self.to_istring())
+        } else {
+            serializer.serialize_bytes(self.0.as_bytes())
+        }
     }
 }
 
+// Either form is accepted from either kind of format, as is a sequence of 16
+// bytes: other languages' serializers write IDs as MessagePack bins, CBOR
+// byte strings or JSON arrays. Human-readable formats are all
+// self-describing, so they can say which one they have.
 impl<'de> Deserialize<'de> for UuidB64 {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
         D: Deserializer<'de>,
     {
-        deserializer.deserialize_str(UuidB64Visitor)
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_any(UuidB64Visitor)
+        } else {
+            deserializer.deserialize_bytes(UuidB64Visitor)
+        }
     }
 }
 
@@ -31,22 +53,328 @@ impl<'de> Visitor<'de> for UuidB64Visitor {
     type Value = UuidB64;
 
     fn expecting(&self, f: &mut Formatter) -> FmtResult {
-        write!(f, "a URL-safe Base64-encoded string")
+        write!(f, "a Base64-encoded string or 16 bytes")
+    }
+
+    fn visit_str<E>(self, s: &str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        s.parse().map_err(de::Error::custom)
+    }
+
+    fn visit_bytes<E>(self, bytes: &[u8]) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        match bytes.len() {
+            16 => Ok(UuidB64(
+                Uuid::from_bytes(bytes).expect("UUIDs are 16 bytes"),
+            )),
+            // Written as a string, before binary formats got the raw bytes
+            22 => str::from_utf8(bytes)
+                .map_err(|_| de::Error::invalid_value(Unexpected::Bytes(bytes), &self))
+                .and_then(|s| self.visit_str(s)),
+            _ => Err(de::Error::invalid_length(bytes.len(), &self)),
+        }
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut bytes = [0; 16];
+        for (i, byte) in bytes.iter_mut().enumerate() {
+            *byte = seq
+                .next_element()?
+                .ok_or_else(|| de::Error::invalid_length(i, &self))?;
+        }
+        if seq.next_element::<de::IgnoredAny>()?.is_some() {
+            return Err(de::Error::invalid_length(17, &self));
+        }
+        Ok(UuidB64::from_bytes(bytes))
+    }
+}
+
+impl Serialize for MaybeUuidB64 {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self.0 {
+            Some(ref id) => id.serialize(serializer),
+            None if serializer.is_human_readable() => serializer.serialize_str(""),
+            None => serializer.serialize_bytes(&[]),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for MaybeUuidB64 {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_str(MaybeUuidB64Visitor)
+        } else {
+            deserializer.deserialize_bytes(MaybeUuidB64Visitor)
+        }
+    }
+}
+
+struct MaybeUuidB64Visitor;
+
+impl<'de> Visitor<'de> for MaybeUuidB64Visitor {
+    type Value = MaybeUuidB64;
+
+    fn expecting(&self, f: &mut Formatter) -> FmtResult {
+        write!(f, "an empty string or a Base64-encoded string")
+    }
+
+    fn visit_str<E>(self, s: &str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        s.parse().map_err(de::Error::custom)
+    }
+
+    fn visit_bytes<E>(self, bytes: &[u8]) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        if bytes.is_empty() {
+            Ok(MaybeUuidB64(None))
+        } else {
+            UuidB64Visitor.visit_bytes(bytes).map(MaybeUuidB64::from)
+        }
+    }
+}
+
+// The whole ID, it's only `Debug` that's redacted
+impl Serialize for Redacted {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.0.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Redacted {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        UuidB64::deserialize(deserializer).map(Redacted)
+    }
+}
+
+// Always a string, it's text in every format
+impl Serialize for B64String {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for B64String {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer
+            .deserialize_str(UuidB64Visitor)
+            .map(|id| id.to_inline())
+    }
+}
+
+// The same as `UuidB64`, without encoding again
+impl Serialize for CachedUuidB64 {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        if serializer.is_human_readable() {
+            serializer.serialize_str(self.as_str())
+        } else {
+            self.id().serialize(serializer)
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for CachedUuidB64 {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        UuidB64::deserialize(deserializer).map(CachedUuidB64::new)
+    }
+}
+
+impl Serialize for UuidB64Pair {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for UuidB64Pair {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_str(UuidB64PairVisitor)
+    }
+}
+
+struct UuidB64PairVisitor;
+
+impl<'de> Visitor<'de> for UuidB64PairVisitor {
+    type Value = UuidB64Pair;
+
+    fn expecting(&self, f: &mut Formatter) -> FmtResult {
+        write!(f, "two concatenated Base64-encoded UUIDs")
+    }
+
+    fn visit_str<E>(self, s: &str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        s.parse().map_err(de::Error::custom)
+    }
+}
+
+macro_rules! versioned_serde {
+    ($name:ident) => {
+        impl Serialize for $name {
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: Serializer,
+            {
+                self.as_uuid_b64().serialize(serializer)
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $name {
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+            where
+                D: Deserializer<'de>,
+            {
+                let id = UuidB64::deserialize(deserializer)?;
+                $name::try_from(id).map_err(de::Error::custom)
+            }
+        }
+    };
+}
+
+versioned_serde!(UuidB64V4);
+versioned_serde!(UuidB64V7);
+
+impl<T> Serialize for Id<T> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.as_uuid_b64().serialize(serializer)
+    }
+}
+
+impl<'de, T> Deserialize<'de> for Id<T> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        UuidB64::deserialize(deserializer).map(Id::from_uuid_b64)
+    }
+}
+
+impl<T: IdPrefix> Serialize for PrefixedId<T> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de, T: IdPrefix> Deserialize<'de> for PrefixedId<T> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_str(PrefixedIdVisitor(PhantomData))
+    }
+}
+
+struct PrefixedIdVisitor<T>(PhantomData<fn() -> T>);
+
+impl<'de, T: IdPrefix> Visitor<'de> for PrefixedIdVisitor<T> {
+    type Value = PrefixedId<T>;
+
+    fn expecting(&self, f: &mut Formatter) -> FmtResult {
+        write!(f, "a Base64-encoded UUID prefixed with '{}_'", T::PREFIX)
     }
 
     fn visit_str<E>(self, s: &str) -> Result<Self::Value, E>
     where
         E: de::Error,
     {
-        Ok(s.parse().map_err(de::Error::custom)?)
+        s.parse().map_err(de::Error::custom)
+    }
+}
+
+// Always a string in `E`, even in binary formats, since that's what the type
+// is for
+impl<E: Encoding> Serialize for EncodedUuid<E> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de, E: Encoding> Deserialize<'de> for EncodedUuid<E> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_str(EncodedUuidVisitor(PhantomData))
+    }
+}
+
+struct EncodedUuidVisitor<E>(PhantomData<fn() -> E>);
+
+impl<'de, E: Encoding> Visitor<'de> for EncodedUuidVisitor<E> {
+    type Value = EncodedUuid<E>;
+
+    fn expecting(&self, f: &mut Formatter) -> FmtResult {
+        write!(f, "a UUID in {} form", E::NAME)
+    }
+
+    fn visit_str<Er>(self, s: &str) -> Result<Self::Value, Er>
+    where
+        Er: de::Error,
+    {
+        s.parse().map_err(de::Error::custom)
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use std::convert::TryFrom;
+
     use uuid::Uuid;
 
-    use UuidB64;
+    use encoding::{Base62, Hyphenated};
+    use {
+        B64String, CachedUuidB64, EncodedUuid, Id, IdPrefix, MaybeUuidB64, PrefixedId, Redacted,
+        UuidB64, UuidB64Pair, UuidB64V4, UuidB64V7,
+    };
 
     #[test]
     fn ser_de() {
@@ -55,7 +383,11 @@ mod tests {
 
         let json = json!({ "myid": my_id }).to_string();
 
-        assert_eq!(json, r#"{"myid":"AAAA_wACAAMBAgMEBQYHCA"}"#);
+        if cfg!(feature = "standard-alphabet") {
+            assert_eq!(json, r#"{"myid":"AAAA/wACAAMBAgMEBQYHCA"}"#);
+        } else {
+            assert_eq!(json, r#"{"myid":"AAAA_wACAAMBAgMEBQYHCA"}"#);
+        }
 
         #[derive(Deserialize)]
         struct TestThing {
@@ -66,4 +398,178 @@ mod tests {
 
         assert_eq!(mything.myid, my_id);
     }
+
+    #[test]
+    fn binary_formats_get_raw_bytes() {
+        let id: UuidB64 = "sMHuhm9GTxuNi3hJ51287g".parse().unwrap();
+        let encoded = ::bincode::serialize(&id).unwrap();
+        // bincode prefixes bytes with a u64 length
+        assert_eq!(encoded.len(), 8 + 16);
+        assert_eq!(&encoded[8..], id.uuid().as_bytes());
+        assert_eq!(::bincode::deserialize::<UuidB64>(&encoded).unwrap(), id);
+
+        let versioned = ::bincode::serialize(&UuidB64V4::try_from(id).unwrap()).unwrap();
+        assert_eq!(versioned, encoded);
+    }
+
+    #[test]
+    fn binary_formats_read_old_strings() {
+        let id: UuidB64 = "sMHuhm9GTxuNi3hJ51287g".parse().unwrap();
+        let old = ::bincode::serialize("sMHuhm9GTxuNi3hJ51287g").unwrap();
+        assert_eq!(::bincode::deserialize::<UuidB64>(&old).unwrap(), id);
+        let short = ::bincode::serialize(&[1u8, 2, 3][..]).unwrap();
+        assert!(::bincode::deserialize::<UuidB64>(&short).is_err());
+    }
+
+    #[test]
+    fn deserializes_bytes_from_text_formats() {
+        use super::serde::de::value::{BytesDeserializer, Error};
+        use super::serde::Deserialize;
+
+        let id: UuidB64 = "sMHuhm9GTxuNi3hJ51287g".parse().unwrap();
+        let array = format!("{:?}", id.as_bytes());
+        assert_eq!(::serde_json::from_str::<UuidB64>(&array).unwrap(), id);
+        let bytes = BytesDeserializer::<Error>::new(id.as_bytes());
+        assert_eq!(UuidB64::deserialize(bytes).unwrap(), id);
+
+        assert!(::serde_json::from_str::<UuidB64>("[1, 2, 3]").is_err());
+        let long = format!("{}, 0]", &array[..array.len() - 1]);
+        assert!(::serde_json::from_str::<UuidB64>(&long).is_err());
+        assert!(::serde_json::from_str::<UuidB64>(
+            "[256, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]"
+        )
+        .is_err());
+        assert!(::serde_json::from_str::<UuidB64>("22").is_err());
+    }
+
+    #[test]
+    fn maybe_binary() {
+        for maybe in &[MaybeUuidB64(None), MaybeUuidB64(Some(UuidB64::new()))] {
+            let encoded = ::bincode::serialize(maybe).unwrap();
+            assert_eq!(
+                ::bincode::deserialize::<MaybeUuidB64>(&encoded).unwrap(),
+                *maybe
+            );
+        }
+    }
+
+    #[test]
+    fn maybe_ser_de() {
+        #[derive(Serialize, Deserialize)]
+        struct Row {
+            id: MaybeUuidB64,
+        }
+
+        let empty: Row = ::serde_json::from_str(r#"{"id":""}"#).unwrap();
+        assert_eq!(empty.id, MaybeUuidB64(None));
+        assert_eq!(::serde_json::to_string(&empty).unwrap(), r#"{"id":""}"#);
+
+        let id = UuidB64::new();
+        let json = ::serde_json::to_string(&Row { id: id.into() }).unwrap();
+        let full: Row = ::serde_json::from_str(&json).unwrap();
+        assert_eq!(full.id, MaybeUuidB64(Some(id)));
+    }
+
+    #[test]
+    fn redacted_ser_de() {
+        let id = UuidB64::new();
+        let json = ::serde_json::to_string(&id.redacted()).unwrap();
+        assert_eq!(json, ::serde_json::to_string(&id).unwrap());
+        assert_eq!(
+            ::serde_json::from_str::<Redacted>(&json).unwrap(),
+            id.redacted()
+        );
+    }
+
+    #[test]
+    fn cached_ser_de() {
+        let id = UuidB64::new();
+        let json = ::serde_json::to_string(&id.cached()).unwrap();
+        assert_eq!(json, ::serde_json::to_string(&id).unwrap());
+        assert_eq!(
+            ::serde_json::from_str::<CachedUuidB64>(&json).unwrap(),
+            id.cached()
+        );
+
+        let encoded = ::bincode::serialize(&id.cached()).unwrap();
+        assert_eq!(encoded, ::bincode::serialize(&id).unwrap());
+        assert_eq!(
+            ::bincode::deserialize::<CachedUuidB64>(&encoded).unwrap(),
+            id.cached()
+        );
+    }
+
+    #[test]
+    fn pair_ser_de() {
+        let pair = UuidB64Pair(UuidB64::new(), UuidB64::new());
+        let json = ::serde_json::to_string(&pair).unwrap();
+        assert_eq!(json, format!(r#""{}{}""#, pair.0, pair.1));
+        assert_eq!(::serde_json::from_str::<UuidB64Pair>(&json).unwrap(), pair);
+    }
+
+    #[test]
+    fn inline_ser_de() {
+        let text = UuidB64::new().to_inline();
+        let json = ::serde_json::to_string(&text).unwrap();
+        assert_eq!(json, format!(r#""{}""#, text));
+        assert_eq!(::serde_json::from_str::<B64String>(&json).unwrap(), text);
+        assert!(::serde_json::from_str::<B64String>(r#""nope""#).is_err());
+        // a string in binary formats too
+        let encoded = ::bincode::serialize(&text).unwrap();
+        assert_eq!(encoded, ::bincode::serialize(text.as_str()).unwrap());
+        assert_eq!(::bincode::deserialize::<B64String>(&encoded).unwrap(), text);
+    }
+
+    #[test]
+    fn versioned_ser_de() {
+        let id = UuidB64V4::new();
+        let json = ::serde_json::to_string(&id).unwrap();
+        assert_eq!(json, format!(r#""{}""#, id));
+        assert_eq!(::serde_json::from_str::<UuidB64V4>(&json).unwrap(), id);
+        assert!(::serde_json::from_str::<UuidB64V7>(&json).is_err());
+    }
+
+    #[test]
+    fn typed_ser_de() {
+        struct User;
+
+        let id = Id::<User>::new();
+        let json = ::serde_json::to_string(&id).unwrap();
+        assert_eq!(json, format!(r#""{}""#, id));
+        assert_eq!(::serde_json::from_str::<Id<User>>(&json).unwrap(), id);
+    }
+
+    #[test]
+    fn prefixed_ser_de() {
+        struct User;
+
+        impl IdPrefix for User {
+            const PREFIX: &'static str = "usr";
+        }
+
+        let id = PrefixedId::<User>::new();
+        let json = ::serde_json::to_string(&id).unwrap();
+        assert_eq!(json, format!(r#""usr_{}""#, id.id()));
+        assert_eq!(
+            ::serde_json::from_str::<PrefixedId<User>>(&json).unwrap(),
+            id
+        );
+        let unprefixed = ::serde_json::to_string(&id.id()).unwrap();
+        assert!(::serde_json::from_str::<PrefixedId<User>>(&unprefixed).is_err());
+    }
+
+    #[test]
+    fn encoded_ser_de() {
+        let id: UuidB64 = "sMHuhm9GTxuNi3hJ51287g".parse().unwrap();
+        let hyphenated = EncodedUuid::<Hyphenated>::from(id);
+        let json = ::serde_json::to_string(&hyphenated).unwrap();
+        assert_eq!(json, r#""b0c1ee86-6f46-4f1b-8d8b-7849e75dbcee""#);
+        assert_eq!(
+            ::serde_json::from_str::<EncodedUuid<Hyphenated>>(&json).unwrap(),
+            hyphenated
+        );
+        let json = ::serde_json::to_string(&hyphenated.reencode::<Base62>()).unwrap();
+        assert_eq!(json, r#""5NXH9G03Qou9vbZ5Nk2VBO""#);
+        assert!(::serde_json::from_str::<EncodedUuid<Hyphenated>>(&json).is_err());
+    }
 }
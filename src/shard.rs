@@ -0,0 +1,82 @@
+//! Mapping IDs onto buckets
+
+use UuidB64;
+
+impl UuidB64 {
+    /// Map this ID to one of `num_shards` buckets, numbered from zero
+    ///
+    /// The algorithm is part of this crate's stability guarantee and will
+    /// never change: the last 8 bytes of the UUID are read as a big-endian
+    /// `u64`, and the result is that number modulo `num_shards`.
+    ///
+    /// The last 8 bytes are used because they are random for both v4 and v7
+    /// UUIDs (apart from the two fixed variant bits, which don't skew the
+    /// result), where the first bytes of a v7 UUID are its timestamp and
+    /// would put all recent IDs in the same bucket.
+    ///
+    /// To reproduce this elsewhere, in SQL for example:
+    ///
+    /// ```sql
+    /// ('x' || right(replace(id::text, '-', ''), 16))::bit(64)::bigint
+    /// ```
+    ///
+    /// gives the same 64 bits as a *signed* number, so take it modulo
+    /// `num_shards` as unsigned arithmetic.
+    ///
+    /// # Panics
+    ///
+    /// If `num_shards` is zero.
+    ///
+    /// ```
+    /// # use uuid_b64::UuidB64;
+    /// let id: UuidB64 = "sMHuhm9GTxuNi3hJ51287g".parse().unwrap();
+    /// assert_eq!(id.shard(16), 14);
+    /// assert!(UuidB64::new().shard(3) < 3);
+    /// ```
+    pub fn shard(&self, num_shards: u32) -> u32 {
+        assert!(num_shards > 0, "there must be at least one shard");
+        let mut tail = [0; 8];
+        tail.copy_from_slice(&self.0.as_bytes()[8..]);
+        (u64::from_be_bytes(tail) % u64::from(num_shards)) as u32
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use uuid::Uuid;
+
+    use UuidB64;
+
+    fn known() -> UuidB64 {
+        UuidB64::from(Uuid::parse_str("b0c1ee86-6f46-4f1b-8d8b-7849e75dbcee").unwrap())
+    }
+
+    #[test]
+    fn output_is_pinned() {
+        // 0x8d8b7849e75dbcee == 10199378039920245998
+        let id = known();
+        assert_eq!(id.shard(1), 0);
+        assert_eq!(id.shard(2), 0);
+        assert_eq!(id.shard(7), (10_199_378_039_920_245_998u64 % 7) as u32);
+        assert_eq!(id.shard(16), 14);
+        assert_eq!(id.shard(1000), 998);
+        assert_eq!(id.shard(u32::MAX), (10_199_378_039_920_245_998u64 % 0xffff_ffff) as u32);
+    }
+
+    #[test]
+    fn distributes_v4_ids() {
+        let mut counts = [0; 4];
+        for _ in 0..4000 {
+            counts[UuidB64::new().shard(4) as usize] += 1;
+        }
+        for &count in &counts {
+            assert!(count > 800, "unbalanced shards: {:?}", counts);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn zero_shards_panics() {
+        known().shard(0);
+    }
+}
@@ -0,0 +1,232 @@
+//! Tamper-evident IDs carrying a truncated HMAC
+
+extern crate hmac;
+extern crate sha2;
+
+use std::fmt::{Display, Formatter, Result as FmtResult};
+
+use self::hmac::{Hmac, Mac};
+use self::sha2::Sha256;
+use base64;
+
+use errors::ErrorKind;
+use {UuidB64, B64_CONFIG};
+
+/// Bytes of HMAC-SHA256 output that are kept
+const TAG_LEN: usize = 8;
+/// Length of the tag once encoded
+const ENCODED_TAG_LEN: usize = 11;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// An ID with a keyed signature, like `user_sMHuhm9GTxuNi3hJ51287g.gV0nKNH3RjE`
+///
+/// The signature is the first 64 bits of an HMAC-SHA256 over the text before
+/// the `.` (including the optional prefix), so IDs exposed in URLs or emails
+/// can be checked for tampering without a database lookup. It is *not*
+/// encryption: the ID itself is in plain sight.
+///
+/// Verification happens on parse, and compares tags in constant time.
+///
+/// ```
+/// # use uuid_b64::{SignedId, UuidB64};
+/// let key = b"super secret key";
+/// let id = UuidB64::new();
+/// let token = SignedId::sign_prefixed("user", id, key).to_string();
+///
+/// let verified = SignedId::verify(&token, key).unwrap();
+/// assert_eq!(verified.id(), id);
+/// assert_eq!(verified.prefix(), Some("user"));
+/// assert!(SignedId::verify(&token, b"some other key").is_err());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SignedId {
+    prefix: Option<String>,
+    id: UuidB64,
+    tag: [u8; TAG_LEN],
+}
+
+impl SignedId {
+    /// Sign an ID
+    pub fn sign(id: UuidB64, key: &[u8]) -> SignedId {
+        SignedId::sign_inner(None, id, key)
+    }
+
+    /// Sign an ID which will be displayed with a prefix, like `user_...`
+    ///
+    /// The prefix is covered by the signature, so an ID signed as a `user`
+    /// can't be passed off as an `order`.
+    ///
+    /// # Panics
+    ///
+    /// If the prefix contains a `.`, which would make the token ambiguous.
+    pub fn sign_prefixed(prefix: &str, id: UuidB64, key: &[u8]) -> SignedId {
+        assert!(!prefix.contains('.'), "prefixes cannot contain '.'");
+        SignedId::sign_inner(Some(prefix.into()), id, key)
+    }
+
+    fn sign_inner(prefix: Option<String>, id: UuidB64, key: &[u8]) -> SignedId {
+        let mut signed = SignedId {
+            prefix,
+            id,
+            tag: [0; TAG_LEN],
+        };
+        let mac = mac_for(&signed.body(), key).finalize().into_bytes();
+        signed.tag.copy_from_slice(&mac[..TAG_LEN]);
+        signed
+    }
+
+    /// Parse a signed ID, checking the signature with `key`
+    pub fn verify(s: &str, key: &[u8]) -> Result<SignedId, ErrorKind> {
+        let invalid = || ErrorKind::ParseError(s.into());
+        let dot = s.rfind('.').ok_or_else(invalid)?;
+        let (body, encoded_tag) = (&s[..dot], &s[dot + 1..]);
+        // the prefix can be any text, so the ID might not start on a char
+        // boundary
+        if body.len() < 22
+            || !body.is_char_boundary(body.len() - 22)
+            || encoded_tag.len() != ENCODED_TAG_LEN
+        {
+            return Err(invalid());
+        }
+        let (prefix, id) = body.split_at(body.len() - 22);
+        let prefix = match prefix.len() {
+            0 => None,
+            len if prefix.ends_with('_') => Some(prefix[..len - 1].to_string()),
+            _ => return Err(invalid()),
+        };
+        let id = id.parse().map_err(|_| invalid())?;
+        let tag = base64::decode_config(encoded_tag, *B64_CONFIG).map_err(|_| invalid())?;
+        // the last character has 2 bits to spare, only accept them as zeros
+        if base64::encode_config(&tag, *B64_CONFIG) != encoded_tag {
+            return Err(invalid());
+        }
+
+        mac_for(body, key)
+            .verify_truncated_left(&tag)
+            .map_err(|_| ErrorKind::InvalidSignature(s.into()))?;
+
+        let mut signed = SignedId {
+            prefix,
+            id,
+            tag: [0; TAG_LEN],
+        };
+        signed.tag.copy_from_slice(&tag);
+        Ok(signed)
+    }
+
+    /// The signed ID
+    pub fn id(&self) -> UuidB64 {
+        self.id
+    }
+
+    /// The prefix, if this was signed with one
+    pub fn prefix(&self) -> Option<&str> {
+        self.prefix.as_ref().map(|p| &p[..])
+    }
+
+    /// The part of the token that the tag covers
+    fn body(&self) -> String {
+        match self.prefix {
+            Some(ref prefix) => format!("{}_{}", prefix, self.id),
+            None => self.id.to_string(),
+        }
+    }
+}
+
+fn mac_for(body: &str, key: &[u8]) -> HmacSha256 {
+    let mut mac = HmacSha256::new_from_slice(key).expect("HMAC accepts keys of any length");
+    mac.update(body.as_bytes());
+    mac
+}
+
+impl Display for SignedId {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        write!(
+            f,
+            "{}.{}",
+            self.body(),
+            base64::encode_config(&self.tag, *B64_CONFIG)
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SignedId;
+    use errors::ErrorKind;
+    use UuidB64;
+
+    const KEY: &[u8] = b"test key";
+
+    #[test]
+    fn roundtrips() {
+        let id = UuidB64::new();
+        let token = SignedId::sign(id, KEY).to_string();
+        assert_eq!(token.len(), 22 + 1 + 11);
+        let verified = SignedId::verify(&token, KEY).unwrap();
+        assert_eq!(verified.id(), id);
+        assert_eq!(verified.prefix(), None);
+        assert_eq!(verified.to_string(), token);
+    }
+
+    #[test]
+    fn prefixes_are_signed() {
+        let id = UuidB64::new();
+        let token = SignedId::sign_prefixed("user", id, KEY).to_string();
+        assert!(token.starts_with(&format!("user_{}.", id)));
+        assert_eq!(SignedId::verify(&token, KEY).unwrap().prefix(), Some("user"));
+
+        let forged = token.replacen("user", "team", 1);
+        match SignedId::verify(&forged, KEY) {
+            Err(ErrorKind::InvalidSignature(_)) => {}
+            other => panic!("forgery was accepted: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn detects_tampering() {
+        let token = SignedId::sign(UuidB64::new(), KEY).to_string();
+        let other_id = UuidB64::new().to_string();
+        let tampered = format!("{}{}", other_id, &token[22..]);
+        assert!(SignedId::verify(&tampered, KEY).is_err());
+        assert!(SignedId::verify(&token, b"wrong key").is_err());
+    }
+
+    #[test]
+    fn rejects_malformed() {
+        let token = SignedId::sign(UuidB64::new(), KEY).to_string();
+        assert!(SignedId::verify(&token[..22], KEY).is_err());
+        assert!(SignedId::verify(&token[1..], KEY).is_err());
+        assert!(SignedId::verify(&format!("x{}", token), KEY).is_err());
+        assert!(SignedId::verify(&token[..token.len() - 1], KEY).is_err());
+    }
+
+    #[test]
+    fn rejects_non_ascii_without_panicking() {
+        let id = UuidB64::new().to_string();
+        let token = SignedId::sign(UuidB64::new(), KEY).to_string();
+        let tag = &token[23..];
+        // the `é` straddles the start of the last 22 bytes
+        let straddling = format!("x\u{e9}{}.{}", &id[1..], tag);
+        assert!(SignedId::verify(&straddling, KEY).is_err());
+        assert!(SignedId::verify(&format!("{}\u{e9}.{}", &id[..21], tag), KEY).is_err());
+
+        let prefixed = SignedId::sign_prefixed("caf\u{e9}", UuidB64::new(), KEY);
+        assert_eq!(
+            SignedId::verify(&prefixed.to_string(), KEY).unwrap(),
+            prefixed
+        );
+    }
+
+    #[test]
+    fn only_accepts_the_canonical_tag() {
+        let token = SignedId::sign(UuidB64::new(), KEY).to_string();
+        let (rest, last) = token.split_at(token.len() - 1);
+        let symbols = "ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_+/";
+        for c in symbols.chars().filter(|&c| c.to_string() != last) {
+            let other = format!("{}{}", rest, c);
+            assert!(SignedId::verify(&other, KEY).is_err(), "{}", other);
+        }
+    }
+}
@@ -0,0 +1,82 @@
+//! slog support
+//!
+//! IDs are logged as their 22 Base64 characters with `emit_str`, so they can
+//! go straight into key-value pairs, `info!(log, "created"; "user" => id)`,
+//! without formatting them into a `String` first.
+
+extern crate slog;
+
+use self::slog::{Key, Record, Result, Serializer, Value};
+
+use b64_string::B64String;
+use UuidB64;
+
+impl Value for UuidB64 {
+    fn serialize(&self, _: &Record<'_>, key: Key, serializer: &mut dyn Serializer) -> Result {
+        serializer.emit_str(key, self.encode_to(&mut [0; 22]))
+    }
+}
+
+impl Value for B64String {
+    fn serialize(&self, _: &Record<'_>, key: Key, serializer: &mut dyn Serializer) -> Result {
+        serializer.emit_str(key, self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fmt::Arguments;
+    use std::sync::{Arc, Mutex};
+
+    use super::slog::{self, Drain, Key, Logger, OwnedKVList, Record, Serializer, KV};
+
+    use UuidB64;
+
+    /// Records every key-value pair as a `(key, value, emitted as a str)`
+    #[derive(Clone, Default)]
+    struct Capture(Arc<Mutex<Vec<(String, String, bool)>>>);
+
+    impl Serializer for Capture {
+        fn emit_str(&mut self, key: Key, val: &str) -> slog::Result {
+            let mut pairs = self.0.lock().unwrap();
+            pairs.push((key.to_string(), val.to_owned(), true));
+            Ok(())
+        }
+
+        fn emit_arguments(&mut self, key: Key, val: &Arguments<'_>) -> slog::Result {
+            let mut pairs = self.0.lock().unwrap();
+            pairs.push((key.to_string(), val.to_string(), false));
+            Ok(())
+        }
+    }
+
+    impl Drain for Capture {
+        type Ok = ();
+        type Err = slog::Never;
+
+        fn log(&self, record: &Record<'_>, values: &OwnedKVList) -> Result<(), slog::Never> {
+            let mut serializer = self.clone();
+            record.kv().serialize(record, &mut serializer).unwrap();
+            values.serialize(record, &mut serializer).unwrap();
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn logs_the_text_form() {
+        let capture = Capture::default();
+        let (id, parent) = (UuidB64::new(), UuidB64::new());
+        let log = Logger::root(capture.clone(), slog::o!("parent" => parent));
+        slog::info!(log, "created"; "id" => id, "inline" => id.to_inline());
+
+        let pairs = capture.0.lock().unwrap();
+        assert_eq!(
+            *pairs,
+            vec![
+                ("inline".to_string(), id.to_string(), true),
+                ("id".to_string(), id.to_string(), true),
+                ("parent".to_string(), parent.to_string(), true),
+            ]
+        );
+    }
+}
@@ -0,0 +1,276 @@
+//! Truncated IDs for short URLs and logs
+
+use std::fmt::{Debug, Display, Formatter, Result as FmtResult};
+use std::str::{self, FromStr};
+
+use encoder::BUILD_ALPHABET;
+use errors::ErrorKind;
+use UuidB64;
+
+/// The first `N` characters of an ID's text form
+///
+/// For URLs where even 22 characters is too long. A slug does not identify
+/// an ID on its own: different IDs can share a slug, so it has to be
+/// resolved with a lookup (`WHERE id_text LIKE 'slug%'`, or a stored slug
+/// column) and the lookup has to handle getting more than one match, for
+/// example by falling back to the full ID. `matches` checks a candidate.
+///
+/// `N` must be between 1 and 21, using it with anything else fails to
+/// compile:
+///
+/// ```compile_fail
+/// # use uuid_b64::{ShortSlug, UuidB64};
+/// let slug: ShortSlug<22> = UuidB64::new().short_slug();
+/// ```
+///
+/// # Collisions
+///
+/// Each character holds 6 bits, but for v4 IDs the version (4 bits, in the
+/// 9th character) and variant (2 bits, in the 11th character) are fixed, so
+/// the random bits in a slug are:
+///
+/// | `N` | random bits | IDs for a 1% chance of any collision | for 50% |
+/// |-----|-------------|--------------------------------------|---------|
+/// | 4   | 24          | 580                                  | 4,800   |
+/// | 6   | 36          | 37,000                               | 310,000 |
+/// | 8   | 48          | 2.4 million                          | 20 million |
+/// | 10  | 56          | 38 million                           | 320 million |
+/// | 12  | 66          | 1.2 billion                          | 10 billion |
+///
+/// These are birthday bounds, the chance that *some* pair of slugs among
+/// that many IDs collides. The chance that a particular new ID collides
+/// with one of `k` existing ones is only about `k / 2^bits`.
+///
+/// Don't use slugs of v7 (or any time-ordered) IDs: their leading
+/// characters are a timestamp, so IDs created close together share them.
+///
+/// ```
+/// # use uuid_b64::{ShortSlug, UuidB64};
+/// let id: UuidB64 = "sMHuhm9GTxuNi3hJ51287g".parse().unwrap();
+/// let slug: ShortSlug<8> = id.short_slug();
+/// assert_eq!(slug.to_string(), "sMHuhm9G");
+/// assert!(slug.matches(&id));
+///
+/// let parsed: ShortSlug<8> = "sMHuhm9G".parse().unwrap();
+/// assert_eq!(parsed, slug);
+/// assert!("sMHuhm9".parse::<ShortSlug<8>>().is_err());
+/// ```
+#[derive(Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ShortSlug<const N: usize>([u8; N]);
+
+impl<const N: usize> ShortSlug<N> {
+    const VALID_LEN: () = assert!(N >= 1 && N <= 21, "ShortSlug length must be 1 to 21");
+
+    /// The slug for `id`
+    pub fn new(id: &UuidB64) -> ShortSlug<N> {
+        #[allow(clippy::let_unit_value)]
+        let () = Self::VALID_LEN;
+        let mut chars = [0; N];
+        chars.copy_from_slice(&id.to_istring().as_bytes()[..N]);
+        ShortSlug(chars)
+    }
+
+    /// Whether `id` has this slug
+    pub fn matches(&self, id: &UuidB64) -> bool {
+        id.to_istring().as_bytes().starts_with(&self.0)
+    }
+
+    /// The slug's characters
+    pub fn as_str(&self) -> &str {
+        str::from_utf8(&self.0).expect("slugs are always ASCII")
+    }
+}
+
+impl UuidB64 {
+    /// The first `N` characters of this ID, see `ShortSlug`
+    pub fn short_slug<const N: usize>(&self) -> ShortSlug<N> {
+        ShortSlug::new(self)
+    }
+}
+
+/// The first few characters of an ID, for showing to people
+///
+/// Made by [`UuidB64::short`](struct.UuidB64.html#method.short). Unlike
+/// `ShortSlug` the length is picked at runtime, and this is only meant to be
+/// displayed: look IDs back up with
+/// [`matches_prefix`](struct.UuidB64.html#method.matches_prefix).
+#[derive(Copy, Clone, PartialEq, Eq, Hash)]
+pub struct ShortId {
+    chars: [u8; 22],
+    len: usize,
+}
+
+impl ShortId {
+    /// The characters, without the allocation `to_string` would make
+    pub fn as_str(&self) -> &str {
+        str::from_utf8(&self.chars[..self.len]).expect("IDs are always ASCII")
+    }
+}
+
+impl Display for ShortId {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        f.pad(self.as_str())
+    }
+}
+
+impl Debug for ShortId {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        write!(f, "ShortId({})", self.as_str())
+    }
+}
+
+impl UuidB64 {
+    /// A good length for [`short`](#method.short): 48 random bits for v4 IDs,
+    /// see `ShortSlug` for the odds of collisions
+    ///
+    /// Not for v7 IDs, whose first 8 characters are exactly their millisecond
+    /// timestamp: every v7 ID made in the same millisecond has the same short
+    /// form. Show them in full.
+    pub const SHORT_LEN: usize = 8;
+
+    /// The first `n` characters of this ID, for logs and UIs, like a git
+    /// short hash
+    ///
+    /// `n` past 22 gives the whole ID. Formatting the result doesn't allocate.
+    ///
+    /// ```
+    /// # use uuid_b64::UuidB64;
+    /// let id: UuidB64 = "sMHuhm9GTxuNi3hJ51287g".parse().unwrap();
+    /// assert_eq!(id.short(UuidB64::SHORT_LEN).to_string(), "sMHuhm9G");
+    /// assert_eq!(format!("item {}", id.short(4)), "item sMHu");
+    /// assert_eq!(id.short(30).to_string(), id.to_string());
+    /// ```
+    pub fn short(&self, n: usize) -> ShortId {
+        ShortId {
+            chars: self.to_b64_array(),
+            len: n.min(22),
+        }
+    }
+
+    /// Whether this ID's text form starts with `prefix`
+    ///
+    /// For finding an item from an abbreviated ID that someone typed or
+    /// copied out of a log. Several IDs can match the same prefix, so check
+    /// that exactly one does. Every ID matches the empty string.
+    ///
+    /// ```
+    /// # use uuid_b64::UuidB64;
+    /// let id: UuidB64 = "sMHuhm9GTxuNi3hJ51287g".parse().unwrap();
+    /// assert!(id.matches_prefix("sMHuhm9G"));
+    /// assert!(!id.matches_prefix("sMHuhm9g"));
+    /// ```
+    pub fn matches_prefix(&self, prefix: &str) -> bool {
+        prefix.len() <= 22 && self.to_b64_array().starts_with(prefix.as_bytes())
+    }
+}
+
+impl<const N: usize> From<UuidB64> for ShortSlug<N> {
+    fn from(id: UuidB64) -> ShortSlug<N> {
+        ShortSlug::new(&id)
+    }
+}
+
+impl<const N: usize> FromStr for ShortSlug<N> {
+    type Err = ErrorKind;
+
+    fn from_str(s: &str) -> Result<ShortSlug<N>, ErrorKind> {
+        #[allow(clippy::let_unit_value)]
+        let () = Self::VALID_LEN;
+        if s.len() != N || !s.chars().all(|c| BUILD_ALPHABET.value_of(c).is_some()) {
+            return Err(ErrorKind::ParseError(s.to_owned()));
+        }
+        let mut chars = [0; N];
+        chars.copy_from_slice(s.as_bytes());
+        Ok(ShortSlug(chars))
+    }
+}
+
+impl<const N: usize> Display for ShortSlug<N> {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        f.write_str(self.as_str())
+    }
+}
+
+impl<const N: usize> Debug for ShortSlug<N> {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        write!(f, "ShortSlug({})", self.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ShortSlug;
+    use UuidB64;
+
+    fn known() -> UuidB64 {
+        "sMHuhm9GTxuNi3hJ51287g".parse().unwrap()
+    }
+
+    #[test]
+    fn lengths() {
+        let id = known();
+        assert_eq!(id.short_slug::<1>().as_str(), "s");
+        assert_eq!(id.short_slug::<21>().as_str(), "sMHuhm9GTxuNi3hJ51287");
+        assert_eq!(format!("{:?}", id.short_slug::<4>()), "ShortSlug(sMHu)");
+    }
+
+    #[test]
+    fn matches_only_prefixes() {
+        let id = known();
+        let slug = ShortSlug::<6>::from(id);
+        assert!(slug.matches(&id));
+        assert!(!"sMHuhn".parse::<ShortSlug<6>>().unwrap().matches(&id));
+        let other = UuidB64::new();
+        assert_eq!(
+            slug.matches(&other),
+            other.to_string().starts_with("sMHuhm")
+        );
+    }
+
+    #[test]
+    fn parse_rejects_bad_input() {
+        assert!("sMHuhm".parse::<ShortSlug<6>>().is_ok());
+        assert!("sMHuh".parse::<ShortSlug<6>>().is_err());
+        assert!("sMHuhm9".parse::<ShortSlug<6>>().is_err());
+        assert!("sMH.hm".parse::<ShortSlug<6>>().is_err());
+        assert!("sMHühm".parse::<ShortSlug<6>>().is_err());
+        if cfg!(feature = "standard-alphabet") {
+            assert!("sMH+/m".parse::<ShortSlug<6>>().is_ok());
+            assert!("sMH-_m".parse::<ShortSlug<6>>().is_err());
+        } else {
+            assert!("sMH-_m".parse::<ShortSlug<6>>().is_ok());
+            assert!("sMH+/m".parse::<ShortSlug<6>>().is_err());
+        }
+    }
+
+    #[test]
+    fn roundtrips_through_display() {
+        let slug = UuidB64::new().short_slug::<10>();
+        assert_eq!(slug.to_string().parse::<ShortSlug<10>>().unwrap(), slug);
+    }
+
+    #[test]
+    fn short_ids() {
+        let id = known();
+        assert_eq!(id.short(0).as_str(), "");
+        assert_eq!(id.short(1).as_str(), "s");
+        assert_eq!(id.short(21).as_str(), id.short_slug::<21>().as_str());
+        assert_eq!(id.short(22).as_str(), id.to_string());
+        assert_eq!(id.short(usize::MAX), id.short(22));
+        assert_eq!(format!("{:?}", id.short(4)), "ShortId(sMHu)");
+        assert_eq!(format!("[{:>6}]", id.short(4)), "[  sMHu]");
+        assert_eq!(id.short(UuidB64::SHORT_LEN).as_str(), "sMHuhm9G");
+    }
+
+    #[test]
+    fn matches_prefixes() {
+        let id = known();
+        let full = id.to_string();
+        for n in 0..=22 {
+            assert!(id.matches_prefix(&full[..n]));
+        }
+        assert!(!id.matches_prefix("sMHuhn"));
+        assert!(!id.matches_prefix(&format!("{}g", full)));
+        assert!(!id.matches_prefix("sMHü"));
+    }
+}
@@ -0,0 +1,36 @@
+extern crate specta;
+
+use self::specta::datatype::DataType;
+use self::specta::{Type, Types};
+
+use UuidB64;
+
+/// `UuidB64`s are exported as `string`, the same as specta does for
+/// `uuid::Uuid`, since that's what they serialize to
+///
+/// The string is always 22 characters of URL-safe base64 without padding
+/// (or standard base64 with the `standard-alphabet` feature), so frontend
+/// code can pass it straight back to Tauri commands expecting a `UuidB64`.
+impl Type for UuidB64 {
+    fn definition(types: &mut Types) -> DataType {
+        <str as Type>::definition(types)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::specta::datatype::{DataType, Primitive};
+    use super::specta::{Type, Types};
+
+    use UuidB64;
+
+    #[test]
+    fn exported_as_a_string() {
+        let mut types = Types::default();
+        match UuidB64::definition(&mut types) {
+            DataType::Primitive(Primitive::str) => {}
+            other => panic!("expected a string, got {:?}", other),
+        }
+        assert!(types.is_empty());
+    }
+}
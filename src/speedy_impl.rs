@@ -0,0 +1,70 @@
+//! speedy serialization, as the 16 raw bytes
+
+extern crate speedy;
+
+use self::speedy::{Context, Readable, Reader, Writable, Writer};
+
+use UuidB64;
+
+impl<'a, C: Context> Readable<'a, C> for UuidB64 {
+    fn read_from<R: Reader<'a, C>>(reader: &mut R) -> Result<Self, C::Error> {
+        let mut bytes = [0; 16];
+        reader.read_bytes(&mut bytes)?;
+        Ok(UuidB64::from_bytes(bytes))
+    }
+
+    fn minimum_bytes_needed() -> usize {
+        16
+    }
+}
+
+impl<C: Context> Writable<C> for UuidB64 {
+    fn write_to<T: ?Sized + Writer<C>>(&self, writer: &mut T) -> Result<(), C::Error> {
+        writer.write_bytes(self.0.as_bytes())
+    }
+
+    fn bytes_needed(&self) -> Result<usize, C::Error> {
+        Ok(16)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::speedy::{self, BigEndian, LittleEndian, Readable, Writable};
+
+    use UuidB64;
+
+    #[derive(Debug, PartialEq, Readable, Writable)]
+    struct Record {
+        id: UuidB64,
+        parent: Option<UuidB64>,
+        size: u32,
+    }
+
+    #[test]
+    fn fixed_16_bytes() {
+        let id: UuidB64 = "sMHuhm9GTxuNi3hJ51287g".parse().unwrap();
+        let encoded = id.write_to_vec().unwrap();
+        assert_eq!(&encoded[..], id.uuid().as_bytes());
+        // the bytes don't depend on endianness
+        assert_eq!(
+            id.write_to_vec_with_ctx(BigEndian::default()).unwrap(),
+            id.write_to_vec_with_ctx(LittleEndian::default()).unwrap()
+        );
+        assert_eq!(UuidB64::read_from_buffer(&encoded).unwrap(), id);
+        assert!(UuidB64::read_from_buffer(&encoded[..15]).is_err());
+    }
+
+    #[test]
+    fn in_records() {
+        let record = Record {
+            id: UuidB64::new(),
+            parent: Some(UuidB64::new()),
+            size: 7,
+        };
+        let encoded = record.write_to_vec().unwrap();
+        // two IDs, an option tag and a u32
+        assert_eq!(encoded.len(), 16 + 1 + 16 + 4);
+        assert_eq!(Record::read_from_buffer(&encoded).unwrap(), record);
+    }
+}
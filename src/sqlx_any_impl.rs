@@ -0,0 +1,118 @@
+//! Support for sqlx's `Any` driver
+//!
+//! `Any` has no UUID type, so IDs are bound as hyphenated UUID text, which
+//! every backend can store and compare: a `TEXT` column on SQLite, `CHAR(36)`
+//! on MySQL. On Postgres, cast parameters into `uuid` columns (`$1::uuid`)
+//! and select them back out as text (`id::text`), since `Any` can't read
+//! `uuid` columns either.
+//!
+//! Reading is more forgiving: hyphenated or simple UUID text, our base64
+//! text, or a 16-byte blob (SQLite `BLOB`, MySQL `BINARY(16)`) all decode.
+
+extern crate sqlx;
+
+use self::sqlx::any::{Any, AnyTypeInfo, AnyTypeInfoKind, AnyValueRef};
+use self::sqlx::encode::IsNull;
+use self::sqlx::error::BoxDynError;
+use self::sqlx::{Decode, Encode, Type, ValueRef};
+
+use uuid::Uuid;
+
+use UuidB64;
+
+impl Type<Any> for UuidB64 {
+    fn type_info() -> AnyTypeInfo {
+        <String as Type<Any>>::type_info()
+    }
+
+    fn compatible(ty: &AnyTypeInfo) -> bool {
+        matches!(ty.kind, AnyTypeInfoKind::Text | AnyTypeInfoKind::Blob)
+    }
+}
+
+impl<'q> Encode<'q, Any> for UuidB64 {
+    fn encode_by_ref(
+        &self,
+        buf: &mut <Any as sqlx::Database>::ArgumentBuffer<'q>,
+    ) -> Result<IsNull, BoxDynError> {
+        Encode::<Any>::encode(self.0.hyphenated().to_string(), buf)
+    }
+}
+
+impl<'r> Decode<'r, Any> for UuidB64 {
+    fn decode(value: AnyValueRef<'r>) -> Result<Self, BoxDynError> {
+        match value.type_info().kind {
+            AnyTypeInfoKind::Blob => {
+                let bytes = <Vec<u8> as Decode<Any>>::decode(value)?;
+                Ok(UuidB64(Uuid::from_bytes(&bytes)?))
+            }
+            _ => {
+                let text = <String as Decode<Any>>::decode(value)?;
+                if text.len() == 22 {
+                    Ok(text.parse::<UuidB64>()?)
+                } else {
+                    Ok(UuidB64(Uuid::parse_str(&text)?))
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::sqlx::any::{Any, AnyArguments, AnyTypeInfo, AnyTypeInfoKind, AnyValue};
+    use super::sqlx::{Arguments, Decode, Encode, Type, Value};
+
+    use UuidB64;
+
+    const UUID: &str = "b0c1ee86-6f46-4f1b-8d8b-7849e75dbcee";
+    const ID: &str = "sMHuhm9GTxuNi3hJ51287g";
+
+    /// What a column holding `value` looks like to the `Any` driver
+    fn column<T: Encode<'static, Any> + Type<Any> + 'static>(value: T) -> AnyValue {
+        let mut args = AnyArguments::default();
+        args.add(value).unwrap();
+        AnyValue {
+            kind: args.values.0.remove(0),
+        }
+    }
+
+    fn decode(value: AnyValue) -> Result<UuidB64, String> {
+        <UuidB64 as Decode<Any>>::decode(value.as_ref()).map_err(|e| e.to_string())
+    }
+
+    #[test]
+    fn binds_as_hyphenated_text() {
+        let id: UuidB64 = ID.parse().unwrap();
+        let bound = column(id);
+        assert_eq!(bound.type_info().kind, AnyTypeInfoKind::Text);
+        assert_eq!(
+            <String as Decode<Any>>::decode(bound.as_ref()).unwrap(),
+            UUID
+        );
+    }
+
+    #[test]
+    fn decodes_text_and_blobs() {
+        let id: UuidB64 = ID.parse().unwrap();
+        assert_eq!(decode(column(UUID.to_owned())).unwrap(), id);
+        assert_eq!(decode(column(ID.to_owned())).unwrap(), id);
+        assert_eq!(decode(column(id.uuid().simple().to_string())).unwrap(), id);
+        assert_eq!(decode(column(id.uuid().as_bytes().to_vec())).unwrap(), id);
+    }
+
+    #[test]
+    fn rejects_garbage() {
+        assert!(decode(column("nope".to_owned())).is_err());
+        assert!(decode(column(vec![1u8, 2, 3])).is_err());
+        assert!(decode(column(7i64)).is_err());
+    }
+
+    #[test]
+    fn compatible_columns() {
+        let compatible = |kind| <UuidB64 as Type<Any>>::compatible(&AnyTypeInfo { kind });
+        assert!(compatible(AnyTypeInfoKind::Text));
+        assert!(compatible(AnyTypeInfoKind::Blob));
+        assert!(!compatible(AnyTypeInfoKind::BigInt));
+    }
+}
@@ -0,0 +1,78 @@
+extern crate sqlx;
+
+use self::sqlx::encode::IsNull;
+use self::sqlx::error::BoxDynError;
+use self::sqlx::postgres::types::Oid;
+use self::sqlx::postgres::{
+    PgArgumentBuffer, PgHasArrayType, PgTypeInfo, PgValueFormat, PgValueRef, Postgres,
+};
+use self::sqlx::{Decode, Encode, Type};
+
+use uuid::Uuid;
+
+use UuidB64;
+
+/// Postgres' builtin `uuid` type
+const UUID_OID: u32 = 2950;
+/// Postgres' builtin `uuid[]` type
+const UUID_ARRAY_OID: u32 = 2951;
+
+impl Type<Postgres> for UuidB64 {
+    fn type_info() -> PgTypeInfo {
+        PgTypeInfo::with_oid(Oid(UUID_OID))
+    }
+}
+
+/// Lets `&[UuidB64]` and `Vec<UuidB64>` bind as `uuid[]`, for `= ANY($1)`
+impl PgHasArrayType for UuidB64 {
+    fn array_type_info() -> PgTypeInfo {
+        PgTypeInfo::with_oid(Oid(UUID_ARRAY_OID))
+    }
+}
+
+impl<'q> Encode<'q, Postgres> for UuidB64 {
+    fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> Result<IsNull, BoxDynError> {
+        buf.extend_from_slice(self.0.as_bytes());
+        Ok(IsNull::No)
+    }
+}
+
+impl<'r> Decode<'r, Postgres> for UuidB64 {
+    fn decode(value: PgValueRef<'r>) -> Result<Self, BoxDynError> {
+        let id = match value.format() {
+            PgValueFormat::Binary => Uuid::from_bytes(value.as_bytes()?)?,
+            PgValueFormat::Text => Uuid::parse_str(value.as_str()?)?,
+        };
+        Ok(UuidB64(id))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::sqlx::encode::IsNull;
+    use super::sqlx::postgres::{PgArgumentBuffer, PgTypeInfo, Postgres};
+    use super::sqlx::{Encode, Type};
+
+    use UuidB64;
+
+    #[test]
+    fn slices_bind_as_uuid_arrays() {
+        assert_eq!(
+            <&[UuidB64] as Type<Postgres>>::type_info(),
+            PgTypeInfo::with_name("_uuid")
+        );
+        assert_eq!(
+            <Vec<UuidB64> as Type<Postgres>>::type_info(),
+            PgTypeInfo::with_name("_uuid")
+        );
+    }
+
+    #[test]
+    fn encodes_raw_bytes() {
+        let id = UuidB64::new();
+        let mut buf = PgArgumentBuffer::default();
+        let is_null = Encode::<Postgres>::encode_by_ref(&id, &mut buf).unwrap();
+        assert!(matches!(is_null, IsNull::No));
+        assert_eq!(&buf[..], id.uuid().as_bytes());
+    }
+}
@@ -0,0 +1,87 @@
+//! Support for sqlx's MySQL driver
+//!
+//! IDs are written as 16 raw bytes, for `BINARY(16)` columns, and read from
+//! those or from text columns holding either a hyphenated UUID or the base64
+//! form.
+
+extern crate sqlx;
+
+use std::str;
+
+use self::sqlx::encode::IsNull;
+use self::sqlx::error::BoxDynError;
+use self::sqlx::mysql::{MySql, MySqlTypeInfo, MySqlValueRef};
+use self::sqlx::{Decode, Encode, Type};
+
+use uuid::Uuid;
+
+use UuidB64;
+
+impl Type<MySql> for UuidB64 {
+    fn type_info() -> MySqlTypeInfo {
+        <[u8] as Type<MySql>>::type_info()
+    }
+
+    fn compatible(ty: &MySqlTypeInfo) -> bool {
+        <[u8] as Type<MySql>>::compatible(ty)
+    }
+}
+
+impl<'q> Encode<'q, MySql> for UuidB64 {
+    fn encode_by_ref(&self, buf: &mut Vec<u8>) -> Result<IsNull, BoxDynError> {
+        <&[u8] as Encode<MySql>>::encode(self.0.as_bytes(), buf)
+    }
+}
+
+impl<'r> Decode<'r, MySql> for UuidB64 {
+    fn decode(value: MySqlValueRef<'r>) -> Result<Self, BoxDynError> {
+        let bytes = <&[u8] as Decode<MySql>>::decode(value)?;
+        parse(bytes)
+            .ok_or_else(|| format!("not a UUID: {:?}", String::from_utf8_lossy(bytes)).into())
+    }
+}
+
+fn parse(bytes: &[u8]) -> Option<UuidB64> {
+    if bytes.len() == 16 {
+        return Uuid::from_bytes(bytes).ok().map(UuidB64);
+    }
+    let s = str::from_utf8(bytes).ok()?;
+    if s.len() == 22 {
+        s.parse().ok()
+    } else {
+        Uuid::parse_str(s).ok().map(UuidB64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse;
+    use super::sqlx::encode::IsNull;
+    use super::sqlx::mysql::MySql;
+    use super::sqlx::Encode;
+
+    use UuidB64;
+
+    const ID: &str = "sMHuhm9GTxuNi3hJ51287g";
+
+    #[test]
+    fn encodes_raw_bytes() {
+        let id: UuidB64 = ID.parse().unwrap();
+        let mut buf = Vec::new();
+        let is_null = Encode::<MySql>::encode_by_ref(&id, &mut buf).unwrap();
+        assert!(matches!(is_null, IsNull::No));
+        // length-prefixed
+        assert_eq!(buf[0], 16);
+        assert_eq!(&buf[1..], id.uuid().as_bytes());
+    }
+
+    #[test]
+    fn parses_every_form() {
+        let id: UuidB64 = ID.parse().unwrap();
+        assert_eq!(parse(id.uuid().as_bytes()), Some(id));
+        assert_eq!(parse(ID.as_bytes()), Some(id));
+        assert_eq!(parse(b"b0c1ee86-6f46-4f1b-8d8b-7849e75dbcee"), Some(id));
+        assert_eq!(parse(b"nope"), None);
+        assert_eq!(parse(&[0xff; 22]), None);
+    }
+}
@@ -0,0 +1,94 @@
+//! Support for sqlx's SQLite driver
+//!
+//! IDs are written as 16-byte `BLOB`s, and read from those or from `TEXT`
+//! holding either a hyphenated UUID or the base64 form.
+
+extern crate sqlx;
+
+use std::borrow::Cow;
+use std::str;
+
+use self::sqlx::encode::IsNull;
+use self::sqlx::error::BoxDynError;
+use self::sqlx::sqlite::{Sqlite, SqliteArgumentValue, SqliteTypeInfo, SqliteValueRef};
+use self::sqlx::{Decode, Encode, Type};
+
+use uuid::Uuid;
+
+use UuidB64;
+
+impl Type<Sqlite> for UuidB64 {
+    fn type_info() -> SqliteTypeInfo {
+        <[u8] as Type<Sqlite>>::type_info()
+    }
+
+    fn compatible(ty: &SqliteTypeInfo) -> bool {
+        <[u8] as Type<Sqlite>>::compatible(ty)
+    }
+}
+
+impl<'q> Encode<'q, Sqlite> for UuidB64 {
+    fn encode_by_ref(
+        &self,
+        args: &mut Vec<SqliteArgumentValue<'q>>,
+    ) -> Result<IsNull, BoxDynError> {
+        args.push(SqliteArgumentValue::Blob(Cow::Owned(
+            self.0.as_bytes().to_vec(),
+        )));
+        Ok(IsNull::No)
+    }
+}
+
+impl<'r> Decode<'r, Sqlite> for UuidB64 {
+    fn decode(value: SqliteValueRef<'r>) -> Result<Self, BoxDynError> {
+        // SQLite hands out the bytes of `TEXT` values as blobs too
+        let bytes = <&[u8] as Decode<Sqlite>>::decode(value)?;
+        parse(bytes)
+            .ok_or_else(|| format!("not a UUID: {:?}", String::from_utf8_lossy(bytes)).into())
+    }
+}
+
+fn parse(bytes: &[u8]) -> Option<UuidB64> {
+    if bytes.len() == 16 {
+        return Uuid::from_bytes(bytes).ok().map(UuidB64);
+    }
+    let s = str::from_utf8(bytes).ok()?;
+    if s.len() == 22 {
+        s.parse().ok()
+    } else {
+        Uuid::parse_str(s).ok().map(UuidB64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse;
+    use super::sqlx::encode::IsNull;
+    use super::sqlx::sqlite::{Sqlite, SqliteArgumentValue};
+    use super::sqlx::Encode;
+
+    use UuidB64;
+
+    const ID: &str = "sMHuhm9GTxuNi3hJ51287g";
+
+    #[test]
+    fn encodes_blobs() {
+        let id: UuidB64 = ID.parse().unwrap();
+        let mut args = Vec::new();
+        let is_null = Encode::<Sqlite>::encode_by_ref(&id, &mut args).unwrap();
+        assert!(matches!(is_null, IsNull::No));
+        match args[..] {
+            [SqliteArgumentValue::Blob(ref blob)] => assert_eq!(&blob[..], id.uuid().as_bytes()),
+            ref other => panic!("unexpected arguments: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn parses_every_form() {
+        let id: UuidB64 = ID.parse().unwrap();
+        assert_eq!(parse(id.uuid().as_bytes()), Some(id));
+        assert_eq!(parse(ID.as_bytes()), Some(id));
+        assert_eq!(parse(b"b0c1ee86-6f46-4f1b-8d8b-7849e75dbcee"), Some(id));
+        assert_eq!(parse(b"nope"), None);
+    }
+}
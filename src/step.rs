@@ -0,0 +1,176 @@
+//! Stepping through the keyspace one ID at a time
+
+use std::ops::RangeInclusive;
+
+use UuidB64;
+
+impl UuidB64 {
+    /// The next ID in sort order, `None` for the all-ones ID
+    ///
+    /// Handy for turning an inclusive bound into an exclusive one when
+    /// paginating: "everything after the last ID on this page" is
+    /// `id >= last.successor()`.
+    ///
+    /// ```
+    /// # use uuid_b64::UuidB64;
+    /// let id: UuidB64 = "sMHuhm9GTxuNi3hJ51287g".parse().unwrap();
+    /// let next = id.successor().unwrap();
+    /// assert_eq!(next.to_string(), "sMHuhm9GTxuNi3hJ51287w");
+    /// assert_eq!(next.predecessor(), Some(id));
+    /// ```
+    pub fn successor(&self) -> Option<UuidB64> {
+        self.to_u128().checked_add(1).map(UuidB64::from_u128)
+    }
+
+    /// The previous ID in sort order, `None` for the nil ID
+    pub fn predecessor(&self) -> Option<UuidB64> {
+        self.to_u128().checked_sub(1).map(UuidB64::from_u128)
+    }
+
+    /// Iterate over every ID in `range`, in order
+    ///
+    /// Also iterates backwards. An empty range (start after end) yields
+    /// nothing.
+    ///
+    /// ```
+    /// # use uuid_b64::UuidB64;
+    /// let start: UuidB64 = "sMHuhm9GTxuNi3hJ51287g".parse().unwrap();
+    /// let end = start.successor().unwrap().successor().unwrap();
+    /// let ids: Vec<_> = UuidB64::iter_range(start..=end).collect();
+    /// assert_eq!(ids.len(), 3);
+    /// assert_eq!(ids[0], start);
+    /// assert_eq!(UuidB64::iter_range(start..=end).next_back(), Some(end));
+    /// ```
+    pub fn iter_range(range: RangeInclusive<UuidB64>) -> IdRange {
+        IdRange::from(range)
+    }
+}
+
+/// An iterator over consecutive IDs, see [`UuidB64::iter_range`][]
+///
+/// [`UuidB64::iter_range`]: struct.UuidB64.html#method.iter_range
+#[derive(Debug, Clone)]
+pub struct IdRange {
+    front: u128,
+    back: u128,
+    exhausted: bool,
+}
+
+impl From<RangeInclusive<UuidB64>> for IdRange {
+    fn from(range: RangeInclusive<UuidB64>) -> IdRange {
+        let (start, end) = range.into_inner();
+        IdRange {
+            front: start.to_u128(),
+            back: end.to_u128(),
+            exhausted: start > end,
+        }
+    }
+}
+
+impl Iterator for IdRange {
+    type Item = UuidB64;
+
+    fn next(&mut self) -> Option<UuidB64> {
+        if self.exhausted {
+            return None;
+        }
+        let id = UuidB64::from_u128(self.front);
+        if self.front == self.back {
+            self.exhausted = true;
+        } else {
+            self.front += 1;
+        }
+        Some(id)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.exhausted {
+            return (0, Some(0));
+        }
+        match (self.back - self.front).checked_add(1) {
+            Some(n) if n <= usize::MAX as u128 => (n as usize, Some(n as usize)),
+            _ => (usize::MAX, None),
+        }
+    }
+}
+
+impl DoubleEndedIterator for IdRange {
+    fn next_back(&mut self) -> Option<UuidB64> {
+        if self.exhausted {
+            return None;
+        }
+        let id = UuidB64::from_u128(self.back);
+        if self.front == self.back {
+            self.exhausted = true;
+        } else {
+            self.back -= 1;
+        }
+        Some(id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use uuid::Uuid;
+
+    use UuidB64;
+
+    #[test]
+    fn stepping_carries() {
+        let id = UuidB64::from(Uuid::parse_str("00000000-0000-0000-00ff-ffffffffffff").unwrap());
+        assert_eq!(
+            id.successor().unwrap().uuid().hyphenated().to_string(),
+            "00000000-0000-0000-0100-000000000000"
+        );
+        assert_eq!(id.successor().unwrap().predecessor(), Some(id));
+        assert!(id < id.successor().unwrap());
+    }
+
+    #[test]
+    fn ends_of_the_keyspace() {
+        assert_eq!(UuidB64::NIL.predecessor(), None);
+        assert_eq!(UuidB64::MAX.successor(), None);
+        assert_eq!(UuidB64::NIL.successor(), Some(UuidB64::from_u128(1)));
+        assert_eq!(
+            UuidB64::MAX.predecessor(),
+            Some(UuidB64::from_u128(u128::MAX - 1))
+        );
+    }
+
+    #[test]
+    fn ranges() {
+        let start = UuidB64::new();
+        let ids: Vec<_> = UuidB64::iter_range(start..=start).collect();
+        assert_eq!(ids, vec![start]);
+
+        let end = UuidB64::from_u128(start.to_u128() + 4);
+        let range = UuidB64::iter_range(start..=end);
+        assert_eq!(range.size_hint(), (5, Some(5)));
+        let forward: Vec<_> = range.clone().collect();
+        let mut backward: Vec<_> = range.rev().collect();
+        backward.reverse();
+        assert_eq!(forward, backward);
+        assert!(forward.windows(2).all(|w| w[0].successor() == Some(w[1])));
+
+        assert_eq!(UuidB64::iter_range(end..=start).next(), None);
+    }
+
+    #[test]
+    fn meeting_in_the_middle() {
+        let start = UuidB64::from_u128(10);
+        let mut range = UuidB64::iter_range(start..=UuidB64::from_u128(12));
+        assert_eq!(range.next(), Some(start));
+        assert_eq!(range.next_back(), Some(UuidB64::from_u128(12)));
+        assert_eq!(range.next(), Some(UuidB64::from_u128(11)));
+        assert_eq!(range.next_back(), None);
+        assert_eq!(range.size_hint(), (0, Some(0)));
+    }
+
+    #[test]
+    fn whole_keyspace_does_not_overflow() {
+        let mut range = UuidB64::iter_range(UuidB64::NIL..=UuidB64::MAX);
+        assert_eq!(range.size_hint(), (usize::MAX, None));
+        assert_eq!(range.next(), Some(UuidB64::NIL));
+        assert_eq!(range.next_back(), Some(UuidB64::MAX));
+    }
+}
@@ -0,0 +1,149 @@
+//! Generated IDs as async streams
+
+extern crate futures_core;
+
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use self::futures_core::Stream;
+
+use {UuidB64, V7Generator};
+
+/// An endless `Stream` of IDs produced by a generator function
+///
+/// Streams are pull-based, so IDs are only generated as fast as they are
+/// consumed. Use [`batched`](#method.batched) to receive them in `Vec`s
+/// instead, which amortizes per-item overhead in channels and queues.
+///
+/// ```
+/// # extern crate futures;
+/// # extern crate uuid_b64;
+/// # use futures::StreamExt;
+/// # use uuid_b64::{IdStream, UuidB64, V7Generator};
+/// # fn main() {
+/// let ids: Vec<UuidB64> = futures::executor::block_on(UuidB64::stream().take(3).collect());
+/// assert_eq!(ids.len(), 3);
+///
+/// let mut v7s = IdStream::v7(V7Generator::new()).batched(100);
+/// let batch = futures::executor::block_on(v7s.next()).unwrap();
+/// assert_eq!(batch.len(), 100);
+/// # }
+/// ```
+pub struct IdStream<G> {
+    generate: G,
+}
+
+impl<G> IdStream<G>
+where
+    G: FnMut() -> UuidB64,
+{
+    /// A stream that calls `generate` for each item
+    pub fn new(generate: G) -> IdStream<G> {
+        IdStream { generate }
+    }
+
+    /// Yield IDs in batches of `size`
+    ///
+    /// # Panics
+    ///
+    /// If `size` is zero.
+    pub fn batched(self, size: usize) -> BatchedIdStream<G> {
+        assert!(size > 0, "batches must hold at least one ID");
+        BatchedIdStream {
+            generate: self.generate,
+            size,
+        }
+    }
+}
+
+impl IdStream<fn() -> UuidB64> {
+    /// A stream of random (v4) IDs
+    pub fn v4() -> IdStream<fn() -> UuidB64> {
+        IdStream::new(UuidB64::new)
+    }
+}
+
+impl IdStream<Box<dyn FnMut() -> UuidB64 + Send>> {
+    /// A stream of time-ordered (v7) IDs from `generator`
+    pub fn v7(mut generator: V7Generator) -> IdStream<Box<dyn FnMut() -> UuidB64 + Send>> {
+        IdStream::new(Box::new(move || generator.generate()))
+    }
+}
+
+impl<G> Stream for IdStream<G>
+where
+    G: FnMut() -> UuidB64 + Unpin,
+{
+    type Item = UuidB64;
+
+    fn poll_next(self: Pin<&mut Self>, _cx: &mut Context) -> Poll<Option<UuidB64>> {
+        Poll::Ready(Some((self.get_mut().generate)()))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (usize::MAX, None)
+    }
+}
+
+/// An endless `Stream` of batches of IDs, see [`IdStream::batched`][]
+///
+/// [`IdStream::batched`]: struct.IdStream.html#method.batched
+pub struct BatchedIdStream<G> {
+    generate: G,
+    size: usize,
+}
+
+impl<G> Stream for BatchedIdStream<G>
+where
+    G: FnMut() -> UuidB64 + Unpin,
+{
+    type Item = Vec<UuidB64>;
+
+    fn poll_next(self: Pin<&mut Self>, _cx: &mut Context) -> Poll<Option<Vec<UuidB64>>> {
+        let this = self.get_mut();
+        let batch = (0..this.size).map(|_| (this.generate)()).collect();
+        Poll::Ready(Some(batch))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (usize::MAX, None)
+    }
+}
+
+impl UuidB64 {
+    /// An endless stream of random (v4) IDs
+    pub fn stream() -> IdStream<fn() -> UuidB64> {
+        IdStream::v4()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate futures;
+
+    use self::futures::executor::block_on;
+    use self::futures::StreamExt;
+
+    use super::IdStream;
+    use {UuidB64, V7Generator};
+
+    #[test]
+    fn yields_distinct_ids() {
+        let ids: Vec<UuidB64> = block_on(UuidB64::stream().take(10).collect());
+        let mut deduped = ids.clone();
+        deduped.sort();
+        deduped.dedup();
+        assert_eq!(deduped.len(), 10);
+    }
+
+    #[test]
+    fn v7_batches_are_ordered() {
+        let batches: Vec<Vec<UuidB64>> =
+            block_on(IdStream::v7(V7Generator::new()).batched(50).take(2).collect());
+        let ids: Vec<UuidB64> = batches.into_iter().flatten().collect();
+        assert_eq!(ids.len(), 100);
+        let mut sorted = ids.clone();
+        sorted.sort();
+        assert_eq!(ids, sorted);
+    }
+}
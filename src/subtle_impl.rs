@@ -0,0 +1,57 @@
+//! Constant-time comparison, for IDs used as unguessable tokens
+
+extern crate subtle;
+
+use self::subtle::{Choice, ConstantTimeEq};
+
+use UuidB64;
+
+impl ConstantTimeEq for UuidB64 {
+    fn ct_eq(&self, other: &UuidB64) -> Choice {
+        self.0.as_bytes().ct_eq(other.0.as_bytes())
+    }
+}
+
+impl UuidB64 {
+    /// Compare two IDs in time that doesn't depend on where they differ
+    ///
+    /// `==` stops at the first differing byte, so comparing a guess with a
+    /// secret ID (a capability URL, a password reset token) can leak how
+    /// much of the guess was right. Use this for those instead.
+    ///
+    /// This shadows `ConstantTimeEq::ct_eq`, which is also implemented for
+    /// combining with other `subtle` comparisons.
+    ///
+    /// ```
+    /// # use uuid_b64::UuidB64;
+    /// let token = UuidB64::new();
+    /// assert!(token.ct_eq(&token.to_string().parse().unwrap()));
+    /// assert!(!token.ct_eq(&UuidB64::new()));
+    /// ```
+    pub fn ct_eq(&self, other: &UuidB64) -> bool {
+        ConstantTimeEq::ct_eq(self, other).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::subtle::ConstantTimeEq;
+    use uuid::Uuid;
+
+    use UuidB64;
+
+    #[test]
+    fn compares_every_byte() {
+        let id = UuidB64::from(Uuid::parse_str("b0c1ee86-6f46-4f1b-8d8b-7849e75dbcee").unwrap());
+        assert!(id.ct_eq(&id));
+        for i in 0..16 {
+            let mut bytes = *id.uuid().as_bytes();
+            bytes[i] ^= 1;
+            let other = UuidB64::from(Uuid::from_bytes(&bytes).unwrap());
+            assert!(!id.ct_eq(&other));
+            assert!(!bool::from(ConstantTimeEq::ct_eq(&id, &other)));
+        }
+        let ids = [id, UuidB64::NIL];
+        assert!(bool::from(ids[..].ct_eq(&[id, UuidB64::NIL][..])));
+    }
+}
@@ -0,0 +1,80 @@
+extern crate tantivy;
+
+use self::tantivy::query::TermQuery;
+use self::tantivy::schema::{Field, IndexRecordOption, SchemaBuilder, FAST, INDEXED, STORED};
+use self::tantivy::{TantivyDocument, Term};
+
+use uuid::Uuid;
+
+use UuidB64;
+
+impl UuidB64 {
+    /// Add a field for IDs to a tantivy schema
+    ///
+    /// The field stores the raw 16 bytes, and is indexed (for term queries),
+    /// fast (for filtering and collecting) and stored.
+    pub fn add_tantivy_field(builder: &mut SchemaBuilder, name: &str) -> Field {
+        builder.add_bytes_field(name, INDEXED | FAST | STORED)
+    }
+
+    /// Add this ID to a document, in a field created by
+    /// [`add_tantivy_field`](#method.add_tantivy_field)
+    pub fn add_to_tantivy_doc(&self, doc: &mut TantivyDocument, field: Field) {
+        doc.add_bytes(field, self.0.as_bytes());
+    }
+
+    /// A term matching this ID in the given bytes field
+    pub fn tantivy_term(&self, field: Field) -> Term {
+        Term::from_field_bytes(field, self.0.as_bytes())
+    }
+
+    /// A query for documents with this ID in the given bytes field
+    pub fn tantivy_query(&self, field: Field) -> TermQuery {
+        TermQuery::new(self.tantivy_term(field), IndexRecordOption::Basic)
+    }
+
+    /// Read an ID back out of a bytes field value
+    ///
+    /// Returns `None` if the value isn't exactly 16 bytes.
+    pub fn from_tantivy_bytes(bytes: &[u8]) -> Option<UuidB64> {
+        if bytes.len() != 16 {
+            return None;
+        }
+        Uuid::from_bytes(bytes).ok().map(UuidB64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::tantivy::collector::TopDocs;
+    use super::tantivy::schema::{Schema, Value};
+    use super::tantivy::{Index, TantivyDocument};
+
+    use UuidB64;
+
+    #[test]
+    fn finds_documents_by_id() {
+        let mut builder = Schema::builder();
+        let field = UuidB64::add_tantivy_field(&mut builder, "id");
+        let index = Index::create_in_ram(builder.build());
+
+        let ids: Vec<UuidB64> = (0..10).map(|_| UuidB64::new()).collect();
+        let mut writer = index.writer_with_num_threads(1, 15_000_000).unwrap();
+        for id in &ids {
+            let mut doc = TantivyDocument::default();
+            id.add_to_tantivy_doc(&mut doc, field);
+            writer.add_document(doc).unwrap();
+        }
+        writer.commit().unwrap();
+
+        let searcher = index.reader().unwrap().searcher();
+        let hits = searcher
+            .search(&ids[3].tantivy_query(field), &TopDocs::with_limit(10).order_by_score())
+            .unwrap();
+        assert_eq!(hits.len(), 1);
+
+        let doc: TantivyDocument = searcher.doc(hits[0].1).unwrap();
+        let stored = doc.get_first(field).and_then(|v| v.as_bytes()).unwrap();
+        assert_eq!(UuidB64::from_tantivy_bytes(stored), Some(ids[3]));
+    }
+}
@@ -0,0 +1,159 @@
+//! Reproducible IDs for tests
+
+use std::cell::RefCell;
+
+use uuid::Uuid;
+
+use UuidB64;
+
+thread_local! {
+    /// The generator `UuidB64::new` uses on this thread, inside
+    /// `DeterministicGenerator::scope`
+    static OVERRIDE: RefCell<Option<DeterministicGenerator>> = const { RefCell::new(None) };
+}
+
+/// Generates the same sequence of v4 IDs for the same seed
+///
+/// Use it directly as an iterator, or with [`scope`](#method.scope) to make
+/// `UuidB64::new` deterministic for code that creates its own IDs:
+///
+/// ```
+/// # use uuid_b64::{DeterministicGenerator, UuidB64};
+/// fn create_user() -> UuidB64 {
+///     UuidB64::new()
+/// }
+///
+/// let first = DeterministicGenerator::from_seed(42).scope(create_user);
+/// let again = DeterministicGenerator::from_seed(42).scope(create_user);
+/// assert_eq!(first, again);
+/// assert_eq!(DeterministicGenerator::from_seed(42).next(), Some(first));
+/// ```
+///
+/// This is not a secure random number generator, only use it in tests.
+#[derive(Debug, Clone)]
+pub struct DeterministicGenerator {
+    state: u64,
+}
+
+impl DeterministicGenerator {
+    /// A generator that starts from `seed`
+    pub fn from_seed(seed: u64) -> DeterministicGenerator {
+        DeterministicGenerator { state: seed }
+    }
+
+    /// The next ID in the sequence
+    pub fn generate(&mut self) -> UuidB64 {
+        UuidB64(Uuid::from_random_bytes(self.random_bytes()))
+    }
+
+    /// The next 16 bytes, before the version and variant are set
+    pub(crate) fn random_bytes(&mut self) -> [u8; 16] {
+        let mut bytes = [0; 16];
+        bytes[..8].copy_from_slice(&self.next_u64().to_le_bytes());
+        bytes[8..].copy_from_slice(&self.next_u64().to_le_bytes());
+        bytes
+    }
+
+    /// Run `f` with `UuidB64::new` drawing from this generator on the
+    /// current thread
+    ///
+    /// Scopes nest, and the previous generator (or none) is restored when
+    /// `f` returns or panics. Other threads are unaffected.
+    pub fn scope<T, F: FnOnce() -> T>(self, f: F) -> T {
+        struct Restore(Option<DeterministicGenerator>);
+
+        impl Drop for Restore {
+            fn drop(&mut self) {
+                let previous = self.0.take();
+                OVERRIDE.with(|current| *current.borrow_mut() = previous);
+            }
+        }
+
+        let _restore = Restore(OVERRIDE.with(|current| current.replace(Some(self))));
+        f()
+    }
+
+    // SplitMix64
+    fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+}
+
+impl Iterator for DeterministicGenerator {
+    type Item = UuidB64;
+
+    fn next(&mut self) -> Option<UuidB64> {
+        Some(self.generate())
+    }
+}
+
+/// Whether there's a generator for this thread's `UuidB64::new`
+pub(crate) fn in_scope() -> bool {
+    OVERRIDE.with(|current| current.borrow().is_some())
+}
+
+/// The next ID from the current scope's generator, if there is one
+pub(crate) fn scoped_id() -> Option<UuidB64> {
+    OVERRIDE.with(|current| current.borrow_mut().as_mut().map(|gen| gen.generate()))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::panic;
+    use std::thread;
+
+    use uuid::UuidVersion;
+
+    use super::DeterministicGenerator;
+    use UuidB64;
+
+    #[test]
+    fn same_seed_same_ids() {
+        let a: Vec<_> = DeterministicGenerator::from_seed(1).take(5).collect();
+        let b: Vec<_> = DeterministicGenerator::from_seed(1).take(5).collect();
+        let c: Vec<_> = DeterministicGenerator::from_seed(2).take(5).collect();
+        assert_eq!(a, b);
+        assert_ne!(a, c);
+        assert!(a
+            .iter()
+            .all(|id| id.uuid().get_version() == Some(UuidVersion::Random)));
+        assert_ne!(a[0], a[1]);
+    }
+
+    #[test]
+    fn scopes_nest_and_restore() {
+        let expected: Vec<_> = DeterministicGenerator::from_seed(7).take(3).collect();
+        let inner: Vec<_> = DeterministicGenerator::from_seed(8).take(1).collect();
+        let ids = DeterministicGenerator::from_seed(7).scope(|| {
+            let first = UuidB64::new();
+            let nested = DeterministicGenerator::from_seed(8).scope(UuidB64::new);
+            // other threads still get random IDs
+            let other = thread::spawn(UuidB64::new).join().unwrap();
+            assert!(!expected.contains(&other));
+            vec![first, nested, UuidB64::new(), UuidB64::new()]
+        });
+        assert_eq!(ids, vec![expected[0], inner[0], expected[1], expected[2]]);
+        assert!(!expected.contains(&UuidB64::new()));
+    }
+
+    #[test]
+    fn batches_use_the_scope() {
+        let expected: Vec<_> = DeterministicGenerator::from_seed(5).take(3).collect();
+        let ids = DeterministicGenerator::from_seed(5).scope(|| UuidB64::new_batch(3));
+        assert_eq!(ids, expected);
+    }
+
+    #[test]
+    fn restores_after_a_panic() {
+        let seeded = DeterministicGenerator::from_seed(3).generate();
+        let result = panic::catch_unwind(|| {
+            DeterministicGenerator::from_seed(3).scope(|| -> () { panic!("in the scope") })
+        });
+        assert!(result.is_err());
+        assert_ne!(UuidB64::new(), seeded);
+    }
+}
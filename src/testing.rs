@@ -0,0 +1,89 @@
+//! proptest strategies for IDs
+//!
+//! `UuidB64` implements `proptest::arbitrary::Arbitrary` with [`any_id`], so
+//! `any::<UuidB64>()` and `#[derive(Arbitrary)]` on structs holding IDs both
+//! use it:
+//!
+//! ```rust,ignore
+//! proptest! {
+//!     #[test]
+//!     fn roundtrips(id in any_id()) {
+//!         prop_assert_eq!(id.to_string().parse::<UuidB64>().unwrap(), id);
+//!     }
+//! }
+//! ```
+//!
+//! [`any_id`]: fn.any_id.html
+
+extern crate proptest;
+
+use self::proptest::arbitrary::{any, Arbitrary};
+use self::proptest::prop_oneof;
+use self::proptest::strategy::{BoxedStrategy, Just, Strategy};
+use uuid::UuidVariant;
+
+use {UuidB64, UuidB64Builder};
+
+/// Any ID, weighted toward the edge cases
+///
+/// Most of the time one of nil, max, or an RFC 4122 ID of each version from
+/// 1 to 8 with random bits, otherwise any 16 bytes at all. Failures shrink
+/// toward nil.
+pub fn any_id() -> BoxedStrategy<UuidB64> {
+    prop_oneof![
+        1 => Just(UuidB64::NIL),
+        1 => Just(UuidB64::MAX),
+        6 => (1..=8u8, any::<[u8; 16]>()).prop_map(|(version, bytes)| {
+            UuidB64Builder::from_bytes(bytes)
+                .with_version(version)
+                .with_variant(UuidVariant::RFC4122)
+                .into_uuid_b64()
+        }),
+        2 => any::<[u8; 16]>().prop_map(UuidB64::from_bytes),
+    ]
+    .boxed()
+}
+
+impl Arbitrary for UuidB64 {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<UuidB64>;
+
+    fn arbitrary_with(_: ()) -> BoxedStrategy<UuidB64> {
+        any_id()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::proptest::prelude::*;
+    use super::proptest::test_runner::TestRunner;
+
+    use super::any_id;
+    use UuidB64;
+
+    #[test]
+    fn covers_the_edge_cases() {
+        let mut runner = TestRunner::deterministic();
+        let mut versions = HashSet::new();
+        let (mut nil, mut max) = (false, false);
+        for _ in 0..1000 {
+            let id = any_id().new_tree(&mut runner).unwrap().current();
+            nil |= id.is_nil();
+            max |= id == UuidB64::MAX;
+            versions.insert(id.uuid().get_version_num());
+        }
+        assert!(nil && max);
+        for version in 1..=8 {
+            assert!(versions.contains(&version), "no v{}", version);
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn any_uuid_b64_roundtrips(id in any::<UuidB64>()) {
+            prop_assert_eq!(id.to_string().parse::<UuidB64>().unwrap(), id);
+        }
+    }
+}
@@ -0,0 +1,77 @@
+//! Support for tiberius, the SQL Server driver
+//!
+//! IDs map to `UNIQUEIDENTIFIER`. SQL Server keeps those in Microsoft's
+//! mixed-endian GUID layout, which tiberius converts to and from on the
+//! wire, so an ID reads back in SQL Server with the same hyphenated form it
+//! has here. (For `BINARY(16)` columns filled by SQL Server or .NET, use
+//! `UuidB64::from_guid_bytes` instead.)
+
+extern crate tiberius;
+
+use self::tiberius::{ColumnData, FromSql, FromSqlOwned, IntoSql, ToSql, Uuid as TdsUuid};
+
+use UuidB64;
+
+fn to_tds(id: &UuidB64) -> TdsUuid {
+    TdsUuid::from_bytes(*id.0.as_bytes())
+}
+
+fn from_tds(uuid: TdsUuid) -> UuidB64 {
+    UuidB64::from_bytes(*uuid.as_bytes())
+}
+
+impl ToSql for UuidB64 {
+    fn to_sql(&self) -> ColumnData<'_> {
+        ColumnData::Guid(Some(to_tds(self)))
+    }
+}
+
+impl<'a> IntoSql<'a> for UuidB64 {
+    fn into_sql(self) -> ColumnData<'a> {
+        ColumnData::Guid(Some(to_tds(&self)))
+    }
+}
+
+impl<'a> FromSql<'a> for UuidB64 {
+    fn from_sql(value: &'a ColumnData<'static>) -> tiberius::Result<Option<Self>> {
+        Ok(TdsUuid::from_sql(value)?.map(from_tds))
+    }
+}
+
+impl FromSqlOwned for UuidB64 {
+    fn from_sql_owned(value: ColumnData<'static>) -> tiberius::Result<Option<Self>> {
+        Ok(TdsUuid::from_sql_owned(value)?.map(from_tds))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::tiberius::{ColumnData, FromSql, FromSqlOwned, IntoSql, ToSql};
+
+    use UuidB64;
+
+    const ID: &str = "sMHuhm9GTxuNi3hJ51287g";
+    const UUID: &str = "b0c1ee86-6f46-4f1b-8d8b-7849e75dbcee";
+
+    #[test]
+    fn binds_as_uniqueidentifier() {
+        let id: UuidB64 = ID.parse().unwrap();
+        for data in [id.to_sql(), id.into_sql()] {
+            match data {
+                // tiberius swaps the byte order on the wire, not here
+                ColumnData::Guid(Some(uuid)) => assert_eq!(uuid.to_string(), UUID),
+                other => panic!("unexpected {:?}", other),
+            }
+        }
+    }
+
+    #[test]
+    fn reads_uniqueidentifiers() {
+        let id: UuidB64 = ID.parse().unwrap();
+        let data: ColumnData<'static> = id.into_sql();
+        assert_eq!(UuidB64::from_sql(&data).unwrap(), Some(id));
+        assert_eq!(UuidB64::from_sql_owned(data).unwrap(), Some(id));
+        assert_eq!(UuidB64::from_sql(&ColumnData::Guid(None)).unwrap(), None);
+        assert!(UuidB64::from_sql(&ColumnData::I32(Some(1))).is_err());
+    }
+}
@@ -0,0 +1,101 @@
+extern crate tonic;
+
+use self::tonic::metadata::MetadataValue;
+use self::tonic::service::Interceptor;
+use self::tonic::{Request, Status};
+
+use UuidB64;
+
+/// The metadata key that request IDs are propagated under
+pub const REQUEST_ID_METADATA_KEY: &str = "x-request-id";
+
+/// A tonic interceptor that ensures every request carries an `x-request-id`
+///
+/// It works on both sides of a call:
+///
+/// * On clients, requests that don't already have a request ID get a new
+///   one, so it can be set explicitly to propagate an ID from an incoming
+///   REST or gRPC request.
+/// * On servers, the ID is validated (a malformed one is rejected with
+///   `InvalidArgument`) or generated if the client didn't send one, and
+///   stored in the request extensions, where handlers can find it with
+///   [`request_id`](fn.request_id.html).
+///
+/// ```
+/// # extern crate tonic;
+/// # extern crate uuid_b64;
+/// # use tonic::service::Interceptor;
+/// # use uuid_b64::{request_id, RequestIdInterceptor};
+/// # fn main() {
+/// let request = RequestIdInterceptor.call(tonic::Request::new(())).unwrap();
+/// assert!(request_id(&request).is_some());
+/// # }
+/// ```
+#[derive(Debug, Copy, Clone, Default)]
+pub struct RequestIdInterceptor;
+
+impl Interceptor for RequestIdInterceptor {
+    fn call(&mut self, mut request: Request<()>) -> Result<Request<()>, Status> {
+        let id = match request.metadata().get(REQUEST_ID_METADATA_KEY) {
+            Some(value) => value
+                .to_str()
+                .ok()
+                .and_then(|s| s.parse::<UuidB64>().ok())
+                .ok_or_else(|| Status::invalid_argument("malformed x-request-id"))?,
+            None => {
+                let id = UuidB64::new();
+                let value: MetadataValue<_> = id
+                    .to_istring()
+                    .parse()
+                    .expect("base64 is always valid metadata");
+                request.metadata_mut().insert(REQUEST_ID_METADATA_KEY, value);
+                id
+            }
+        };
+        request.extensions_mut().insert(id);
+        Ok(request)
+    }
+}
+
+/// The request ID of a request that passed through `RequestIdInterceptor`
+pub fn request_id<T>(request: &Request<T>) -> Option<UuidB64> {
+    request.extensions().get::<UuidB64>().cloned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::tonic::service::Interceptor;
+    use super::tonic::{Code, Request};
+
+    use super::{request_id, RequestIdInterceptor};
+    use UuidB64;
+
+    #[test]
+    fn generates_missing_ids() {
+        let request = RequestIdInterceptor.call(Request::new(())).unwrap();
+        let id = request_id(&request).unwrap();
+        let value = request.metadata().get("x-request-id").unwrap();
+        assert_eq!(value.to_str().unwrap(), id.to_string());
+    }
+
+    #[test]
+    fn keeps_existing_ids() {
+        let id = UuidB64::new();
+        let mut request = Request::new(());
+        request
+            .metadata_mut()
+            .insert("x-request-id", id.to_string().parse().unwrap());
+        let request = RequestIdInterceptor.call(request).unwrap();
+        assert_eq!(request_id(&request), Some(id));
+    }
+
+    #[test]
+    fn rejects_malformed_ids() {
+        let mut request = Request::new(());
+        request
+            .metadata_mut()
+            .insert("x-request-id", "nope".parse().unwrap());
+        let status = RequestIdInterceptor.call(request).unwrap_err();
+        assert_eq!(status.code(), Code::InvalidArgument);
+    }
+}
@@ -0,0 +1,74 @@
+extern crate ts_rs;
+
+use std::path::PathBuf;
+
+use self::ts_rs::{Config, TS};
+
+use UuidB64;
+
+/// `UuidB64` is exported as a branded string:
+///
+/// ```ts
+/// export type UuidB64 = string & { readonly __brand: "UuidB64" };
+/// ```
+///
+/// The brand only exists at the type level, values are still the plain
+/// strings we serialize to. Frontend code has to go through a cast (or a
+/// validating helper) to turn an arbitrary string into a `UuidB64`, so IDs
+/// can't be mixed up with other strings by accident.
+impl TS for UuidB64 {
+    type WithoutGenerics = Self;
+    type OptionInnerType = Self;
+
+    fn docs() -> Option<String> {
+        Some(
+            "/**\n * A UUID encoded as 22 characters of URL-safe base64, without padding\n */\n"
+                .to_owned(),
+        )
+    }
+
+    fn decl(cfg: &Config) -> String {
+        format!("type {} = {};", Self::name(cfg), Self::inline(cfg))
+    }
+
+    fn decl_concrete(cfg: &Config) -> String {
+        Self::decl(cfg)
+    }
+
+    fn name(_: &Config) -> String {
+        "UuidB64".to_owned()
+    }
+
+    fn inline(_: &Config) -> String {
+        "string & { readonly __brand: \"UuidB64\" }".to_owned()
+    }
+
+    fn output_path() -> Option<PathBuf> {
+        Some(PathBuf::from("UuidB64.ts"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ts_rs::{Config, TS};
+
+    use UuidB64;
+
+    #[test]
+    fn referenced_by_name() {
+        let cfg = Config::default();
+        assert_eq!(UuidB64::name(&cfg), "UuidB64");
+        assert_eq!(Option::<UuidB64>::name(&cfg), "UuidB64 | null");
+        assert_eq!(Vec::<UuidB64>::name(&cfg), "Array<UuidB64>");
+    }
+
+    #[test]
+    fn exported_as_a_branded_string() {
+        let cfg = Config::default();
+        let exported = UuidB64::export_to_string(&cfg).unwrap();
+        assert!(exported.contains(
+            "export type UuidB64 = string & { readonly __brand: \"UuidB64\" };"
+        ));
+        assert!(exported.contains(" * A UUID encoded as 22 characters"));
+    }
+}
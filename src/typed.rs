@@ -0,0 +1,225 @@
+//! IDs that know what they identify
+
+use std::cmp::Ordering;
+use std::fmt::{Debug, Display, Formatter, Result as FmtResult};
+use std::hash::{Hash, Hasher};
+use std::marker::PhantomData;
+use std::str::FromStr;
+
+use inlinable_string::inline_string::InlineString;
+use uuid::Uuid;
+
+use errors::ParseUuidB64Error;
+use UuidB64;
+
+/// A `UuidB64` for one kind of thing
+///
+/// `Id<User>` and `Id<Order>` are different types, so one can't be passed
+/// where the other is expected, but they display, parse and serialize
+/// exactly like `UuidB64`:
+///
+/// ```
+/// # use uuid_b64::Id;
+/// struct User;
+/// struct Order;
+///
+/// fn cancel(order: Id<Order>, by: Id<User>) { /* ... */ }
+///
+/// let user: Id<User> = "sMHuhm9GTxuNi3hJ51287g".parse().unwrap();
+/// let order = Id::<Order>::new();
+/// cancel(order, user);
+/// assert_eq!(user.to_string(), "sMHuhm9GTxuNi3hJ51287g");
+/// ```
+///
+/// ```compile_fail
+/// # use uuid_b64::Id;
+/// # struct User;
+/// # struct Order;
+/// # fn cancel(order: Id<Order>, by: Id<User>) {}
+/// let user = Id::<User>::new();
+/// let order = Id::<Order>::new();
+/// cancel(user, order);
+/// ```
+///
+/// `T` is only a marker, it's never stored, so `Id<T>` is `Copy`, `Send`,
+/// `Hash` and so on whatever `T` is.
+pub struct Id<T>(UuidB64, PhantomData<fn() -> T>);
+
+impl<T> Id<T> {
+    /// Generate a new v4 ID
+    #[allow(clippy::new_without_default)]
+    pub fn new() -> Id<T> {
+        Id::from_uuid_b64(UuidB64::new())
+    }
+
+    /// Mark an untyped ID as identifying a `T`
+    ///
+    /// This is `const`, so well-known IDs can be typed too:
+    ///
+    /// ```
+    /// # #[macro_use] extern crate uuid_b64;
+    /// # use uuid_b64::Id;
+    /// # struct User;
+    /// const SYSTEM: Id<User> = Id::from_uuid_b64(uuid_b64!("sMHuhm9GTxuNi3hJ51287g"));
+    /// # fn main() {}
+    /// ```
+    pub const fn from_uuid_b64(id: UuidB64) -> Id<T> {
+        Id(id, PhantomData)
+    }
+
+    /// The untyped ID
+    pub fn as_uuid_b64(&self) -> UuidB64 {
+        self.0
+    }
+
+    /// Copy the raw UUID out
+    pub fn uuid(&self) -> Uuid {
+        self.0.uuid()
+    }
+
+    /// See [`UuidB64::sort_key`](struct.UuidB64.html#method.sort_key)
+    pub fn sort_key(&self) -> [u8; 16] {
+        self.0.sort_key()
+    }
+
+    /// See [`UuidB64::to_istring`](struct.UuidB64.html#method.to_istring)
+    pub fn to_istring(&self) -> InlineString {
+        self.0.to_istring()
+    }
+
+    /// See [`UuidB64::to_buf`](struct.UuidB64.html#method.to_buf)
+    pub fn to_buf(&self, buffer: &mut String) {
+        self.0.to_buf(buffer)
+    }
+}
+
+// These are written out by hand because deriving them would require `T` to
+// implement them too
+
+impl<T> Clone for Id<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Id<T> {}
+
+impl<T> PartialEq for Id<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl<T> Eq for Id<T> {}
+
+impl<T> PartialOrd for Id<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T> Ord for Id<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.cmp(&other.0)
+    }
+}
+
+impl<T> Hash for Id<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.hash(state)
+    }
+}
+
+impl<T> From<UuidB64> for Id<T> {
+    fn from(id: UuidB64) -> Self {
+        Id::from_uuid_b64(id)
+    }
+}
+
+impl<T> From<Uuid> for Id<T> {
+    fn from(id: Uuid) -> Self {
+        Id::from_uuid_b64(UuidB64(id))
+    }
+}
+
+// UuidB64's blanket `From<T: Into<Uuid>>` turns this into a conversion to
+// UuidB64 as well
+impl<T> From<Id<T>> for Uuid {
+    fn from(id: Id<T>) -> Self {
+        (id.0).0
+    }
+}
+
+impl<T> FromStr for Id<T> {
+    type Err = ParseUuidB64Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.parse().map(Id::from_uuid_b64)
+    }
+}
+
+impl<T> Display for Id<T> {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        Display::fmt(&self.0, f)
+    }
+}
+
+impl<T> Debug for Id<T> {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        write!(f, "Id({})", self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+    use std::rc::Rc;
+
+    use uuid::Uuid;
+
+    use super::Id;
+    use UuidB64;
+
+    /// Neither `Clone` nor `Send`, to check that `Id` doesn't need them
+    struct User(#[allow(dead_code)] Rc<()>);
+
+    fn assert_send_sync<T: Send + Sync>() {}
+
+    #[test]
+    fn no_bounds_on_the_marker() {
+        assert_send_sync::<Id<User>>();
+        let id = Id::<User>::new();
+        let copy = id;
+        assert_eq!(id, copy);
+        let set: HashSet<_> = vec![id, copy].into_iter().collect();
+        assert_eq!(set.len(), 1);
+    }
+
+    #[test]
+    fn same_text_as_untyped() {
+        let raw: UuidB64 = "sMHuhm9GTxuNi3hJ51287g".parse().unwrap();
+        let id: Id<User> = "sMHuhm9GTxuNi3hJ51287g".parse().unwrap();
+        assert_eq!(id.as_uuid_b64(), raw);
+        assert_eq!(id.to_string(), raw.to_string());
+        assert_eq!(&*id.to_istring(), &*raw.to_istring());
+        assert_eq!(format!("{:?}", id), "Id(sMHuhm9GTxuNi3hJ51287g)");
+        assert!("nope".parse::<Id<User>>().is_err());
+    }
+
+    #[test]
+    fn conversions() {
+        let uuid = Uuid::new_v4();
+        let id = Id::<User>::from(uuid);
+        assert_eq!(id.uuid(), uuid);
+        assert_eq!(Uuid::from(id), uuid);
+        assert_eq!(UuidB64::from(id), UuidB64::from(uuid));
+        assert_eq!(Id::<User>::from(UuidB64::from(uuid)), id);
+    }
+
+    #[test]
+    fn orders_like_untyped() {
+        let a = Id::<User>::new();
+        let b = Id::<User>::new();
+        assert_eq!(a.cmp(&b), a.as_uuid_b64().cmp(&b.as_uuid_b64()));
+    }
+}
@@ -0,0 +1,116 @@
+//! Converting between ULIDs and IDs
+//!
+//! Both are 128 bits, so every ULID is a valid `UuidB64` and the other way
+//! around, with the bytes unchanged. A ULID's 48-bit millisecond timestamp
+//! sits where a v7 UUID keeps its own, so v7 IDs sort the same either way,
+//! but a ULID converted to an ID isn't a v7 UUID: its version and variant
+//! bits are just part of the random payload.
+
+extern crate ulid;
+
+use self::ulid::Ulid;
+
+use errors::ErrorKind;
+use UuidB64;
+
+// Every ID is a valid ULID, so this is `From` rather than `TryFrom`
+impl From<UuidB64> for Ulid {
+    fn from(id: UuidB64) -> Ulid {
+        let mut bytes = [0; 16];
+        bytes.copy_from_slice(id.0.as_bytes());
+        Ulid::from_bytes(bytes)
+    }
+}
+
+impl UuidB64 {
+    /// The ID with the same 128 bits as `ulid`
+    ///
+    /// This stands in for `From<Ulid>`, which can't be implemented here
+    /// because of `UuidB64`'s blanket `From` impl.
+    pub fn from_ulid(ulid: Ulid) -> UuidB64 {
+        UuidB64::from_bytes(ulid.to_bytes())
+    }
+
+    /// Parse the 26-character ULID string form
+    ///
+    /// ```
+    /// # use uuid_b64::UuidB64;
+    /// let id = UuidB64::parse_ulid("01ARZ3NDEKTSV4RRFFQ69G5FAV").unwrap();
+    /// assert_eq!(
+    ///     id.uuid().hyphenated().to_string(),
+    ///     "01563e3a-b5d3-d676-4c61-efb99302bd5b"
+    /// );
+    /// // ULID strings are Crockford base32
+    /// assert_eq!(id.to_base32_crockford(), "01ARZ3NDEKTSV4RRFFQ69G5FAV");
+    /// ```
+    pub fn parse_ulid(s: &str) -> Result<UuidB64, ErrorKind> {
+        // the ulid crate drops the bits past 128 rather than rejecting them
+        match s.bytes().next() {
+            Some(b'0'..=b'7') => {}
+            _ => return Err(ErrorKind::InvalidUlid(s.to_owned())),
+        }
+        Ulid::from_string(s)
+            .map(UuidB64::from_ulid)
+            .map_err(|_| ErrorKind::InvalidUlid(s.to_owned()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ulid::Ulid;
+
+    use UuidB64;
+
+    #[test]
+    fn same_128_bits() {
+        for id in &[
+            UuidB64::NIL,
+            UuidB64::MAX,
+            UuidB64::new(),
+            UuidB64::new_v7(),
+        ] {
+            let ulid = Ulid::from(*id);
+            assert_eq!(&ulid.to_bytes()[..], id.uuid().as_bytes());
+            assert_eq!(UuidB64::from_ulid(ulid), *id);
+            assert_eq!(UuidB64::parse_ulid(&ulid.to_string()).unwrap(), *id);
+            assert_eq!(ulid.to_string(), id.to_base32_crockford());
+        }
+    }
+
+    #[test]
+    fn v7_timestamps_line_up() {
+        let id = UuidB64::new_v7();
+        let millis = u64::from_str_radix(&id.uuid().simple().to_string()[..12], 16).unwrap();
+        assert_eq!(Ulid::from(id).timestamp_ms(), millis);
+    }
+
+    #[test]
+    fn parses_only_ulid_strings() {
+        let id: UuidB64 = "sMHuhm9GTxuNi3hJ51287g".parse().unwrap();
+        assert_eq!(
+            UuidB64::parse_ulid("5GR7Q8CVT69WDRV2VR97KNVF7E").unwrap(),
+            id
+        );
+        assert_eq!(
+            UuidB64::parse_ulid("5gr7q8cvt69wdrv2vr97knvf7e").unwrap(),
+            id
+        );
+        for bad in &[
+            "",
+            "5GR7Q8CVT69WDRV2VR97KNVF7",
+            "5GR7Q8CVT69WDRV2VR97KNVF7EE",
+            // past the 128 bits
+            "8ZZZZZZZZZZZZZZZZZZZZZZZZZ",
+            "5GR7Q8CVT69WDRV2VR97KNVF7U",
+            "sMHuhm9GTxuNi3hJ51287g",
+        ] {
+            assert!(UuidB64::parse_ulid(bad).is_err(), "{}", bad);
+        }
+        assert_eq!(
+            UuidB64::parse_ulid("sMHuhm9GTxuNi3hJ51287g")
+                .unwrap_err()
+                .to_string(),
+            "Invalid ULID: 'sMHuhm9GTxuNi3hJ51287g'"
+        );
+    }
+}
@@ -0,0 +1,85 @@
+//! UniFFI bindings, for Kotlin and Swift apps
+//!
+//! `UuidB64` crosses the FFI boundary as its string form, so in the
+//! generated bindings it's a `String` typealias. The functions here are the
+//! only way for app code to create or check one, which keeps IDs produced
+//! on devices identical to those produced by backend services.
+
+extern crate uniffi;
+
+use std::fmt::{Display, Formatter, Result as FmtResult};
+
+use uuid::Uuid;
+
+use errors::ParseUuidB64Error;
+use UuidB64;
+
+uniffi::custom_type!(UuidB64, String, {
+    lower: |id| id.to_string(),
+    try_lift: |s| s.parse().map_err(|e: ParseUuidB64Error| uniffi::deps::anyhow::anyhow!("{}", e)),
+});
+
+/// Raised to the foreign side when an ID or UUID can't be parsed
+#[derive(Debug, uniffi::Error)]
+#[uniffi(flat_error)]
+pub enum UuidB64Error {
+    Invalid(String),
+}
+
+impl Display for UuidB64Error {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        match *self {
+            UuidB64Error::Invalid(ref msg) => f.write_str(msg),
+        }
+    }
+}
+
+/// Generate a new random ID
+#[uniffi::export]
+pub fn generate_uuid_b64() -> UuidB64 {
+    UuidB64::new()
+}
+
+/// Parse an ID from its base64 form
+#[uniffi::export]
+pub fn parse_uuid_b64(id: String) -> Result<UuidB64, UuidB64Error> {
+    id.parse().map_err(|e| UuidB64Error::Invalid(format!("{}", e)))
+}
+
+/// Parse an ID from a hyphenated UUID
+#[uniffi::export]
+pub fn uuid_b64_from_uuid(uuid: String) -> Result<UuidB64, UuidB64Error> {
+    Uuid::parse_str(&uuid)
+        .map(UuidB64::from)
+        .map_err(|e| UuidB64Error::Invalid(format!("{}", e)))
+}
+
+/// Format an ID as a hyphenated UUID
+#[uniffi::export]
+pub fn uuid_b64_to_uuid(id: UuidB64) -> String {
+    id.uuid().hyphenated().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const UUID: &str = "b0c1ee86-6f46-4f1b-8d8b-7849e75dbcee";
+    const ID: &str = "sMHuhm9GTxuNi3hJ51287g";
+
+    #[test]
+    fn parse_and_convert() {
+        let id = parse_uuid_b64(ID.to_owned()).unwrap();
+        assert_eq!(uuid_b64_to_uuid(id), UUID);
+        assert_eq!(uuid_b64_from_uuid(UUID.to_owned()).unwrap(), id);
+        let generated = generate_uuid_b64();
+        assert_eq!(parse_uuid_b64(generated.to_string()).unwrap(), generated);
+    }
+
+    #[test]
+    fn errors_carry_the_message() {
+        let err = parse_uuid_b64("nope".to_owned()).unwrap_err();
+        assert!(err.to_string().contains("expected 22 characters"), "{}", err);
+        assert!(uuid_b64_from_uuid("nope".to_owned()).is_err());
+    }
+}
@@ -0,0 +1,52 @@
+extern crate utoipa;
+
+use std::borrow::Cow;
+
+use self::utoipa::openapi::schema::{ObjectBuilder, Schema, SchemaFormat, Type};
+use self::utoipa::openapi::RefOr;
+use self::utoipa::{PartialSchema, ToSchema};
+
+use UuidB64;
+
+/// `UuidB64`s are described as 22-character base64 strings, the same way as
+/// the schemars impl, so utoipa documents ID fields, and path and query
+/// parameters (`IntoParams` and `params(...)` use this schema too), as more
+/// than bare strings.
+impl PartialSchema for UuidB64 {
+    fn schema() -> RefOr<Schema> {
+        ObjectBuilder::new()
+            .schema_type(Type::String)
+            .format(Some(SchemaFormat::Custom("uuid-b64".to_owned())))
+            .min_length(Some(22))
+            .max_length(Some(22))
+            .pattern(Some(UuidB64::PATTERN))
+            .examples(["sMHuhm9GTxuNi3hJ51287g"])
+            .into()
+    }
+}
+
+impl ToSchema for UuidB64 {
+    fn name() -> Cow<'static, str> {
+        "UuidB64".into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::utoipa::{PartialSchema, ToSchema};
+
+    use UuidB64;
+
+    #[test]
+    fn schema_is_a_patterned_string() {
+        let schema = serde_json::to_value(UuidB64::schema()).unwrap();
+        assert_eq!(schema["type"], "string");
+        assert_eq!(schema["format"], "uuid-b64");
+        assert_eq!(schema["minLength"], 22);
+        assert_eq!(schema["maxLength"], 22);
+        assert_eq!(schema["pattern"], UuidB64::PATTERN);
+        let example = schema["examples"][0].as_str().unwrap();
+        assert!(example.parse::<UuidB64>().is_ok());
+        assert_eq!(UuidB64::name(), "UuidB64");
+    }
+}
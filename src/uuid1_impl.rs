@@ -0,0 +1,74 @@
+//! Converting to and from version 1 of the uuid crate
+//!
+//! `UuidB64` wraps the `Uuid` of uuid 0.6, which this crate re-exports, but
+//! crates across the ecosystem have moved on to uuid 1. Both are 16 bytes in
+//! the same order, so converting never fails, and `as_uuid1` views an ID as a
+//! uuid 1 `Uuid` without copying it.
+
+extern crate uuid1;
+
+use UuidB64;
+
+impl UuidB64 {
+    /// An ID from a uuid 1 `Uuid`
+    ///
+    /// This stands in for `From<uuid1::Uuid>`, which would overlap with the
+    /// `From` impl for everything that converts into a (uuid 0.6) `Uuid`.
+    pub const fn from_uuid1(uuid: uuid1::Uuid) -> UuidB64 {
+        UuidB64::from_bytes(uuid.into_bytes())
+    }
+
+    /// Copy this ID out as a uuid 1 `Uuid`
+    pub fn to_uuid1(&self) -> uuid1::Uuid {
+        *self.as_uuid1()
+    }
+
+    /// View this ID as a uuid 1 `Uuid`, without copying it
+    ///
+    /// A method rather than `AsRef`, since a second `AsRef` impl would break
+    /// type inference for existing `id.as_ref()` calls whenever this feature
+    /// is on. There's no `Borrow` either: uuid 1 hashes a `Uuid` differently
+    /// from uuid 0.6, so a `HashMap<UuidB64, _>` can't be looked up with one.
+    pub fn as_uuid1(&self) -> &uuid1::Uuid {
+        uuid1::Uuid::from_bytes_ref(self.as_bytes())
+    }
+}
+
+impl From<UuidB64> for uuid1::Uuid {
+    fn from(id: UuidB64) -> uuid1::Uuid {
+        id.to_uuid1()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::uuid1;
+
+    use UuidB64;
+
+    #[test]
+    fn same_bytes() {
+        let id: UuidB64 = "sMHuhm9GTxuNi3hJ51287g".parse().unwrap();
+        let uuid = uuid1::Uuid::parse_str("b0c1ee86-6f46-4f1b-8d8b-7849e75dbcee").unwrap();
+        assert_eq!(id.to_uuid1(), uuid);
+        assert_eq!(uuid1::Uuid::from(id), uuid);
+        assert_eq!(UuidB64::from_uuid1(uuid), id);
+        assert_eq!(*id.as_uuid1(), uuid);
+        assert_eq!(id.as_uuid1().get_version_num(), 4);
+
+        for id in &[UuidB64::NIL, UuidB64::MAX, UuidB64::new_v7()] {
+            assert_eq!(UuidB64::from_uuid1(id.to_uuid1()), *id);
+            assert_eq!(
+                id.to_uuid1().hyphenated().to_string(),
+                id.uuid().hyphenated().to_string()
+            );
+        }
+    }
+
+    #[test]
+    fn orders_the_same() {
+        let (a, b) = (UuidB64::new_v7(), UuidB64::new_v7());
+        assert!(a < b);
+        assert!(a.to_uuid1() < b.to_uuid1());
+    }
+}
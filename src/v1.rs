@@ -0,0 +1,212 @@
+//! Time-based UUIDs with a node ID (versions 1 and 6)
+
+use std::sync::atomic::{AtomicU16, Ordering};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use uuid::Uuid;
+
+use UuidB64;
+
+/// 100ns intervals between the start of the Gregorian calendar (1582-10-15)
+/// and the Unix epoch
+const GREGORIAN_OFFSET: u64 = 0x01B2_1DD2_1381_4000;
+
+/// The 14-bit clock sequence of v1 and v6 UUIDs
+///
+/// The sequence is bumped for every ID, so IDs generated by one node in the
+/// same 100ns tick still differ. Share one `ClockSequence` between
+/// everything generating IDs with the same node ID.
+///
+/// ```
+/// # use uuid_b64::{ClockSequence, UuidB64};
+/// let clock = ClockSequence::new();
+/// let node = [0x9f, 0x6b, 0xde, 0xce, 0xd8, 0x46];
+/// let first = UuidB64::new_v6(&clock, node);
+/// let second = UuidB64::new_v6(&clock, node);
+/// assert_ne!(first, second);
+/// assert_eq!(first.uuid().get_version_num(), 6);
+/// ```
+#[derive(Debug)]
+pub struct ClockSequence(AtomicU16);
+
+impl ClockSequence {
+    /// A clock sequence starting at a random value
+    #[allow(clippy::new_without_default)]
+    pub fn new() -> ClockSequence {
+        let random = Uuid::new_v4();
+        ClockSequence::starting_at(u16::from_be_bytes([
+            random.as_bytes()[0],
+            random.as_bytes()[1],
+        ]))
+    }
+
+    /// A clock sequence starting at `start`, only the low 14 bits are used
+    ///
+    /// For restoring a sequence saved across restarts.
+    pub fn starting_at(start: u16) -> ClockSequence {
+        ClockSequence(AtomicU16::new(start))
+    }
+
+    /// The next value of the sequence
+    fn next(&self) -> u16 {
+        self.0.fetch_add(1, Ordering::Relaxed) & 0x3fff
+    }
+}
+
+impl UuidB64 {
+    /// Generate a new v1 UUID for the node `node_id`
+    ///
+    /// v1 UUIDs don't sort by time, because the low bits of the timestamp
+    /// come first. Prefer [`new_v6`](#method.new_v6) unless something
+    /// requires v1 specifically.
+    pub fn new_v1(clock: &ClockSequence, node_id: [u8; 6]) -> UuidB64 {
+        from_gregorian(1, gregorian_ticks(now()), clock.next(), node_id)
+    }
+
+    /// Generate a new v6 UUID for the node `node_id`
+    ///
+    /// v6 is v1 with the timestamp reordered to be most significant first,
+    /// so IDs sort by creation time.
+    pub fn new_v6(clock: &ClockSequence, node_id: [u8; 6]) -> UuidB64 {
+        from_gregorian(6, gregorian_ticks(now()), clock.next(), node_id)
+    }
+}
+
+impl UuidB64 {
+    /// The Gregorian timestamp embedded in a v1 or v6 UUID, in 100ns
+    /// intervals since 1582-10-15
+    ///
+    /// Returns `None` for other versions.
+    pub(crate) fn gregorian_ticks(&self) -> Option<u64> {
+        let b = self.0.as_bytes();
+        let field = |range: &[u8]| range.iter().fold(0u64, |acc, &b| acc << 8 | u64::from(b));
+        match b[6] >> 4 {
+            1 => Some((field(&b[6..8]) & 0x0fff) << 48 | field(&b[4..6]) << 32 | field(&b[..4])),
+            6 => Some(field(&b[..6]) << 12 | field(&b[6..8]) & 0x0fff),
+            _ => None,
+        }
+    }
+
+    /// The creation time of a v1 or v6 UUID, as a duration since (or, for
+    /// the few possible IDs older than 1970, before) the Unix epoch
+    pub(crate) fn v1_time(&self) -> Option<Result<Duration, Duration>> {
+        self.gregorian_ticks().map(|ticks| {
+            let to_duration = |t: u64| Duration::new(t / 10_000_000, (t % 10_000_000) as u32 * 100);
+            if ticks >= GREGORIAN_OFFSET {
+                Ok(to_duration(ticks - GREGORIAN_OFFSET))
+            } else {
+                Err(to_duration(GREGORIAN_OFFSET - ticks))
+            }
+        })
+    }
+}
+
+fn now() -> Duration {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_else(|_| Duration::from_secs(0))
+}
+
+/// 100ns intervals since the start of the Gregorian calendar
+fn gregorian_ticks(since_epoch: Duration) -> u64 {
+    GREGORIAN_OFFSET
+        + since_epoch.as_secs() * 10_000_000
+        + u64::from(since_epoch.subsec_nanos() / 100)
+}
+
+/// Lay out a 60-bit Gregorian timestamp as a v1 or v6 UUID
+fn from_gregorian(version: u8, ticks: u64, clock_seq: u16, node_id: [u8; 6]) -> UuidB64 {
+    let mut bytes = [0; 16];
+    if version == 1 {
+        bytes[..4].copy_from_slice(&(ticks as u32).to_be_bytes());
+        bytes[4..6].copy_from_slice(&((ticks >> 32) as u16).to_be_bytes());
+        bytes[6..8].copy_from_slice(&((ticks >> 48) as u16 & 0x0fff).to_be_bytes());
+    } else {
+        bytes[..4].copy_from_slice(&((ticks >> 28) as u32).to_be_bytes());
+        bytes[4..6].copy_from_slice(&((ticks >> 12) as u16).to_be_bytes());
+        bytes[6..8].copy_from_slice(&(ticks as u16 & 0x0fff).to_be_bytes());
+    }
+    bytes[6] |= version << 4;
+    bytes[8] = 0x80 | (clock_seq >> 8) as u8 & 0x3f;
+    bytes[9] = clock_seq as u8;
+    bytes[10..].copy_from_slice(&node_id);
+    UuidB64::from_bytes(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::{from_gregorian, gregorian_ticks, ClockSequence};
+    use UuidB64;
+
+    const NODE: [u8; 6] = [0x9f, 0x6b, 0xde, 0xce, 0xd8, 0x46];
+
+    fn hyphenated(id: UuidB64) -> String {
+        id.uuid().hyphenated().to_string()
+    }
+
+    // Test vectors from RFC 9562 appendix A
+    #[test]
+    fn matches_rfc_vectors() {
+        let ticks = 0x01EC_9414_C232_AB00;
+        assert_eq!(
+            hyphenated(from_gregorian(1, ticks, 0x33c8, NODE)),
+            "c232ab00-9414-11ec-b3c8-9f6bdeced846"
+        );
+        assert_eq!(
+            hyphenated(from_gregorian(6, ticks, 0x33c8, NODE)),
+            "1ec9414c-232a-6b00-b3c8-9f6bdeced846"
+        );
+    }
+
+    #[test]
+    fn ticks_from_unix_time() {
+        assert_eq!(
+            gregorian_ticks(Duration::from_secs(0)),
+            0x01B2_1DD2_1381_4000
+        );
+        assert_eq!(
+            gregorian_ticks(Duration::new(1, 250)) - gregorian_ticks(Duration::from_secs(0)),
+            10_000_002
+        );
+    }
+
+    #[test]
+    fn generates_valid_ids() {
+        let clock = ClockSequence::starting_at(0x3fff);
+        let v1 = UuidB64::new_v1(&clock, NODE);
+        let v6 = UuidB64::new_v6(&clock, NODE);
+        assert_eq!(v1.uuid().get_version_num(), 1);
+        assert_eq!(v6.uuid().get_version_num(), 6);
+        for id in &[v1, v6] {
+            assert_eq!(&id.uuid().as_bytes()[10..], &NODE);
+            assert_eq!(id.uuid().as_bytes()[8] >> 6, 0b10);
+        }
+        // the sequence wraps within 14 bits
+        assert_eq!(&v1.uuid().as_bytes()[8..10], &[0xbf, 0xff]);
+        assert_eq!(&v6.uuid().as_bytes()[8..10], &[0x80, 0x00]);
+    }
+
+    #[test]
+    fn extracts_ticks() {
+        let ticks = 0x01EC_9414_C232_AB00;
+        for version in &[1, 6] {
+            let id = from_gregorian(*version, ticks, 0x33c8, NODE);
+            assert_eq!(id.gregorian_ticks(), Some(ticks));
+        }
+        assert_eq!(UuidB64::new().gregorian_ticks(), None);
+        let unix = Duration::new(1_645_557_742, 0);
+        let id = from_gregorian(6, gregorian_ticks(unix), 0, NODE);
+        assert_eq!(id.v1_time(), Some(Ok(unix)));
+        let id = from_gregorian(1, 0, 0, NODE);
+        assert_eq!(id.v1_time(), Some(Err(Duration::new(12_219_292_800, 0))));
+    }
+
+    #[test]
+    fn v6_sorts_by_time() {
+        let earlier = from_gregorian(6, 0x01EC_9414_C232_AB00, 0x3fff, [0xff; 6]);
+        let later = from_gregorian(6, 0x01EC_9414_C232_AB01, 0, [0; 6]);
+        assert!(earlier < later);
+    }
+}
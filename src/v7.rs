@@ -0,0 +1,382 @@
+//! Helpers for time-ordered (version 7) UUIDs
+
+extern crate rand04;
+
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use self::rand04::Rng;
+
+use UuidB64;
+
+/// Number of bits after the 48-bit timestamp that we control: 12 bits of
+/// `rand_a` and 62 bits of `rand_b`
+const TAIL_BITS: u32 = 74;
+const MAX_SUB_MILLIS_BITS: u8 = 12;
+const MAX_COUNTER_BITS: u8 = 42;
+
+const MILLIS_PER_HOUR: u64 = 60 * 60 * 1000;
+const MILLIS_PER_DAY: u64 = 24 * MILLIS_PER_HOUR;
+
+lazy_static! {
+    /// The generator behind `UuidB64::new_v7`, shared so that IDs are
+    /// monotonic across the whole process
+    static ref SHARED: Mutex<V7Generator> = Mutex::new(V7Generator::new());
+}
+
+impl UuidB64 {
+    /// Generate a new time-ordered (v7) UUID
+    ///
+    /// Every ID from this function sorts after the ones created before it in
+    /// this process, even within the same millisecond, thanks to a shared
+    /// [`V7Generator`][]. Use a `V7Generator` directly to choose the counter
+    /// width, or to avoid contending on the shared one.
+    ///
+    /// ```
+    /// # use uuid_b64::UuidB64;
+    /// let first = UuidB64::new_v7();
+    /// let second = UuidB64::new_v7();
+    /// assert!(first < second);
+    /// assert_eq!(first.uuid().get_version_num(), 7);
+    /// ```
+    ///
+    /// [`V7Generator`]: struct.V7Generator.html
+    pub fn new_v7() -> UuidB64 {
+        // The generator's state is valid even if another thread panicked
+        // while holding the lock
+        let mut gen = SHARED.lock().unwrap_or_else(|e| e.into_inner());
+        gen.generate()
+    }
+
+    /// The Unix timestamp in milliseconds embedded in a v7 UUID
+    ///
+    /// Returns `None` if this is not a v7 UUID.
+    pub(crate) fn v7_millis(&self) -> Option<u64> {
+        let bytes = self.0.as_bytes();
+        if bytes[6] >> 4 != 7 {
+            return None;
+        }
+        let mut millis = [0; 8];
+        millis[2..].copy_from_slice(&bytes[..6]);
+        Some(u64::from_be_bytes(millis))
+    }
+
+    /// The creation time of a v7 UUID, truncated to the start of its hour
+    ///
+    /// The result is in milliseconds since the Unix epoch, so it can be used
+    /// directly as a partition key or as the lower bound of an hourly
+    /// partition. Returns `None` for non-v7 UUIDs, which carry no timestamp
+    /// that can be routed on.
+    ///
+    /// ```
+    /// # extern crate uuid;
+    /// # extern crate uuid_b64;
+    /// # use uuid::Uuid;
+    /// # use uuid_b64::UuidB64;
+    /// # fn main() {
+    /// // 2020-09-13T12:26:40.123Z
+    /// let id = UuidB64::from(Uuid::parse_str("0174876e-807b-7000-8000-000000000000").unwrap());
+    /// assert_eq!(id.bucket_by_hour(), Some(1_599_998_400_000)); // 12:00:00
+    /// assert_eq!(UuidB64::new().bucket_by_hour(), None);
+    /// # }
+    /// ```
+    pub fn bucket_by_hour(&self) -> Option<u64> {
+        self.v7_millis().map(|ms| ms - ms % MILLIS_PER_HOUR)
+    }
+
+    /// The creation time of a v7 UUID, truncated to the start of its (UTC) day
+    ///
+    /// Like [`bucket_by_hour`](#method.bucket_by_hour) the result is in
+    /// milliseconds since the Unix epoch, and is `None` for non-v7 UUIDs.
+    pub fn bucket_by_day(&self) -> Option<u64> {
+        self.v7_millis().map(|ms| ms - ms % MILLIS_PER_DAY)
+    }
+}
+
+/// A generator for v7 UUIDs with configurable precision and counter width
+///
+/// Following [RFC 9562 section 6.2][rfc], the 74 bits after the millisecond
+/// timestamp are split into three fields, most significant first:
+///
+/// * `sub_millis_bits` of sub-millisecond timestamp fraction ("method 3"),
+///   for up to ~244ns resolution with all 12 bits.
+/// * `counter_bits` of monotonic counter ("method 1"). When two IDs fall in
+///   the same timestamp tick the counter is incremented, so they still sort
+///   in creation order. If the counter overflows the timestamp is advanced by
+///   one tick instead. Each new tick starts the counter at a random value
+///   with its top bit clear, leaving at least half the space as headroom.
+/// * Whatever is left is random.
+///
+/// More timestamp precision means fewer IDs share a tick, more counter bits
+/// means more IDs can share a tick before the generator has to run ahead of
+/// the clock, and both come out of the random bits. The default is 12 bits
+/// of counter and no sub-millisecond precision.
+///
+/// IDs are only guaranteed to be monotonic per-generator.
+///
+/// ```
+/// # use uuid_b64::V7Generator;
+/// let mut gen = V7Generator::with_precision(12, 18);
+/// let first = gen.generate();
+/// let second = gen.generate();
+/// assert!(first < second);
+/// ```
+///
+/// [rfc]: https://www.rfc-editor.org/rfc/rfc9562#section-6.2
+#[derive(Debug, Clone)]
+pub struct V7Generator {
+    sub_millis_bits: u8,
+    counter_bits: u8,
+    last_tick: u128,
+    counter: u64,
+}
+
+impl V7Generator {
+    /// A generator with a 12-bit counter and millisecond precision
+    #[allow(clippy::new_without_default)]
+    pub fn new() -> V7Generator {
+        V7Generator::with_precision(0, 12)
+    }
+
+    /// A generator with custom sub-millisecond precision and counter width
+    ///
+    /// # Panics
+    ///
+    /// If `sub_millis_bits` is more than 12 or `counter_bits` is more than 42.
+    pub fn with_precision(sub_millis_bits: u8, counter_bits: u8) -> V7Generator {
+        assert!(
+            sub_millis_bits <= MAX_SUB_MILLIS_BITS,
+            "at most {} bits of sub-millisecond precision fit in a v7 UUID",
+            MAX_SUB_MILLIS_BITS
+        );
+        assert!(
+            counter_bits <= MAX_COUNTER_BITS,
+            "counters are limited to {} bits",
+            MAX_COUNTER_BITS
+        );
+        V7Generator {
+            sub_millis_bits,
+            counter_bits,
+            last_tick: 0,
+            counter: 0,
+        }
+    }
+
+    /// Bits of sub-millisecond timestamp precision
+    pub fn sub_millis_bits(&self) -> u8 {
+        self.sub_millis_bits
+    }
+
+    /// Bits of monotonic counter
+    pub fn counter_bits(&self) -> u8 {
+        self.counter_bits
+    }
+
+    /// Generate a new v7 UUID using the current system time
+    pub fn generate(&mut self) -> UuidB64 {
+        self.generate_at(now())
+    }
+
+    /// Generate a new v7 UUID using the current system time, with random
+    /// bits from somewhere other than the OS
+    #[cfg(any(feature = "rand", feature = "test-util"))]
+    pub(crate) fn generate_with(&mut self, random: u128) -> UuidB64 {
+        self.generate_from(now(), random)
+    }
+
+    /// Generate a new v7 UUID as though the current time were `since_epoch`
+    pub(crate) fn generate_at(&mut self, since_epoch: Duration) -> UuidB64 {
+        self.generate_from(since_epoch, random_u128())
+    }
+
+    fn generate_from(&mut self, since_epoch: Duration, random: u128) -> UuidB64 {
+        let sub_bits = u32::from(self.sub_millis_bits);
+        let counter_bits = u32::from(self.counter_bits);
+
+        let millis = since_epoch.as_secs() as u128 * 1000 + u128::from(since_epoch.subsec_millis());
+        let sub_nanos = u128::from(since_epoch.subsec_nanos() % 1_000_000);
+        let tick = (millis << sub_bits) | ((sub_nanos << sub_bits) / 1_000_000);
+
+        let counter_max = if counter_bits == 0 {
+            0
+        } else {
+            u64::MAX >> (64 - counter_bits)
+        };
+        if tick > self.last_tick {
+            self.last_tick = tick;
+            // The counter starts from the top of `random` and the random field
+            // takes the bottom, so they never share bits
+            self.counter = (random >> 64 >> (64 - counter_bits)) as u64 & (counter_max >> 1);
+        } else if self.counter < counter_max {
+            self.counter += 1;
+        } else {
+            self.last_tick += 1;
+            self.counter = 0;
+        }
+
+        let random_bits = TAIL_BITS - sub_bits - counter_bits;
+        let tail = (self.last_tick & ((1 << sub_bits) - 1)) << (counter_bits + random_bits)
+            | u128::from(self.counter) << random_bits
+            | random & ((1 << random_bits) - 1);
+        let millis = (self.last_tick >> sub_bits) & ((1 << 48) - 1);
+
+        let value =
+            millis << 80 | 0x7 << 76 | (tail >> 62) << 64 | 0b10 << 62 | tail & ((1 << 62) - 1);
+        UuidB64::from_bytes(value.to_be_bytes())
+    }
+}
+
+fn now() -> Duration {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_else(|_| Duration::from_secs(0))
+}
+
+/// 128 random bits, none of them fixed like a v4's version and variant
+fn random_u128() -> u128 {
+    let mut bytes = [0; 16];
+    rand04::thread_rng().fill_bytes(&mut bytes);
+    u128::from_be_bytes(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+    use std::thread;
+    use std::time::Duration;
+
+    use uuid::Uuid;
+
+    use super::V7Generator;
+    use UuidB64;
+
+    fn v7_at(millis: u64) -> UuidB64 {
+        let mut bytes = [0xab; 16];
+        bytes[..6].copy_from_slice(&millis.to_be_bytes()[2..]);
+        bytes[6] = 0x7b;
+        bytes[8] = 0x8b;
+        UuidB64::from(Uuid::from_bytes(&bytes).unwrap())
+    }
+
+    #[test]
+    fn extracts_v7_millis() {
+        assert_eq!(
+            v7_at(1_599_999_999_999).v7_millis(),
+            Some(1_599_999_999_999)
+        );
+        assert_eq!(UuidB64::new().v7_millis(), None);
+    }
+
+    #[test]
+    fn buckets_truncate() {
+        // 2020-09-13T12:26:40.123Z
+        let id = v7_at(1_600_000_000_123);
+        assert_eq!(id.bucket_by_hour(), Some(1_599_998_400_000));
+        assert_eq!(id.bucket_by_day(), Some(1_599_955_200_000));
+    }
+
+    #[test]
+    fn ids_in_same_hour_share_a_bucket() {
+        let start = v7_at(1_599_998_400_000);
+        let end = v7_at(1_599_998_400_000 + 60 * 60 * 1000 - 1);
+        let next = v7_at(1_599_998_400_000 + 60 * 60 * 1000);
+        assert_eq!(start.bucket_by_hour(), end.bucket_by_hour());
+        assert_ne!(end.bucket_by_hour(), next.bucket_by_hour());
+        assert_eq!(start.bucket_by_day(), next.bucket_by_day());
+    }
+
+    #[test]
+    fn non_v7_has_no_bucket() {
+        let id = UuidB64::new();
+        assert_eq!(id.bucket_by_hour(), None);
+        assert_eq!(id.bucket_by_day(), None);
+    }
+
+    fn field(id: &UuidB64, start: u32, bits: u32) -> u128 {
+        let value = u128::from_be_bytes(id.sort_key());
+        (value >> (128 - start - bits)) & ((1 << bits) - 1)
+    }
+
+    #[test]
+    fn generates_valid_v7() {
+        let id = V7Generator::new().generate();
+        assert_eq!(id.uuid().get_version_num(), 7);
+        assert_eq!(field(&id, 64, 2), 0b10);
+        assert!(id.v7_millis().unwrap() > 1_600_000_000_000);
+    }
+
+    #[test]
+    fn counter_orders_ids_in_the_same_tick() {
+        let now = Duration::from_millis(1_600_000_000_123);
+        let mut gen = V7Generator::new();
+        let ids: Vec<UuidB64> = (0..1000).map(|_| gen.generate_at(now)).collect();
+        let mut sorted = ids.clone();
+        sorted.sort();
+        assert_eq!(ids, sorted);
+        assert_eq!(ids[0].v7_millis(), Some(1_600_000_000_123));
+    }
+
+    #[test]
+    fn counter_overflow_advances_the_timestamp() {
+        let now = Duration::from_millis(1_600_000_000_123);
+        let mut gen = V7Generator::with_precision(0, 1);
+        let ids: Vec<UuidB64> = (0..4).map(|_| gen.generate_at(now)).collect();
+        for pair in ids.windows(2) {
+            assert!(pair[0] < pair[1]);
+        }
+        assert_eq!(ids[3].v7_millis(), Some(1_600_000_000_124));
+    }
+
+    #[test]
+    fn clock_going_backwards_stays_monotonic() {
+        let mut gen = V7Generator::new();
+        let first = gen.generate_at(Duration::from_millis(1_600_000_000_123));
+        let second = gen.generate_at(Duration::from_millis(1_600_000_000_000));
+        assert!(first < second);
+    }
+
+    #[test]
+    fn sub_millis_precision_is_encoded() {
+        let mut gen = V7Generator::with_precision(12, 0);
+        let id = gen.generate_at(Duration::new(1_600_000_000, 123_500_000));
+        assert_eq!(id.v7_millis(), Some(1_600_000_000_123));
+        // 0.5ms of 4096 ticks
+        assert_eq!(field(&id, 52, 12), 2048);
+    }
+
+    #[test]
+    fn fills_every_random_bit() {
+        let now = Duration::from_millis(1_600_000_000_123);
+        let ids: Vec<UuidB64> = (0..64)
+            .map(|_| V7Generator::with_precision(0, 0).generate_at(now))
+            .collect();
+        // 74 random bits, the top one landing in `rand_a`
+        let top: HashSet<u128> = ids.iter().map(|id| field(id, 52, 1)).collect();
+        assert_eq!(top.len(), 2);
+        // and the two where a v4 has its variant at the end of `rand_a`
+        let low: HashSet<u128> = ids.iter().map(|id| field(id, 62, 2)).collect();
+        assert_eq!(low.len(), 4);
+    }
+
+    #[test]
+    fn new_v7_is_monotonic_across_threads() {
+        let threads: Vec<_> = (0..4)
+            .map(|_| thread::spawn(|| (0..1000).map(|_| UuidB64::new_v7()).collect::<Vec<_>>()))
+            .collect();
+        let mut all = Vec::new();
+        for thread in threads {
+            let ids = thread.join().unwrap();
+            assert!(ids.windows(2).all(|pair| pair[0] < pair[1]));
+            all.extend(ids);
+        }
+        all.sort();
+        all.dedup();
+        assert_eq!(all.len(), 4000);
+    }
+
+    #[test]
+    #[should_panic]
+    fn rejects_oversized_precision() {
+        V7Generator::with_precision(13, 0);
+    }
+}
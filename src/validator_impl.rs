@@ -0,0 +1,64 @@
+extern crate validator;
+
+use std::borrow::Cow;
+
+use self::validator::ValidationError;
+
+use UuidB64;
+
+/// A `validator` custom validation function for strings holding IDs
+///
+/// Use it on `String` (or `&str`) fields that must contain a valid
+/// base64 UUID:
+///
+/// ```rust,ignore
+/// #[derive(Validate)]
+/// struct CreateOrder {
+///     #[validate(custom(function = "uuid_b64::validate_uuid_b64"))]
+///     customer_id: String,
+/// }
+/// ```
+///
+/// Failures have the code `"uuid_b64"`, the offending value as the `value`
+/// param and a human-readable message. Fields typed as `UuidB64` don't need
+/// this: they are already validated by deserialization.
+pub fn validate_uuid_b64<T>(value: &T) -> Result<(), ValidationError>
+where
+    T: AsRef<str> + ?Sized,
+{
+    let value = value.as_ref();
+    if value.parse::<UuidB64>().is_ok() {
+        return Ok(());
+    }
+    let mut error = ValidationError::new("uuid_b64");
+    error.message = Some(Cow::Borrowed("must be a 22-character Base64-encoded UUID"));
+    error.add_param(Cow::Borrowed("value"), &value);
+    Err(error)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::validate_uuid_b64;
+    use UuidB64;
+
+    #[test]
+    fn accepts_ids() {
+        assert!(validate_uuid_b64(&UuidB64::new().to_string()).is_ok());
+        assert!(validate_uuid_b64("sMHuhm9GTxuNi3hJ51287g").is_ok());
+    }
+
+    #[test]
+    fn rejects_garbage() {
+        let error = validate_uuid_b64("b0c1ee86-6f46-4f1b-8d8b-7849e75dbcee").unwrap_err();
+        assert_eq!(error.code, "uuid_b64");
+        assert_eq!(
+            error.message.unwrap(),
+            "must be a 22-character Base64-encoded UUID"
+        );
+        assert_eq!(
+            error.params["value"],
+            "b0c1ee86-6f46-4f1b-8d8b-7849e75dbcee"
+        );
+        assert!(validate_uuid_b64("").is_err());
+    }
+}
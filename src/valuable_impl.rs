@@ -0,0 +1,95 @@
+//! valuable support, for structured logging with tracing
+//!
+//! `tracing::Value` is sealed, so IDs reach tracing through valuable
+//! instead: with tracing's (unstable) `valuable` feature, record them as
+//! `id = tracing::field::valuable(&id)`. Layers that serialize valuables,
+//! like tracing-subscriber's JSON formatter, then write the 22 Base64
+//! characters as a plain string, encoded on the stack rather than through a
+//! `to_string()` per event.
+//!
+//! A `UuidB64` doesn't hold its text form, so it's a `Structable` with one
+//! unnamed field, which serializers treat as a newtype around the string.
+//! A `B64String` is simply a `Value::String`.
+
+extern crate valuable;
+
+use self::valuable::{Fields, StructDef, Structable, Valuable, Value, Visit};
+
+use b64_string::B64String;
+use UuidB64;
+
+impl Valuable for UuidB64 {
+    fn as_value(&self) -> Value<'_> {
+        Value::Structable(self)
+    }
+
+    fn visit(&self, visit: &mut dyn Visit) {
+        let mut buf = [0; 22];
+        visit.visit_unnamed_fields(&[Value::String(self.encode_to(&mut buf))]);
+    }
+}
+
+impl Structable for UuidB64 {
+    fn definition(&self) -> StructDef<'_> {
+        StructDef::new_static("UuidB64", Fields::Unnamed(1))
+    }
+}
+
+impl Valuable for B64String {
+    fn as_value(&self) -> Value<'_> {
+        Value::String(self.as_str())
+    }
+
+    fn visit(&self, visit: &mut dyn Visit) {
+        visit.visit_value(self.as_value());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::valuable::{NamedValues, Structable, Valuable, Value, Visit};
+
+    use UuidB64;
+
+    /// Collects the strings it's shown, however they're nested
+    #[derive(Default)]
+    struct Strings(Vec<String>);
+
+    impl Visit for Strings {
+        fn visit_value(&mut self, value: Value<'_>) {
+            match value {
+                Value::String(s) => self.0.push(s.to_owned()),
+                Value::Structable(s) => s.visit(self),
+                Value::Listable(l) => l.visit(self),
+                _ => panic!("unexpected value"),
+            }
+        }
+
+        fn visit_named_fields(&mut self, _: &NamedValues<'_>) {
+            panic!("IDs have no named fields");
+        }
+
+        fn visit_unnamed_fields(&mut self, values: &[Value<'_>]) {
+            for value in values {
+                self.visit_value(*value);
+            }
+        }
+    }
+
+    #[test]
+    fn visits_the_text_form() {
+        let id = UuidB64::new();
+        let mut strings = Strings::default();
+        id.as_value().visit(&mut strings);
+        id.to_inline().as_value().visit(&mut strings);
+        vec![id, id].as_value().visit(&mut strings);
+        assert_eq!(strings.0, vec![id.to_string(); 4]);
+
+        assert_eq!(id.definition().name(), "UuidB64");
+        assert!(id.definition().fields().is_unnamed());
+        match id.to_inline().as_value() {
+            Value::String(s) => assert_eq!(s, id.to_string()),
+            _ => panic!("B64String is a string"),
+        }
+    }
+}
@@ -0,0 +1,170 @@
+//! Wrappers that guarantee which UUID version an ID is
+
+use std::convert::TryFrom;
+use std::fmt::{Debug, Display, Formatter, Result as FmtResult};
+use std::str::FromStr;
+
+use uuid::Uuid;
+
+use errors::ErrorKind;
+use UuidB64;
+
+macro_rules! versioned_id {
+    ($(#[$attr:meta])* $name:ident, $version:expr) => {
+        $(#[$attr])*
+        #[derive(Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+        pub struct $name(UuidB64);
+
+        impl $name {
+            /// The UUID version that this type guarantees
+            pub const VERSION: usize = $version;
+
+            /// The underlying `UuidB64`
+            pub fn as_uuid_b64(&self) -> UuidB64 {
+                self.0
+            }
+
+            /// Copy the raw UUID out
+            pub fn uuid(&self) -> Uuid {
+                self.0.uuid()
+            }
+        }
+
+        impl TryFrom<UuidB64> for $name {
+            type Error = ErrorKind;
+
+            fn try_from(id: UuidB64) -> Result<Self, Self::Error> {
+                match id.0.get_version_num() {
+                    $version => Ok($name(id)),
+                    found => Err(ErrorKind::WrongVersion($version, found)),
+                }
+            }
+        }
+
+        impl TryFrom<Uuid> for $name {
+            type Error = ErrorKind;
+
+            fn try_from(id: Uuid) -> Result<Self, Self::Error> {
+                $name::try_from(UuidB64(id))
+            }
+        }
+
+        // UuidB64's blanket `From<T: Into<Uuid>>` turns this into a
+        // conversion to UuidB64 as well
+        impl From<$name> for Uuid {
+            fn from(id: $name) -> Self {
+                (id.0).0
+            }
+        }
+
+        impl FromStr for $name {
+            type Err = ErrorKind;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                $name::try_from(s.parse::<UuidB64>()?)
+            }
+        }
+
+        impl Display for $name {
+            fn fmt(&self, f: &mut Formatter) -> FmtResult {
+                Display::fmt(&self.0, f)
+            }
+        }
+
+        impl Debug for $name {
+            fn fmt(&self, f: &mut Formatter) -> FmtResult {
+                write!(f, "{}({})", stringify!($name), self.0)
+            }
+        }
+    };
+}
+
+versioned_id!(
+    /// A `UuidB64` that is guaranteed to be a random (v4) UUID
+    ///
+    /// ```
+    /// # use std::convert::TryFrom;
+    /// # use uuid_b64::{UuidB64, UuidB64V4};
+    /// let id = UuidB64V4::new();
+    /// assert_eq!(id.uuid().get_version_num(), 4);
+    /// assert!(UuidB64V4::try_from(UuidB64::new()).is_ok());
+    /// ```
+    UuidB64V4,
+    4
+);
+
+versioned_id!(
+    /// A `UuidB64` that is guaranteed to be a time-ordered (v7) UUID
+    ///
+    /// Use this where keys *must* be time-ordered, for example for index
+    /// locality, so that a random ID can't be passed in by mistake.
+    ///
+    /// ```
+    /// # use std::convert::TryFrom;
+    /// # use uuid_b64::{UuidB64, UuidB64V7};
+    /// let id = UuidB64V7::new();
+    /// assert_eq!(UuidB64V7::try_from(id.as_uuid_b64()).unwrap(), id);
+    /// assert!(UuidB64V7::try_from(UuidB64::new()).is_err());
+    /// ```
+    UuidB64V7,
+    7
+);
+
+impl UuidB64V4 {
+    /// Generate a new v4 UUID
+    #[allow(clippy::new_without_default)]
+    pub fn new() -> UuidB64V4 {
+        UuidB64V4(UuidB64::new())
+    }
+}
+
+impl UuidB64V7 {
+    /// Generate a new v7 UUID, see [`UuidB64::new_v7`][]
+    ///
+    /// [`UuidB64::new_v7`]: struct.UuidB64.html#method.new_v7
+    #[allow(clippy::new_without_default)]
+    pub fn new() -> UuidB64V7 {
+        UuidB64V7(UuidB64::new_v7())
+    }
+
+    /// The Unix timestamp in milliseconds at which this ID was created
+    pub fn timestamp_millis(&self) -> u64 {
+        self.0.v7_millis().expect("UuidB64V7 is always v7")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::TryFrom;
+
+    use super::{UuidB64V4, UuidB64V7};
+    use errors::ErrorKind;
+    use {UuidB64, V7Generator};
+
+    #[test]
+    fn v4_roundtrips() {
+        let id = UuidB64V4::new();
+        assert_eq!(id.to_string().parse::<UuidB64V4>().unwrap(), id);
+        assert_eq!(format!("{:?}", id), format!("UuidB64V4({})", id));
+        assert_eq!(UuidB64::from(id).to_string(), id.to_string());
+    }
+
+    #[test]
+    fn v7_roundtrips() {
+        let raw = V7Generator::new().generate();
+        let id = UuidB64V7::try_from(raw).unwrap();
+        assert_eq!(id.to_string().parse::<UuidB64V7>().unwrap(), id);
+        assert_eq!(Some(id.timestamp_millis()), raw.v7_millis());
+    }
+
+    #[test]
+    fn rejects_wrong_versions() {
+        let v4 = UuidB64::new();
+        match UuidB64V7::try_from(v4) {
+            Err(ErrorKind::WrongVersion(7, 4)) => {}
+            other => panic!("unexpected result: {:?}", other),
+        }
+        let v7 = V7Generator::new().generate();
+        assert!(v7.to_string().parse::<UuidB64V4>().is_err());
+    }
+}
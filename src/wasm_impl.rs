@@ -0,0 +1,52 @@
+extern crate wasm_bindgen;
+
+use self::wasm_bindgen::convert::TryFromJsValue;
+use self::wasm_bindgen::JsValue;
+
+use UuidB64;
+
+/// IDs are handed to JS as their string form, without going through JSON
+impl From<UuidB64> for JsValue {
+    fn from(id: UuidB64) -> Self {
+        JsValue::from_str(&id.to_istring())
+    }
+}
+
+/// Parse an ID from a JS string
+///
+/// This is wasm-bindgen's equivalent of `TryFrom<JsValue>`, which can't be
+/// implemented here because of `UuidB64`'s blanket `From` impl. On failure
+/// `try_from_js_value` hands back the original value, as it does for
+/// wasm-bindgen's own types.
+impl TryFromJsValue for UuidB64 {
+    fn try_from_js_value_ref(value: &JsValue) -> Option<Self> {
+        value.as_string().and_then(|s| s.parse().ok())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::wasm_bindgen::convert::TryFromJsValue;
+    use super::wasm_bindgen::JsValue;
+
+    use UuidB64;
+
+    fn assert_converts<T: Into<JsValue> + TryFromJsValue>() {}
+
+    // JsValues live on the JS side, so natively only the impls can be checked
+    #[test]
+    fn converts() {
+        assert_converts::<UuidB64>();
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    #[test]
+    fn roundtrips() {
+        let id: UuidB64 = "sMHuhm9GTxuNi3hJ51287g".parse().unwrap();
+        let value = JsValue::from(id);
+        assert_eq!(value.as_string().unwrap(), "sMHuhm9GTxuNi3hJ51287g");
+        assert_eq!(UuidB64::try_from_js_value(value).unwrap(), id);
+        assert!(UuidB64::try_from_js_value(JsValue::from_str("nope")).is_err());
+        assert!(UuidB64::try_from_js_value(JsValue::from(42)).is_err());
+    }
+}
@@ -0,0 +1,145 @@
+//! Path extractors for axum and actix-web with useful rejections
+//!
+//! Both frameworks can already extract a `UuidB64` through `Path`, but a
+//! malformed ID is rejected with their generic deserialization message.
+//! `IdPath` rejects it with a `400 Bad Request` naming the segment that
+//! didn't parse instead:
+//!
+//! ```text
+//! {"error":"invalid_id","message":"Invalid Base64 representation for UUID: 'nope'","value":"nope"}
+//! ```
+
+extern crate serde_json;
+
+use std::error::Error;
+use std::fmt;
+
+use errors::ErrorKind;
+use UuidB64;
+
+/// A `UuidB64` taken from the only parameter of the matched route
+///
+/// ```rust,ignore
+/// // axum
+/// async fn user(IdPath(id): IdPath) -> String {
+///     id.to_string()
+/// }
+/// let app = Router::new().route("/users/{id}", get(user));
+///
+/// // actix-web
+/// #[get("/users/{id}")]
+/// async fn user(IdPath(id): IdPath) -> String {
+///     id.to_string()
+/// }
+/// ```
+///
+/// Routes with more than one parameter should keep using `Path`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct IdPath(pub UuidB64);
+
+/// Why an `IdPath` couldn't be extracted
+///
+/// Both frameworks turn this into a JSON response with an `error` code, a
+/// human readable `message`, and for `Invalid`, the offending `value`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IdRejection {
+    /// The path segment isn't an ID: `400 Bad Request`
+    Invalid(String),
+    /// The route has this many parameters rather than exactly one: `500
+    /// Internal Server Error`, since it's a bug in the route table
+    WrongParamCount(usize),
+}
+
+impl IdRejection {
+    /// The HTTP status code of the response
+    pub fn status(&self) -> u16 {
+        match *self {
+            IdRejection::Invalid(_) => 400,
+            IdRejection::WrongParamCount(_) => 500,
+        }
+    }
+
+    /// The JSON body of the response
+    pub fn body(&self) -> String {
+        let body = match *self {
+            IdRejection::Invalid(ref value) => serde_json::json!({
+                "error": "invalid_id",
+                "message": self.to_string(),
+                "value": value,
+            }),
+            IdRejection::WrongParamCount(_) => serde_json::json!({
+                "error": "bad_route",
+                "message": self.to_string(),
+            }),
+        };
+        body.to_string()
+    }
+}
+
+impl fmt::Display for IdRejection {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            IdRejection::Invalid(ref value) => ErrorKind::ParseError(value.clone()).fmt(f),
+            IdRejection::WrongParamCount(n) => write!(
+                f,
+                "Expected a route with exactly one path parameter, found {}",
+                n
+            ),
+        }
+    }
+}
+
+impl Error for IdRejection {}
+
+/// Parse the values of the matched route's parameters
+pub(crate) fn from_params<'a, I>(params: I) -> Result<IdPath, IdRejection>
+where
+    I: Iterator<Item = &'a str>,
+{
+    let params: Vec<_> = params.collect();
+    match params[..] {
+        [value] => value
+            .parse()
+            .map(IdPath)
+            .map_err(|_| IdRejection::Invalid(value.to_owned())),
+        _ => Err(IdRejection::WrongParamCount(params.len())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::serde_json;
+    use super::{from_params, IdPath, IdRejection};
+
+    #[test]
+    fn parses_one_param() {
+        let found = from_params(vec!["sMHuhm9GTxuNi3hJ51287g"].into_iter()).unwrap();
+        assert_eq!(found, IdPath("sMHuhm9GTxuNi3hJ51287g".parse().unwrap()));
+        assert_eq!(
+            from_params(vec!["nope"].into_iter()),
+            Err(IdRejection::Invalid("nope".into()))
+        );
+        assert_eq!(
+            from_params(vec!["a", "b"].into_iter()),
+            Err(IdRejection::WrongParamCount(2))
+        );
+        assert_eq!(
+            from_params(Vec::new().into_iter()),
+            Err(IdRejection::WrongParamCount(0))
+        );
+    }
+
+    #[test]
+    fn bodies_are_json() {
+        let rejection = IdRejection::Invalid("no\"pe".into());
+        assert_eq!(rejection.status(), 400);
+        let body: serde_json::Value = serde_json::from_str(&rejection.body()).unwrap();
+        assert_eq!(body["error"], "invalid_id");
+        assert_eq!(body["value"], "no\"pe");
+        assert_eq!(
+            body["message"],
+            "Invalid Base64 representation for UUID: 'no\"pe'"
+        );
+        assert_eq!(IdRejection::WrongParamCount(2).status(), 500);
+    }
+}
@@ -0,0 +1,76 @@
+//! Wiping IDs from memory, for IDs used as secrets such as session or
+//! password reset tokens
+
+extern crate zeroize;
+
+use std::ptr;
+use std::sync::atomic::{self, Ordering};
+
+use self::zeroize::{Zeroize, Zeroizing};
+
+use UuidB64;
+
+impl Zeroize for UuidB64 {
+    /// Overwrite the ID with the nil UUID
+    fn zeroize(&mut self) {
+        // the same volatile write and fence that zeroize uses for its own
+        // types, so the write isn't optimized away
+        unsafe { ptr::write_volatile(self, UuidB64::NIL) };
+        atomic::compiler_fence(Ordering::SeqCst);
+    }
+}
+
+impl UuidB64 {
+    /// The Base64 form, in a string that's wiped when it's dropped
+    ///
+    /// `UuidB64` implements `Zeroize`, but as a `Copy` type it can't be
+    /// `ZeroizeOnDrop`: copies are dropped without running any code. Keep
+    /// secret IDs in a `Zeroizing<UuidB64>`, which wipes its one copy on
+    /// drop, and use this for their string form:
+    ///
+    /// ```rust,ignore
+    /// let token = Zeroizing::new(UuidB64::new());
+    /// send_reset_link(&token.to_zeroizing_string());
+    /// // both the ID and the string are wiped by now
+    /// ```
+    pub fn to_zeroizing_string(&self) -> Zeroizing<String> {
+        // encoded on a wiped buffer, into exactly the right capacity so that
+        // no reallocation leaves a copy behind
+        let mut buf = Zeroizing::new([0; 22]);
+        let mut s = String::with_capacity(22);
+        s.push_str(self.encode_to(&mut buf));
+        Zeroizing::new(s)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::zeroize::{Zeroize, ZeroizeOnDrop, Zeroizing};
+
+    use UuidB64;
+
+    fn zeroizes_on_drop<T: ZeroizeOnDrop>(_: &T) {}
+
+    #[test]
+    fn wipes_to_nil() {
+        let mut id = UuidB64::new();
+        id.zeroize();
+        assert_eq!(id, UuidB64::NIL);
+
+        let mut ids = vec![UuidB64::new(); 3];
+        ids.zeroize();
+        assert!(ids.is_empty());
+    }
+
+    #[test]
+    fn zeroizing_wrappers() {
+        let token = Zeroizing::new(UuidB64::new());
+        zeroizes_on_drop(&token);
+        assert!(!token.is_nil());
+
+        let text = token.to_zeroizing_string();
+        zeroizes_on_drop(&text);
+        assert_eq!(*text, token.to_string());
+        assert_eq!(text.capacity(), 22);
+    }
+}